@@ -1,6 +1,7 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use geo::{coord, wkt, BooleanOps, Rect};
-use geo_types::{line_string, polygon, Geometry, Line};
+use geo_types::{line_string, polygon, Coord, Geometry, Line, LineString};
+use klippa::convex::{clip_ring, HalfPlaneClipper};
 use klippa::ClipRect;
 
 // clips line at single point
@@ -34,6 +35,40 @@ fn linestringclip_geo() {
     rect.clip(&g.into(), false);
 }
 
+// densified 100k-vertex line winding in and out of the rect
+fn dense_linestring() -> LineString {
+    (0..100_000)
+        .map(|i| {
+            let t = i as f64 / 100.0;
+            (t % 12.0 - 4.0, 2.0 + 3.0 * (t / 7.0).sin())
+        })
+        .collect()
+}
+
+fn denseclip_klippa(g: &LineString) {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    rect.clip(&Geometry::LineString(g.clone())).unwrap();
+}
+
+// 256-gon circle approximation as convex window
+fn ngon_window() -> Vec<Coord> {
+    (0..256)
+        .map(|i| {
+            let a = i as f64 / 256.0 * std::f64::consts::TAU;
+            coord! {x: a.cos(), y: a.sin()}
+        })
+        .collect()
+}
+
+fn ngon_subject() -> Vec<Coord> {
+    vec![
+        coord! {x: 0., y: -2.},
+        coord! {x: 2., y: -2.},
+        coord! {x: 2., y: 2.},
+        coord! {x: 0., y: 2.},
+    ]
+}
+
 fn polyclip_klippa() {
     let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
     let g = polygon![(x: 1.0, y: 1.0), (x: 5.0, y: 5.0)];
@@ -46,6 +81,20 @@ fn polyclip_geo() {
     rect.intersection(&g);
 }
 
+fn hexagon() -> geo_types::Polygon {
+    wkt!(POLYGON((3. 1.,5. 1.,6. 3.,5. 5.,3. 5.,2. 3.,3. 1.)))
+}
+
+fn hexclip_klippa() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    rect.clip(&Geometry::Polygon(hexagon())).unwrap();
+}
+
+fn hexclip_convex_klippa() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    rect.clip_convex_polygon(&hexagon()).unwrap();
+}
+
 fn polyclip_holes_klippa() {
     let rect = ClipRect::new(1.5, 1.5, 5.0, 5.0);
     let g = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.),(1. 1.,1. 2.9999999999999716,3. 2.9999999999999716,3. 1.,1. 1.)));
@@ -58,23 +107,75 @@ fn polyclip_holes_geo() {
     rect.intersection(&g);
 }
 
+// square with 1024 small holes, half of them within the rect
+fn many_holes() -> geo_types::Polygon {
+    let holes = (0..1024)
+        .map(|i| {
+            let (x, y) = ((i % 32) as f64 * 0.25, (i / 32) as f64 * 0.25);
+            LineString::from(vec![
+                (x + 0.1, y + 0.1),
+                (x + 0.1, y + 0.2),
+                (x + 0.2, y + 0.2),
+                (x + 0.2, y + 0.1),
+                (x + 0.1, y + 0.1),
+            ])
+        })
+        .collect();
+
+    geo_types::Polygon::new(
+        LineString::from(vec![(0., 0.), (8., 0.), (8., 8.), (0., 8.), (0., 0.)]),
+        holes,
+    )
+}
+
+fn polyclip_many_holes_klippa(g: &geo_types::Polygon) {
+    let rect = ClipRect::new(-1.0, -1.0, 4.0, 9.0);
+    rect.clip(&Geometry::Polygon(g.clone())).unwrap();
+}
+
+// sound polygon, which skips the crossing check of the fallback
+#[cfg(feature = "boolean")]
+fn polyclip_many_holes_fallback(g: &geo_types::Polygon) {
+    let rect = ClipRect::new(-1.0, -1.0, 4.0, 9.0);
+    rect.clip_with_fallback(&Geometry::Polygon(g.clone()))
+        .unwrap();
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("lineclip_klippa", |b| b.iter(|| lineclip_klippa()));
-    c.bench_function("lineclip_geo", |b| b.iter(|| lineclip_geo()));
+    c.bench_function("lineclip_klippa", |b| b.iter(lineclip_klippa));
+    c.bench_function("lineclip_geo", |b| b.iter(lineclip_geo));
+
+    c.bench_function("linestringclip_klippa", |b| b.iter(linestringclip_klippa));
+    c.bench_function("linestringclip_geo", |b| b.iter(linestringclip_geo));
+
+    let dense = dense_linestring();
+    c.bench_function("denseclip_klippa", |b| b.iter(|| denseclip_klippa(&dense)));
 
-    c.bench_function("linestringclip_klippa", |b| {
-        b.iter(|| linestringclip_klippa())
+    let (window, subject) = (ngon_window(), ngon_subject());
+    c.bench_function("convexclip_alloc", |b| {
+        b.iter(|| clip_ring(&subject, &window))
     });
-    c.bench_function("linestringclip_geo", |b| b.iter(|| linestringclip_geo()));
+    let mut clipper = HalfPlaneClipper::new();
+    c.bench_function("convexclip_reuse", |b| {
+        b.iter(|| clipper.clip(&subject, &window).unwrap().len())
+    });
+
+    c.bench_function("polyclip_klippa", |b| b.iter(polyclip_klippa));
+    c.bench_function("polyclip_geo", |b| b.iter(polyclip_geo));
+
+    c.bench_function("hexclip_klippa", |b| b.iter(hexclip_klippa));
+    c.bench_function("hexclip_convex_klippa", |b| b.iter(hexclip_convex_klippa));
 
-    c.bench_function("polyclip_klippa", |b| b.iter(|| polyclip_klippa()));
-    c.bench_function("polyclip_geo", |b| b.iter(|| polyclip_geo()));
+    c.bench_function("polyclip_holes_klippa", |b| b.iter(polyclip_holes_klippa));
+    c.bench_function("polyclip_holes_holes_geo", |b| b.iter(polyclip_holes_geo));
 
-    c.bench_function("polyclip_holes_klippa", |b| {
-        b.iter(|| polyclip_holes_klippa())
+    let holes = many_holes();
+    c.bench_function("polyclip_many_holes_klippa", |b| {
+        b.iter(|| polyclip_many_holes_klippa(&holes))
     });
-    c.bench_function("polyclip_holes_holes_geo", |b| {
-        b.iter(|| polyclip_holes_geo())
+    #[cfg(feature = "boolean")]
+    c.bench_function("polyclip_many_holes_fallback", |b| {
+        b.iter(|| polyclip_many_holes_fallback(&holes))
     });
 }
 
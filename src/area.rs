@@ -0,0 +1,87 @@
+use crate::{util, ClipRect, IntersectionBackend, MaybeSync};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use geo_types::{CoordFloat, Geometry, MultiPolygon, Polygon};
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips polygon, returning the area of the input polygon along with each output part and
+    // its area. Attributes can be distributed to the parts by `part_area / original_area`.
+    pub fn clip_with_area_context(&self, g: &Polygon<T>) -> (T, Vec<(Polygon<T>, T)>) {
+        let parts = self
+            .clip_polygon(g)
+            .into_iter()
+            .map(|poly| {
+                let area = polygon_area(&poly);
+                (poly, area)
+            })
+            .collect();
+
+        (polygon_area(g), parts)
+    }
+
+    // Clips geometry, returning the output with its area. The area is absolute, summed over
+    // output polygons with holes subtracted, and zero for output without polygons.
+    pub fn clip_with_area(&self, g: &Geometry<T>) -> (Option<Geometry<T>>, T) {
+        let clipped = self.clip(g);
+        let area = clipped.as_ref().map_or(T::zero(), geometry_area);
+
+        (clipped, area)
+    }
+
+    // Clips polygon, keeping only the output part with the largest area
+    pub fn clip_largest(&self, g: &Polygon<T>) -> Option<Polygon<T>> {
+        self.clip_with_area_context(g)
+            .1
+            .into_iter()
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+            .map(|(poly, _)| poly)
+    }
+
+    // Clips polygon, keeping at most `max_parts` output parts with the largest areas in their
+    // output order. Returns the kept parts with the count of dropped parts.
+    pub fn clip_capped_parts(&self, g: &Polygon<T>, max_parts: usize) -> (MultiPolygon<T>, usize) {
+        let parts = self.clip_with_area_context(g).1;
+
+        let mut order: Vec<usize> = (0..parts.len()).collect();
+        order.sort_by(|&a, &b| {
+            parts[b]
+                .1
+                .partial_cmp(&parts[a].1)
+                .unwrap_or(Ordering::Equal)
+        });
+        order.truncate(max_parts);
+
+        let dropped = parts.len() - order.len();
+        let kept = parts
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| order.contains(i))
+            .map(|(_, (poly, _))| poly)
+            .collect();
+
+        (kept, dropped)
+    }
+}
+
+// Area of polygon exterior minus its holes, independent of ring winding
+fn polygon_area<T: CoordFloat>(poly: &Polygon<T>) -> T {
+    poly.interiors()
+        .iter()
+        .fold(util::signed_area(&poly.exterior().0).abs(), |area, ls| {
+            area - util::signed_area(&ls.0).abs()
+        })
+}
+
+// Summed area of polygons within geometry
+fn geometry_area<T: CoordFloat>(g: &Geometry<T>) -> T {
+    match g {
+        Geometry::Polygon(poly) => polygon_area(poly),
+        Geometry::MultiPolygon(mp) => mp
+            .iter()
+            .fold(T::zero(), |area, poly| area + polygon_area(poly)),
+        Geometry::GeometryCollection(gc) => {
+            gc.iter().fold(T::zero(), |area, g| area + geometry_area(g))
+        }
+        _ => T::zero(),
+    }
+}
@@ -0,0 +1,66 @@
+use crate::geom::LineExt;
+use crate::MaybeSync;
+use geo_types::{Coord, CoordFloat, Line};
+
+// Computes intersections of rect edges with segments during clipping.
+// Edges are axis-aligned. Segments running along the edge do not intersect it. Segment end
+// points on the edge need not be reported, as clipping keeps them by containment.
+pub trait IntersectionBackend<T: CoordFloat>: MaybeSync {
+    fn intersection(&self, edge: &Line<T>, seg: &Line<T>) -> Option<Coord<T>>;
+}
+
+// Plain floating point intersections, see `LineExt::intersection`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FloatIntersection;
+
+impl<T: CoordFloat> IntersectionBackend<T> for FloatIntersection {
+    fn intersection(&self, edge: &Line<T>, seg: &Line<T>) -> Option<Coord<T>> {
+        edge.intersection(seg)
+    }
+}
+
+// Intersections decided by exact orientation predicates from the `robust` crate, for
+// segments passing close to rect corners or edges where rounding could tip the decision.
+// Intersection points are placed exactly on the edge.
+#[cfg(feature = "robust")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RobustIntersection;
+
+#[cfg(feature = "robust")]
+impl<T: CoordFloat> IntersectionBackend<T> for RobustIntersection {
+    fn intersection(&self, edge: &Line<T>, seg: &Line<T>) -> Option<Coord<T>> {
+        use crate::geom::CoordExt;
+        use robust::orient2d;
+
+        if !edge.is_vertical() {
+            let (edge, seg) = (edge.swap_axes(), seg.swap_axes());
+            return self.intersection(&edge, &seg).map(|c| c.yx());
+        }
+
+        if seg.is_vertical() {
+            return None;
+        }
+
+        let c = |c: &Coord<T>| robust::Coord {
+            x: c.x.to_f64().unwrap(),
+            y: c.y.to_f64().unwrap(),
+        };
+        let (a, b, p, q) = (c(&edge.start), c(&edge.end), c(&seg.start), c(&seg.end));
+
+        // segment end points must lie strictly on opposite sides of the edge line, and the edge
+        // end points not strictly on the same side of the segment
+        let same_side = |u: f64, v: f64| (u > 0.0 && v > 0.0) || (u < 0.0 && v < 0.0);
+        let (o_p, o_q) = (orient2d(a, b, p), orient2d(a, b, q));
+        if o_p == 0.0 || o_q == 0.0 || same_side(o_p, o_q) {
+            return None;
+        }
+        if same_side(orient2d(p, q, a), orient2d(p, q, b)) {
+            return None;
+        }
+
+        let x = edge.start.x;
+        let y = seg.start.y + (x - seg.start.x) * seg.slope();
+        let (y0, y1) = (edge.start.y.min(edge.end.y), edge.start.y.max(edge.end.y));
+        Some((x, y.max(y0).min(y1)).into())
+    }
+}
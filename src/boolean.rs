@@ -0,0 +1,108 @@
+use crate::stats::StatsSink;
+use crate::{util, ClipRect, IntersectionBackend, MaybeSync};
+use geo::algorithm::bool_ops::BoolOpsNum;
+use geo::{Area, BooleanOps};
+use geo_types::{CoordFloat, Geometry, LineString, MultiPolygon, Polygon};
+use log::warn;
+
+// Operations delegating to `geo` boolean ops on inputs clipped to the rect first,
+// so the boolean ops only see the geometry within the window.
+impl<T: CoordFloat + MaybeSync + BoolOpsNum, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Returns (a - b) clipped to rect.
+    pub fn clip_difference(&self, a: &Polygon<T>, b: &Polygon<T>) -> MultiPolygon<T> {
+        let a = self.clip_polygon(a);
+        if a.0.is_empty() {
+            return a;
+        }
+
+        let b = self.clip_polygon(b);
+        if b.0.is_empty() {
+            return a;
+        }
+
+        a.difference(&b)
+    }
+
+    // Clips polygons and unions the results, dissolving boundaries shared
+    // between the polygons, including ones produced on the rect boundary.
+    pub fn clip_and_dissolve(&self, polys: &[Polygon<T>]) -> MultiPolygon<T> {
+        polys
+            .iter()
+            .map(|poly| self.clip_polygon(poly))
+            .filter(|mp| !mp.0.is_empty())
+            .reduce(|acc, mp| acc.union(&mp))
+            .unwrap_or_else(|| MultiPolygon::new(vec![]))
+    }
+
+    // Like `clip`, but polygons with unrecoverable clip output (non-finite coordinates or
+    // crossing ring edges) are clipped again with `geo` boolean intersection.
+    // Slower, but only paid for the problematic polygons. Output is checked for crossing edges
+    // only when the clip shows signs of trouble: sewing leaving pieces or holes over, non-finite
+    // coordinates, or more area out than in.
+    pub fn clip_with_fallback(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        let polys = match g {
+            Geometry::Polygon(poly) => vec![poly],
+            Geometry::MultiPolygon(mp) => mp.iter().collect(),
+            _ => return self.clip(g),
+        };
+
+        let polys: Vec<Polygon<T>> = polys
+            .into_iter()
+            .flat_map(|poly| {
+                let stats = StatsSink::default();
+                let clipped = self
+                    .try_clip_polygon(poly, &stats)
+                    .unwrap_or_else(|_| MultiPolygon::new(vec![]));
+
+                let suspect =
+                    stats.is_suspect() || !is_finite(&clipped) || grows_area(poly, &clipped);
+                if !suspect || is_sound(&clipped) {
+                    clipped
+                } else {
+                    warn!("clip output unsound, falling back to boolean intersection");
+                    self.window().intersection(poly)
+                }
+            })
+            .collect();
+
+        match polys.len() {
+            0 => None,
+            1 => polys.into_iter().next().map(Geometry::Polygon),
+            _ => Some(Geometry::MultiPolygon(polys.into())),
+        }
+    }
+
+    fn window(&self) -> Polygon<T> {
+        let r = self.rect();
+        geo_types::Rect::new((r.x0, r.y0), (r.x1, r.y1)).to_polygon()
+    }
+}
+
+fn is_finite<T: CoordFloat>(mp: &MultiPolygon<T>) -> bool {
+    mp.iter()
+        .flat_map(|poly| {
+            poly.exterior()
+                .0
+                .iter()
+                .chain(poly.interiors().iter().flat_map(|ls| &ls.0))
+        })
+        .all(|c| c.x.is_finite() && c.y.is_finite())
+}
+
+// Clip output covering more than the input does, as clipping self-crossing rings can
+fn grows_area<T: CoordFloat + BoolOpsNum>(poly: &Polygon<T>, clipped: &MultiPolygon<T>) -> bool {
+    let (before, after) = (poly.unsigned_area(), clipped.unsigned_area());
+    after - before > T::epsilon().sqrt() * before.max(T::one())
+}
+
+// Finite coordinates and no crossing edges within or between rings of the output polygons
+fn is_sound<T: CoordFloat>(mp: &MultiPolygon<T>) -> bool {
+    let rings: Vec<&LineString<T>> = mp
+        .iter()
+        .flat_map(|poly| [poly.exterior()].into_iter().chain(poly.interiors()))
+        .collect();
+
+    is_finite(mp)
+        && (0..rings.len())
+            .all(|i| (i..rings.len()).all(|j| !util::rings_cross(rings[i], rings[j], i == j)))
+}
@@ -0,0 +1,183 @@
+use crate::{ClipRect, IntersectionBackend, MaybeSync};
+use alloc::vec;
+use alloc::vec::Vec;
+use geo_types::{Coord, CoordFloat, Line, LineString, MultiLineString, Polygon};
+
+// Segment of clipped polygon ring running along the rect boundary.
+// Rings are indexed with 0 being the exterior and 1.. the interiors, and `segment` is the index
+// of the segment within its ring.
+// Edges are indexed as in `Rect::lines`: bottom, right, top, left. The edges wind
+// counter-clockwise, and `forward` tells if the segment travels in the edge direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundarySegment {
+    pub ring: usize,
+    pub segment: usize,
+    pub edge: usize,
+    pub forward: bool,
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips polygon, annotating each output polygon with the segments on the rect boundary.
+    pub fn clip_polygon_boundary(&self, g: &Polygon<T>) -> Vec<(Polygon<T>, Vec<BoundarySegment>)> {
+        self.clip_polygon(g)
+            .into_iter()
+            .map(|poly| {
+                let segments = [poly.exterior()]
+                    .into_iter()
+                    .chain(poly.interiors())
+                    .enumerate()
+                    .flat_map(|(ring, ls)| {
+                        ls.lines().enumerate().filter_map(move |(segment, l)| {
+                            let (edge, forward) = self.boundary_edge(&l)?;
+                            Some(BoundarySegment {
+                                ring,
+                                segment,
+                                edge,
+                                forward,
+                            })
+                        })
+                    })
+                    .collect();
+
+                (poly, segments)
+            })
+            .collect()
+    }
+
+    // Splits clipped polygon rings into (original edges, seam edges on the rect boundary), as
+    // continuous pieces. Original edges lying exactly on the rect boundary count as seam.
+    pub fn clip_edge_classes(&self, g: &Polygon<T>) -> (MultiLineString<T>, MultiLineString<T>) {
+        let mut original = vec![];
+        let mut seam = vec![];
+
+        for poly in self.clip_polygon(g) {
+            for ls in [poly.exterior()].into_iter().chain(poly.interiors()) {
+                for (piece, is_seam) in self.ring_runs(ls) {
+                    if is_seam {
+                        seam.push(piece);
+                    } else {
+                        original.push(piece);
+                    }
+                }
+            }
+        }
+
+        (MultiLineString::new(original), MultiLineString::new(seam))
+    }
+
+    // Clips polygon, returning for each output ring the rect edges its boundary portions run
+    // along, in ring order. Rings are listed exterior first for each output polygon. A ring
+    // entering on the right edge and leaving on the top gives `[1, 2]`.
+    pub fn clip_boundary_traversal(&self, g: &Polygon<T>) -> Vec<Vec<usize>> {
+        self.clip_polygon(g)
+            .iter()
+            .flat_map(|poly| [poly.exterior()].into_iter().chain(poly.interiors()))
+            .map(|ls| {
+                let mut edges: Vec<usize> = vec![];
+                for (piece, _) in self.ring_runs(ls).into_iter().filter(|(_, seam)| *seam) {
+                    for (edge, _) in piece.lines().filter_map(|l| self.boundary_edge(&l)) {
+                        if edges.last() != Some(&edge) {
+                            edges.push(edge);
+                        }
+                    }
+                }
+                edges
+            })
+            .collect()
+    }
+
+    // Clips polygon into TopoJSON style arcs: returns the arc list and arc indices of each ring,
+    // exterior first. Rings are split into arcs where they meet or leave the rect boundary, so
+    // boundary arcs can be shared with adjacent tiles; see `is_boundary_arc`.
+    // Identical arcs are emitted once.
+    pub fn clip_to_arcs(&self, g: &Polygon<T>) -> (Vec<Vec<Coord<T>>>, Vec<Vec<usize>>) {
+        let mut arcs: Vec<Vec<Coord<T>>> = vec![];
+        let mut rings = vec![];
+
+        for poly in self.clip_polygon(g) {
+            for ls in [poly.exterior()].into_iter().chain(poly.interiors()) {
+                let ring = self
+                    .ring_runs(ls)
+                    .into_iter()
+                    .map(
+                        |(piece, _)| match arcs.iter().position(|arc| *arc == piece.0) {
+                            Some(i) => i,
+                            None => {
+                                arcs.push(piece.0);
+                                arcs.len() - 1
+                            }
+                        },
+                    )
+                    .collect();
+
+                rings.push(ring);
+            }
+        }
+
+        (arcs, rings)
+    }
+
+    // Arc runs along the rect boundary
+    pub fn is_boundary_arc(&self, arc: &[Coord<T>]) -> bool {
+        arc.len() > 1
+            && arc
+                .windows(2)
+                .all(|w| self.boundary_edge(&Line::new(w[0], w[1])).is_some())
+    }
+
+    // Splits closed ring into continuous runs of (coordinates, on boundary)
+    fn ring_runs(&self, ls: &LineString<T>) -> Vec<(LineString<T>, bool)> {
+        let lines: Vec<Line<T>> = ls.lines().collect();
+        let is_seam = |i: usize| self.boundary_edge(&lines[i % lines.len()]).is_some();
+
+        // start from a class change, so that no run wraps around the ring end
+        let offset = (0..lines.len())
+            .find(|i| is_seam(*i) != is_seam(i + lines.len() - 1))
+            .unwrap_or(0);
+
+        let mut runs = vec![];
+        let mut run: Vec<Line<T>> = vec![];
+        for i in offset..offset + lines.len() {
+            run.push(lines[i % lines.len()]);
+
+            if i + 1 == offset + lines.len() || is_seam(i) != is_seam(i + 1) {
+                let piece: LineString<T> = run
+                    .iter()
+                    .map(|l| l.start)
+                    .chain(run.last().map(|l| l.end))
+                    .collect();
+
+                runs.push((piece, is_seam(i)));
+                run.clear();
+            }
+        }
+
+        runs
+    }
+
+    // Rect edge the segment lies on with its travel direction
+    pub(crate) fn boundary_edge(&self, l: &Line<T>) -> Option<(usize, bool)> {
+        let r = &self.inner;
+
+        if l.start == l.end {
+            return None;
+        }
+
+        [r.y0, r.x1, r.y1, r.x0]
+            .into_iter()
+            .enumerate()
+            .find(|(i, v)| {
+                if i % 2 == 0 {
+                    r.near(l.start.y, *v) && r.near(l.end.y, *v)
+                } else {
+                    r.near(l.start.x, *v) && r.near(l.end.x, *v)
+                }
+            })
+            .map(|(i, _)| {
+                let edge = r.lines[i];
+                let dot = (l.end.x - l.start.x) * (edge.end.x - edge.start.x)
+                    + (l.end.y - l.start.y) * (edge.end.y - edge.start.y);
+                (i, dot > T::zero())
+            })
+    }
+}
@@ -0,0 +1,337 @@
+use crate::util;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use geo_types::{Coord, CoordFloat, Geometry, Line, LineString, MultiLineString, Polygon};
+
+const DEFAULT_SEGMENTS: usize = 16;
+
+// Circular clipping window.
+// Polygon rings are cut where they cross the circle, and the cut pieces are sewn together with
+// arcs running counter-clockwise along the circle, like rect corners are inserted between exit
+// and entry points when clipping against a rect. Lines are cut to their chords inside the circle.
+pub struct ClipCircle<T: CoordFloat> {
+    center: Coord<T>,
+    radius: T,
+    segments: usize,
+}
+
+// Piece of a ring within the circle, starting and ending on the circle
+struct Chain<T: CoordFloat> {
+    coords: Vec<Coord<T>>,
+    entry: T,
+    exit: T,
+}
+
+impl<T: CoordFloat> ClipCircle<T> {
+    pub fn new(center: Coord<T>, radius: T) -> Self {
+        Self {
+            center,
+            radius: radius.abs(),
+            segments: DEFAULT_SEGMENTS,
+        }
+    }
+
+    // Sets the number of segments approximating each arc sewn along the circle, and the whole
+    // circle when a polygon covers it. Defaults to 16, values below 1 are taken as 1.
+    pub fn with_segments(mut self, segments: usize) -> Self {
+        self.segments = segments.max(1);
+        self
+    }
+
+    pub fn clip(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        use Geometry::*;
+
+        match g {
+            Point(p) => self.contains(&p.0).then_some(Point(*p)),
+            Line(l) => self.clip_segment(l).map(Line),
+            LineString(ls) => lines_geometry(self.clip_linestring(ls)),
+            Polygon(poly) => polygons_geometry(self.clip_polygon(poly)),
+            MultiPoint(mp) => {
+                let points: Vec<_> = mp.iter().filter(|p| self.contains(&p.0)).copied().collect();
+                (!points.is_empty()).then(|| MultiPoint(points.into()))
+            }
+            MultiLineString(mls) => {
+                lines_geometry(mls.iter().flat_map(|ls| self.clip_linestring(ls)).collect())
+            }
+            MultiPolygon(mp) => {
+                polygons_geometry(mp.iter().flat_map(|poly| self.clip_polygon(poly)).collect())
+            }
+            GeometryCollection(gc) => {
+                let members: Vec<_> = gc.iter().filter_map(|g| self.clip(g)).collect();
+                (!members.is_empty()).then(|| GeometryCollection(members.into()))
+            }
+            Rect(r) => polygons_geometry(self.clip_polygon(&r.to_polygon())),
+            Triangle(t) => polygons_geometry(self.clip_polygon(&t.to_polygon())),
+        }
+    }
+
+    // Coordinate is inside the circle or on it
+    pub fn contains(&self, c: &Coord<T>) -> bool {
+        let d = *c - self.center;
+        d.x * d.x + d.y * d.y <= self.radius * self.radius
+    }
+
+    // Chord of the segment inside the circle
+    pub fn clip_segment(&self, l: &Line<T>) -> Option<Line<T>> {
+        let (t0, t1) = self.span(l)?;
+        Some(Line::new(self.at(l, t0), self.at(l, t1)))
+    }
+
+    // Clips linestring into the pieces within the circle
+    pub fn clip_linestring(&self, ls: &LineString<T>) -> Vec<LineString<T>> {
+        let mut pieces: Vec<LineString<T>> = vec![];
+
+        for l in ls.lines().filter_map(|l| self.clip_segment(&l)) {
+            match pieces.last_mut() {
+                Some(piece) if piece.0.last() == Some(&l.start) => piece.0.push(l.end),
+                _ => pieces.push(LineString::new(vec![l.start, l.end])),
+            }
+        }
+
+        pieces
+    }
+
+    // Clips polygon, returning the output parts. Exteriors wind counter-clockwise and holes
+    // clockwise.
+    pub fn clip_polygon(&self, poly: &Polygon<T>) -> Vec<Polygon<T>> {
+        let exterior = oriented(poly.exterior(), true);
+        let interiors: Vec<LineString<T>> = poly
+            .interiors()
+            .iter()
+            .map(|ls| oriented(ls, false))
+            .collect();
+
+        let mut chains = vec![];
+        let mut shells = vec![];
+        let mut holes = vec![];
+        let mut crossed = false;
+
+        for (i, ls) in [&exterior].into_iter().chain(&interiors).enumerate() {
+            match self.ring_chains(ls) {
+                Some(c) if !c.is_empty() => {
+                    crossed = true;
+                    chains.extend(c);
+                }
+                // uncrossed ring inside the circle
+                Some(_) if i == 0 => shells.push(ls.clone()),
+                Some(_) => holes.push(ls.clone()),
+                None => {}
+            }
+        }
+
+        shells.extend(self.sew(chains));
+
+        // circle within the polygon, with no ring crossing it
+        if !crossed && shells.is_empty() {
+            let on_circle = self.center + (self.radius, T::zero()).into();
+            let inside = util::point_in_ring(&on_circle, &exterior).unwrap_or(true)
+                && !interiors
+                    .iter()
+                    .any(|ls| util::point_in_ring(&on_circle, ls).unwrap_or(false));
+
+            if inside {
+                let full = T::from(core::f64::consts::TAU).unwrap();
+                let mut ring = self.arc(T::zero(), full);
+                ring.insert(0, self.on_circle(T::zero()));
+                ring.push(ring[0]);
+                shells.push(LineString::new(ring));
+            }
+        }
+
+        let mut polys: Vec<Polygon<T>> = shells
+            .into_iter()
+            .map(|ls| Polygon::new(ls, vec![]))
+            .collect();
+
+        for hole in holes {
+            if let Some(poly) = polys
+                .iter_mut()
+                .find(|poly| util::point_in_ring(&hole.0[0], poly.exterior()).unwrap_or(false))
+            {
+                poly.interiors_push(hole);
+            }
+        }
+
+        polys
+    }
+
+    // Splits closed ring into pieces within the circle.
+    // Gives an empty list for rings fully inside the circle, and None for rings outside of it.
+    fn ring_chains(&self, ls: &LineString<T>) -> Option<Vec<Chain<T>>> {
+        let lines: Vec<Line<T>> = ls.lines().collect();
+        let spans: Vec<Option<(T, T)>> = lines.iter().map(|l| self.span(l)).collect();
+
+        if spans.iter().all(|s| *s == Some((T::zero(), T::one()))) {
+            return (!lines.is_empty()).then(Vec::new);
+        }
+
+        // start from an entry into the circle, so that no chain wraps around the ring end
+        let start = spans
+            .iter()
+            .position(|s| matches!(s, Some((t0, _)) if *t0 > T::zero()))?;
+
+        let mut chains = vec![];
+        let mut coords: Vec<Coord<T>> = vec![];
+
+        for i in start..start + lines.len() {
+            let (l, Some((t0, t1))) = (&lines[i % lines.len()], spans[i % lines.len()]) else {
+                continue;
+            };
+
+            if t0 > T::zero() || coords.is_empty() {
+                coords.clear();
+                coords.push(self.at(l, t0));
+            }
+
+            if t1 < T::one() {
+                coords.push(self.at(l, t1));
+                chains.push(self.chain(core::mem::take(&mut coords)));
+            } else {
+                coords.push(l.end);
+            }
+        }
+
+        Some(chains)
+    }
+
+    fn chain(&self, coords: Vec<Coord<T>>) -> Chain<T> {
+        let angle = |c: &Coord<T>| (c.y - self.center.y).atan2(c.x - self.center.x);
+
+        Chain {
+            entry: angle(&coords[0]),
+            exit: angle(&coords[coords.len() - 1]),
+            coords,
+        }
+    }
+
+    // Joins chains into closed rings, going counter-clockwise along the circle from each chain
+    // exit to the nearest chain entry
+    fn sew(&self, chains: Vec<Chain<T>>) -> Vec<LineString<T>> {
+        let full = T::from(core::f64::consts::TAU).unwrap();
+        let ccw = |from: T, to: T| {
+            let d = to - from;
+            if d < T::zero() {
+                d + full
+            } else {
+                d
+            }
+        };
+
+        let mut used = vec![false; chains.len()];
+        let mut rings = vec![];
+
+        for first in 0..chains.len() {
+            if used[first] {
+                continue;
+            }
+
+            let mut ring: Vec<Coord<T>> = vec![];
+            let mut j = first;
+            loop {
+                used[j] = true;
+                ring.extend_from_slice(&chains[j].coords);
+
+                let exit = chains[j].exit;
+                let next = (0..chains.len())
+                    .filter(|k| !used[*k] || *k == first)
+                    .min_by(|a, b| {
+                        ccw(exit, chains[*a].entry)
+                            .partial_cmp(&ccw(exit, chains[*b].entry))
+                            .unwrap_or(Ordering::Equal)
+                    })
+                    .unwrap();
+
+                ring.extend(self.arc(exit, ccw(exit, chains[next].entry)));
+
+                if next == first {
+                    break;
+                }
+                j = next;
+            }
+
+            ring.push(ring[0]);
+            rings.push(LineString::new(ring));
+        }
+
+        rings
+    }
+
+    // Points strictly between the ends of the arc starting at angle `from` and turning
+    // counter-clockwise by `delta`
+    fn arc(&self, from: T, delta: T) -> Vec<Coord<T>> {
+        if delta <= T::zero() {
+            return vec![];
+        }
+
+        let n = T::from(self.segments).unwrap();
+        (1..self.segments)
+            .map(|k| self.on_circle(from + delta * T::from(k).unwrap() / n))
+            .collect()
+    }
+
+    fn on_circle(&self, angle: T) -> Coord<T> {
+        self.center + (self.radius * angle.cos(), self.radius * angle.sin()).into()
+    }
+
+    // Parameter range of the segment inside the circle
+    fn span(&self, l: &Line<T>) -> Option<(T, T)> {
+        let d = l.delta();
+        let f = l.start - self.center;
+
+        let a = d.x * d.x + d.y * d.y;
+        let b = f.x * d.x + f.y * d.y;
+        let c = f.x * f.x + f.y * f.y - self.radius * self.radius;
+
+        if a == T::zero() {
+            return (c <= T::zero()).then_some((T::zero(), T::one()));
+        }
+
+        let disc = b * b - a * c;
+        if disc <= T::zero() {
+            return None;
+        }
+
+        let root = disc.sqrt();
+        let t0 = ((-b - root) / a).max(T::zero());
+        let t1 = ((-b + root) / a).min(T::one());
+
+        (t0 < t1).then_some((t0, t1))
+    }
+
+    // Point at parameter t, exact at the segment ends
+    fn at(&self, l: &Line<T>, t: T) -> Coord<T> {
+        if t == T::zero() {
+            l.start
+        } else if t == T::one() {
+            l.end
+        } else {
+            l.start + l.delta() * t
+        }
+    }
+}
+
+// Ring winding counter-clockwise if ccw, clockwise otherwise
+fn oriented<T: CoordFloat>(ls: &LineString<T>, ccw: bool) -> LineString<T> {
+    if (util::signed_area(&ls.0) > T::zero()) != ccw {
+        LineString::new(ls.0.iter().rev().copied().collect())
+    } else {
+        ls.clone()
+    }
+}
+
+fn lines_geometry<T: CoordFloat>(mut pieces: Vec<LineString<T>>) -> Option<Geometry<T>> {
+    match pieces.len() {
+        0 => None,
+        1 => pieces.pop().map(Geometry::LineString),
+        _ => Some(Geometry::MultiLineString(MultiLineString::new(pieces))),
+    }
+}
+
+fn polygons_geometry<T: CoordFloat>(mut polys: Vec<Polygon<T>>) -> Option<Geometry<T>> {
+    match polys.len() {
+        0 => None,
+        1 => polys.pop().map(Geometry::Polygon),
+        _ => Some(Geometry::MultiPolygon(polys.into())),
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{ClipRect, IntersectionBackend, MaybeSync};
+use geo_types::{CoordFloat, Geometry};
+
+// Types wrapping a geometry with metadata, such as SRID or properties.
+// Implementors tell how the geometry is extracted, and how the wrapper is rebuilt around the
+// clipped geometry, so that `ClipRect::clip_feature` carries the metadata over.
+//
+// struct Feature {
+//     srid: u32,
+//     geom: Geometry<f64>,
+// }
+//
+// impl Clippable<f64> for Feature {
+//     fn geometry(&self) -> &Geometry<f64> {
+//         &self.geom
+//     }
+//
+//     fn rebuild(&self, geom: Geometry<f64>) -> Self {
+//         Feature { srid: self.srid, geom }
+//     }
+// }
+pub trait Clippable<T: CoordFloat>: Sized {
+    fn geometry(&self) -> &Geometry<T>;
+    fn rebuild(&self, clipped: Geometry<T>) -> Self;
+}
+
+impl<T: CoordFloat + MaybeSync> Clippable<T> for Geometry<T> {
+    fn geometry(&self) -> &Geometry<T> {
+        self
+    }
+
+    fn rebuild(&self, clipped: Geometry<T>) -> Self {
+        clipped
+    }
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips the geometry of a wrapper type, rebuilding the wrapper around the clipped geometry.
+    // Returns None when nothing of the geometry is left.
+    pub fn clip_feature<C: Clippable<T>>(&self, item: &C) -> Option<C> {
+        self.clip(item.geometry()).map(|g| item.rebuild(g))
+    }
+}
@@ -0,0 +1,333 @@
+use crate::util::{orientation, signed_area};
+use crate::{ClipError, ClipRect, IntersectionBackend, MaybeSync};
+use alloc::vec;
+use alloc::vec::Vec;
+use geo_types::{Coord, CoordFloat, Geometry, Line, LineString, MultiLineString, Polygon};
+
+// Sutherland–Hodgman clipping of a ring against a convex window.
+//
+// Rings are given as open coordinate lists, without repeating the first coordinate at the end.
+// The window is clipped against one edge (half-plane) per pass.
+
+const DEFAULT_MAX_EDGES: usize = 4096;
+
+// Reusable half-plane clipper.
+// Passes ping-pong between two buffers owned by the clipper, so clipping against many-sided
+// windows does not allocate per pass, and the buffers are reused between calls.
+pub struct HalfPlaneClipper<T: CoordFloat> {
+    front: Vec<Coord<T>>,
+    back: Vec<Coord<T>>,
+    max_edges: usize,
+}
+
+impl<T: CoordFloat> Default for HalfPlaneClipper<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: CoordFloat> HalfPlaneClipper<T> {
+    pub fn new() -> Self {
+        Self {
+            front: vec![],
+            back: vec![],
+            max_edges: DEFAULT_MAX_EDGES,
+        }
+    }
+
+    // Sets the maximum number of window edges (passes) accepted by `clip`.
+    pub fn with_max_edges(mut self, max_edges: usize) -> Self {
+        self.max_edges = max_edges;
+        self
+    }
+
+    // Clips subject ring against convex window ring.
+    // Returns None when window has more edges than allowed by max_edges.
+    pub fn clip(&mut self, subject: &[Coord<T>], window: &[Coord<T>]) -> Option<&[Coord<T>]> {
+        if window.len() > self.max_edges {
+            return None;
+        }
+
+        let sign = winding_sign(window);
+
+        self.front.clear();
+        self.front.extend_from_slice(subject);
+
+        for i in 0..window.len() {
+            if self.front.is_empty() {
+                break;
+            }
+
+            let a = window[i];
+            let b = window[(i + 1) % window.len()];
+
+            clip_half_plane(&self.front, &mut self.back, a, b, sign);
+            core::mem::swap(&mut self.front, &mut self.back);
+        }
+
+        Some(&self.front)
+    }
+}
+
+// Clips subject ring against convex window ring, allocating a new buffer for each pass.
+pub fn clip_ring<T: CoordFloat>(subject: &[Coord<T>], window: &[Coord<T>]) -> Vec<Coord<T>> {
+    let sign = winding_sign(window);
+    let mut ring = subject.to_vec();
+
+    for i in 0..window.len() {
+        if ring.is_empty() {
+            break;
+        }
+
+        let mut out = vec![];
+        clip_half_plane(
+            &ring,
+            &mut out,
+            window[i],
+            window[(i + 1) % window.len()],
+            sign,
+        );
+        ring = out;
+    }
+
+    ring
+}
+
+// Inside of the window is left of its edges for counter-clockwise windows
+fn winding_sign<T: CoordFloat>(window: &[Coord<T>]) -> T {
+    let mut closed = window.to_vec();
+    if let Some(first) = window.first() {
+        closed.push(*first);
+    }
+
+    if signed_area(&closed) < T::zero() {
+        -T::one()
+    } else {
+        T::one()
+    }
+}
+
+// Keeps the part of the ring on the inner side of line a-b, writing it into `out`.
+fn clip_half_plane<T: CoordFloat>(
+    ring: &[Coord<T>],
+    out: &mut Vec<Coord<T>>,
+    a: Coord<T>,
+    b: Coord<T>,
+    sign: T,
+) {
+    out.clear();
+
+    let edge = Line::new(a, b);
+    let side = |p: &Coord<T>| sign * orientation(&edge, p);
+
+    for i in 0..ring.len() {
+        let p = ring[i];
+        let q = ring[(i + 1) % ring.len()];
+        let (sp, sq) = (side(&p), side(&q));
+
+        if sp >= T::zero() {
+            out.push(p);
+        }
+
+        // edge crosses the line
+        if (sp > T::zero() && sq < T::zero()) || (sp < T::zero() && sq > T::zero()) {
+            let t = sp / (sp - sq);
+            out.push(p + (q - p) * t);
+        }
+    }
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips polygon with the classic Sutherland–Hodgman passes over the four rect edges, as an
+    // alternative to the sewing in `clip_polygon` for input known to be simple.
+    // Each ring is clipped on its own, so a ring leaving and re-entering the rect stays one
+    // ring, connected along the rect boundary, instead of splitting into parts. Rings clipped
+    // away are dropped, and None is returned when the exterior is.
+    pub fn clip_sutherland_hodgman(&self, g: &Polygon<T>) -> Option<Polygon<T>> {
+        // rect corners counter-clockwise from the bottom left
+        let window: Vec<Coord<T>> = self.inner.lines.iter().map(|l| l.start).collect();
+
+        let ring = |ls: &LineString<T>, exterior: bool| {
+            let open = &ls.0[..ls.0.len().saturating_sub(1)];
+            let mut out = clip_ring(open, &window);
+            if out.len() < 3 {
+                return None;
+            }
+
+            out.push(out[0]);
+            Some(self.orient_ring(LineString::new(out), exterior))
+        };
+
+        Some(Polygon::new(
+            ring(g.exterior(), true)?,
+            g.interiors()
+                .iter()
+                .filter_map(|ls| ring(ls, false))
+                .collect(),
+        ))
+    }
+}
+
+// Clipping window bounded by a convex polygon.
+// Polygons are clipped by Sutherland–Hodgman passes over the window edges, lines by trimming
+// each segment to the inner side of every edge.
+pub struct ClipConvex<T: CoordFloat> {
+    // open counter-clockwise ring
+    window: Vec<Coord<T>>,
+}
+
+impl<T: CoordFloat> ClipConvex<T> {
+    // Window from convex polygon without holes, in either winding. Collinear and repeated
+    // vertices are allowed.
+    pub fn new(poly: &Polygon<T>) -> Result<Self, ClipError> {
+        let mut window: Vec<Coord<T>> = vec![];
+        for c in &poly.exterior().0 {
+            if window.last() != Some(c) {
+                window.push(*c);
+            }
+        }
+        if window.len() > 1 && window.first() == window.last() {
+            window.pop();
+        }
+
+        if !poly.interiors().is_empty() || window.len() < 3 || !is_convex(&window) {
+            return Err(ClipError::NotConvex);
+        }
+
+        if winding_sign(&window) < T::zero() {
+            window.reverse();
+        }
+
+        Ok(Self { window })
+    }
+
+    pub fn clip(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        use Geometry::*;
+
+        match g {
+            Point(p) => self.contains(&p.0).then_some(Point(*p)),
+            Line(l) => self.clip_segment(l).map(Line),
+            LineString(ls) => lines_geometry(self.clip_linestring(ls)),
+            Polygon(poly) => self.clip_polygon(poly).map(Polygon),
+            MultiPoint(mp) => {
+                let points: Vec<_> = mp.iter().filter(|p| self.contains(&p.0)).copied().collect();
+                (!points.is_empty()).then(|| MultiPoint(points.into()))
+            }
+            MultiLineString(mls) => {
+                lines_geometry(mls.iter().flat_map(|ls| self.clip_linestring(ls)).collect())
+            }
+            MultiPolygon(mp) => {
+                let polys: Vec<_> = mp
+                    .iter()
+                    .filter_map(|poly| self.clip_polygon(poly))
+                    .collect();
+                (!polys.is_empty()).then(|| MultiPolygon(polys.into()))
+            }
+            GeometryCollection(gc) => {
+                let members: Vec<_> = gc.iter().filter_map(|g| self.clip(g)).collect();
+                (!members.is_empty()).then(|| GeometryCollection(members.into()))
+            }
+            Rect(r) => self.clip_polygon(&r.to_polygon()).map(Polygon),
+            Triangle(t) => self.clip_polygon(&t.to_polygon()).map(Polygon),
+        }
+    }
+
+    // Coordinate is inside the window or on its boundary
+    pub fn contains(&self, c: &Coord<T>) -> bool {
+        self.edges().all(|edge| orientation(&edge, c) >= T::zero())
+    }
+
+    // Trims segment to the window, keeping the end points inside it exactly
+    pub fn clip_segment(&self, l: &Line<T>) -> Option<Line<T>> {
+        let (mut t0, mut t1) = (T::zero(), T::one());
+
+        for edge in self.edges() {
+            let (sp, sq) = (orientation(&edge, &l.start), orientation(&edge, &l.end));
+
+            if sp < T::zero() && sq < T::zero() {
+                return None;
+            } else if sp < T::zero() {
+                t0 = t0.max(sp / (sp - sq));
+            } else if sq < T::zero() {
+                t1 = t1.min(sp / (sp - sq));
+            }
+        }
+
+        if t0 >= t1 {
+            return None;
+        }
+
+        let at = |t: T| l.start + l.delta() * t;
+        Some(Line::new(
+            if t0 == T::zero() { l.start } else { at(t0) },
+            if t1 == T::one() { l.end } else { at(t1) },
+        ))
+    }
+
+    // Clips linestring into the pieces within the window
+    pub fn clip_linestring(&self, ls: &LineString<T>) -> Vec<LineString<T>> {
+        let mut pieces: Vec<LineString<T>> = vec![];
+
+        for l in ls.lines().filter_map(|l| self.clip_segment(&l)) {
+            match pieces.last_mut() {
+                Some(piece) if piece.0.last() == Some(&l.start) => piece.0.push(l.end),
+                _ => pieces.push(LineString::new(vec![l.start, l.end])),
+            }
+        }
+
+        pieces
+    }
+
+    // Clips polygon rings, dropping holes outside the window
+    pub fn clip_polygon(&self, poly: &Polygon<T>) -> Option<Polygon<T>> {
+        let ring = |ls: &LineString<T>| {
+            let open = &ls.0[..ls.0.len().saturating_sub(1)];
+            let mut out = clip_ring(open, &self.window);
+            if out.len() < 3 {
+                return None;
+            }
+
+            out.push(out[0]);
+            Some(LineString::new(out))
+        };
+
+        Some(Polygon::new(
+            ring(poly.exterior())?,
+            poly.interiors().iter().filter_map(ring).collect(),
+        ))
+    }
+
+    fn edges(&self) -> impl Iterator<Item = Line<T>> + '_ {
+        let n = self.window.len();
+        (0..n).map(move |i| Line::new(self.window[i], self.window[(i + 1) % n]))
+    }
+}
+
+fn lines_geometry<T: CoordFloat>(mut pieces: Vec<LineString<T>>) -> Option<Geometry<T>> {
+    match pieces.len() {
+        0 => None,
+        1 => pieces.pop().map(Geometry::LineString),
+        _ => Some(Geometry::MultiLineString(MultiLineString::new(pieces))),
+    }
+}
+
+// Open ring turns the same way at every vertex and winds around once
+fn is_convex<T: CoordFloat>(ring: &[Coord<T>]) -> bool {
+    let n = ring.len();
+    let (mut left, mut right) = (false, false);
+    let mut turning = T::zero();
+
+    for i in 0..n {
+        let (a, b, c) = (ring[i], ring[(i + 1) % n], ring[(i + 2) % n]);
+        let cross = orientation(&Line::new(a, b), &c);
+        left |= cross > T::zero();
+        right |= cross < T::zero();
+
+        let (u, v) = (b - a, c - b);
+        turning = turning + cross.atan2(u.x * v.x + u.y * v.y);
+    }
+
+    // a star polygon turns the same way, but winds around more than once
+    let pi = T::from(core::f64::consts::PI).unwrap();
+    !(left && right) && (left || right) && turning.abs() < pi * (T::one() + T::one() + T::one())
+}
@@ -0,0 +1,282 @@
+use crate::stats::StatsSink;
+use crate::util::rough_eq;
+use crate::{
+    snap_geometry, util, ClipError, ClipRect, FloatIntersection, IntersectionBackend, MaybeSync,
+    Rect,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use geo_types::{
+    Coord, CoordFloat, Geometry, Line, LineString, MultiLineString, MultiPolygon, Point, Polygon,
+};
+use num_traits::Euclid;
+
+// Clipping window with a rectangular hole.
+// Keeps geometry inside the outer rect but outside the interior of the inner rect.
+// Settings of the outer window apply, and the inner window computes the edge intersections.
+pub struct ExclusionClipRect<T: CoordFloat, B = FloatIntersection> {
+    outer: ClipRect<T, B>,
+    inner: ClipRect<T, B>,
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Excludes inner rect from the window. Inner rect is expected to be within the window.
+    pub fn with_exclusion(self, inner: ClipRect<T, B>) -> ExclusionClipRect<T, B> {
+        ExclusionClipRect { outer: self, inner }
+    }
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ExclusionClipRect<T, B> {
+    pub fn clip(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        let mut clipped = self.clip_excluded(g)?;
+        if let Some(scale) = self.outer.precision {
+            snap_geometry(&mut clipped, scale);
+        }
+        Some(clipped)
+    }
+
+    fn clip_excluded(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        let excluded = |p: &Point<T>| self.inner.rect().coord_inside(&p.0);
+
+        match self.outer.clip(g)? {
+            Geometry::Point(p) => (!excluded(&p)).then_some(Geometry::Point(p)),
+            Geometry::MultiPoint(mp) => Some(Geometry::MultiPoint(
+                mp.into_iter().filter(|p| !excluded(p)).collect(),
+            )),
+            Geometry::Line(l) => self.lines(&[l]),
+            Geometry::LineString(ls) => self.lines(&ls.lines().collect::<Vec<_>>()),
+            Geometry::MultiLineString(mls) => {
+                let pieces: Vec<LineString<T>> = mls
+                    .iter()
+                    .filter_map(|ls| self.lines(&ls.lines().collect::<Vec<_>>()))
+                    .flat_map(|g| match g {
+                        Geometry::LineString(ls) => vec![ls],
+                        Geometry::MultiLineString(mls) => mls.0,
+                        _ => vec![],
+                    })
+                    .collect();
+
+                (!pieces.is_empty())
+                    .then(|| Geometry::MultiLineString(MultiLineString::new(pieces)))
+            }
+            Geometry::Polygon(poly) => self.polygons(MultiPolygon::new(vec![poly])),
+            Geometry::MultiPolygon(mp) => self.polygons(mp),
+            g => Some(g),
+        }
+    }
+
+    // Keeps the parts of already clipped segments outside of the inner rect
+    fn lines(&self, lines: &[Line<T>]) -> Option<Geometry<T>> {
+        let mut pieces: Vec<LineString<T>> = vec![];
+        let mut last: Option<Coord<T>> = None;
+
+        for part in lines.iter().flat_map(|l| self.outside_parts(l)).flatten() {
+            match pieces.last_mut() {
+                Some(ls) if last == Some(part.start) => ls.0.push(part.end),
+                _ => pieces.push(LineString::new(vec![part.start, part.end])),
+            }
+            last = Some(part.end);
+        }
+
+        match pieces.len() {
+            0 => None,
+            1 => pieces.pop().map(Geometry::LineString),
+            _ => Some(Geometry::MultiLineString(MultiLineString::new(pieces))),
+        }
+    }
+
+    fn polygons(&self, mp: MultiPolygon<T>) -> Option<Geometry<T>> {
+        let mut polys: Vec<Polygon<T>> = mp
+            .iter()
+            .flat_map(|poly| {
+                let entered: Vec<bool> = [poly.exterior()]
+                    .into_iter()
+                    .chain(poly.interiors())
+                    .map(|ls| self.enters_inner(ls))
+                    .collect();
+
+                self.polygon_outside(poly, &|ls| self.clip_ring_ccw(ls), &entered)
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        match polys.len() {
+            0 => None,
+            1 => polys.pop().map(Geometry::Polygon),
+            _ => Some(Geometry::MultiPolygon(polys.into())),
+        }
+    }
+
+    // Parts of polygon outside the inner rect, from its rings clipped by the counter-clockwise
+    // ring clipper. `entered` tells which rings reach into the inner rect, exterior first.
+    pub(crate) fn polygon_outside(
+        &self,
+        poly: &Polygon<T>,
+        clip_ccw: &impl Fn(&LineString<T>) -> Vec<LineString<T>>,
+        entered: &[bool],
+    ) -> Result<Vec<Polygon<T>>, ClipError> {
+        let inner = self.inner.rect();
+        let two = T::one() + T::one();
+        let center: Coord<T> = ((inner.x0 + inner.x1) / two, (inner.y0 + inner.y1) / two).into();
+
+        let mut polys = self
+            .outer
+            .try_clip_polygon_with(poly, clip_ccw, &StatsSink::default())?
+            .0;
+
+        // Rings not reaching into the inner rect leave it either fully inside or outside
+        // of the polygon. Fully inside, it becomes a hole.
+        if !entered[0]
+            && util::point_in_ring(&center, poly.exterior()).unwrap_or(false)
+            && !poly
+                .interiors()
+                .iter()
+                .zip(&entered[1..])
+                .any(|(ls, entered)| !entered && util::point_in_ring(&center, ls).unwrap_or(true))
+        {
+            for p in polys.iter_mut() {
+                if util::point_in_ring(&center, p.exterior()).unwrap_or(false) {
+                    p.interiors_push(self.outer.orient_ring(self.hole(), false));
+                    break;
+                }
+            }
+        }
+
+        Ok(polys)
+    }
+
+    // Inner rect as clockwise hole ring
+    fn hole(&self) -> LineString<T> {
+        let r = self.inner.rect();
+        LineString::from(vec![
+            (r.x0, r.y0),
+            (r.x0, r.y1),
+            (r.x1, r.y1),
+            (r.x1, r.y0),
+            (r.x0, r.y0),
+        ])
+    }
+
+    fn enters_inner(&self, ls: &LineString<T>) -> bool {
+        ls.lines()
+            .any(|l| self.outside_parts(&l) != [Some(l), None])
+    }
+
+    // Parts of segment before and after passing through the interior of the inner rect.
+    // Segments only touching the inner rect are outside as a whole.
+    fn outside_parts(&self, l: &Line<T>) -> [Option<Line<T>>; 2] {
+        let r = self.inner.rect();
+        let two = T::one() + T::one();
+
+        match r.segment_span(l) {
+            Some((t0, t1))
+                if t0 < t1 && r.coord_inside(&(l.start + l.delta() * ((t0 + t1) / two))) =>
+            {
+                // boundary points are taken from the inside clip when it has them, so the parts
+                // meet clipped geometry exactly
+                let at = |t: T| snap(r, l.start + l.delta() * t);
+                let (p0, p1) = match r.clip_segment_with(l, &self.inner.backend) {
+                    Some(inside) => (inside.start, inside.end),
+                    None => (at(t0), at(t1)),
+                };
+                [
+                    (t0 > T::zero()).then(|| Line::new(l.start, p0)),
+                    (t1 < T::one()).then(|| Line::new(p1, l.end)),
+                ]
+            }
+            _ => [Some(*l), None],
+        }
+    }
+
+    // Clips counter-clockwise ring to the outside of the inner rect.
+    // Pieces outside are sewn together walking the inner rect perimeter clockwise, keeping the
+    // inner rect on the right hand side.
+    pub(crate) fn clip_ring_ccw(&self, g: &LineString<T>) -> Vec<LineString<T>> {
+        if !self.enters_inner(g) {
+            return vec![g.clone()];
+        }
+
+        let parts: Vec<Line<T>> = g
+            .lines()
+            .flat_map(|l| self.outside_parts(&l))
+            .flatten()
+            .collect();
+
+        self.sew_outside(parts)
+    }
+
+    // Sews the parts of a counter-clockwise ring outside the inner rect, cut at its boundary,
+    // see `clip_ring_ccw`
+    pub(crate) fn sew_outside(&self, parts: Vec<Line<T>>) -> Vec<LineString<T>> {
+        let r = self.inner.rect();
+        let mut pieces: Vec<LineString<T>> = util::group_segments(parts)
+            .into_iter()
+            .map(util::segments_to_linestring)
+            .collect();
+
+        let mut output = vec![];
+
+        while let Some(mut a) = pieces.pop() {
+            loop {
+                // pieces are cut at the inner rect boundary, ends off it are closed as they are
+                let Some(p_end) = r.perimeter_index(a.0.last().unwrap()) else {
+                    a.0.push(a.0[0]);
+                    output.push(util::dedup_coords(&a).into_owned());
+                    break;
+                };
+                let dist = |c: &Coord<T>| {
+                    r.perimeter_index(c)
+                        .map_or(f64::INFINITY, |p| Euclid::rem_euclid(&(p_end - p), &4.0))
+                };
+
+                let next = pieces
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ls)| (i, dist(&ls.0[0])))
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+
+                let (d, join) = match next {
+                    Some((i, d)) if d < dist(&a.0[0]) => (d, Some(i)),
+                    // closing back to a head off the boundary passes no corners
+                    _ => (dist(&a.0[0]), None),
+                };
+                let d = if d.is_finite() { d } else { 0.0 };
+
+                // corners passed walking clockwise from the piece end
+                let mut corners =
+                    r.corner_nodes_between(Euclid::rem_euclid(&(p_end - d), &4.0), p_end);
+                corners.reverse();
+                a.0.extend(corners);
+
+                match join {
+                    Some(i) => {
+                        let b = pieces.remove(i);
+                        a.0.extend(b.0);
+                    }
+                    None => {
+                        a.0.push(a.0[0]);
+                        output.push(util::dedup_coords(&a).into_owned());
+                        break;
+                    }
+                }
+            }
+        }
+
+        output
+    }
+}
+
+// Snaps coordinate computed on the rect boundary exactly onto it
+fn snap<T: CoordFloat>(r: &Rect<T>, c: Coord<T>) -> Coord<T> {
+    let snap_to = |v: T, a: T, b: T| {
+        if rough_eq(v, a) {
+            a
+        } else if rough_eq(v, b) {
+            b
+        } else {
+            v
+        }
+    };
+
+    (snap_to(c.x, r.x0, r.x1), snap_to(c.y, r.y0, r.y1)).into()
+}
@@ -0,0 +1,105 @@
+use crate::{ClipRect, MaybeSync};
+use alloc::vec;
+use alloc::vec::Vec;
+use geo_types::{Coord, CoordFloat, Geometry, LineString, MultiPolygon, Polygon};
+
+// Clipping window in lon/lat degrees.
+// Polygons enclosing a pole wrap all longitudes, so in lon/lat coordinates their exterior runs
+// from -180 to 180 without enclosing anything. Such rings are closed along the pole before
+// clipping, so windows reaching the pole get the polar cap they are covered by.
+pub struct ClipRectGeo<T: CoordFloat> {
+    rect: ClipRect<T>,
+}
+
+impl<T: CoordFloat + MaybeSync> ClipRectGeo<T> {
+    pub fn new(lon0: T, lat0: T, lon1: T, lat1: T) -> Self {
+        Self {
+            rect: ClipRect::new(lon0, lat0, lon1, lat1),
+        }
+    }
+
+    // Window in lon/lat coordinates
+    pub fn rect(&self) -> &ClipRect<T> {
+        &self.rect
+    }
+
+    // Clips geometry, handling polygons enclosing a pole. Other geometries are clipped as is.
+    pub fn clip(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        let polys: Vec<Polygon<T>> = match g {
+            Geometry::Polygon(poly) => self.clip_polygon(poly).0,
+            Geometry::MultiPolygon(mp) => mp.iter().flat_map(|p| self.clip_polygon(p)).collect(),
+            g => return self.rect.clip(g),
+        };
+
+        match polys.len() {
+            0 => None,
+            1 => polys.into_iter().next().map(Geometry::Polygon),
+            _ => Some(Geometry::MultiPolygon(polys.into())),
+        }
+    }
+
+    fn clip_polygon(&self, g: &Polygon<T>) -> MultiPolygon<T> {
+        let Some(cap) = polar_cap(g.exterior()) else {
+            return self.rect.clip_polygon(g);
+        };
+
+        // Unwrapped cap spans 360 degrees of longitude starting from the first vertex, so parts
+        // of it are clipped with the window shifted by a full turn and shifted back.
+        let turn = T::from(360.0).unwrap();
+        let cap = Polygon::new(cap, g.interiors().to_vec());
+        let r = self.rect.rect();
+
+        [-turn, T::zero(), turn]
+            .into_iter()
+            .flat_map(|shift| {
+                let window = ClipRect::new(r.x0 + shift, r.y0, r.x1 + shift, r.y1);
+                let unshift = move |ls: &mut LineString<T>| {
+                    ls.0.iter_mut().for_each(|c| c.x = c.x - shift);
+                };
+
+                window.clip_polygon(&cap).into_iter().map(move |mut poly| {
+                    poly.exterior_mut(unshift);
+                    poly.interiors_mut(|rings| rings.iter_mut().for_each(unshift));
+                    poly
+                })
+            })
+            .collect()
+    }
+}
+
+// Closes ring winding around a pole along the pole latitude, with longitudes unwrapped to be
+// continuous from the first vertex. Returns None for rings not enclosing a pole.
+fn polar_cap<T: CoordFloat>(ls: &LineString<T>) -> Option<LineString<T>> {
+    let (half, turn) = (T::from(180.0).unwrap(), T::from(360.0).unwrap());
+    let first = *ls.0.first()?;
+
+    let mut lon = first.x;
+    let mut coords: Vec<Coord<T>> = vec![first];
+    for w in ls.0.windows(2) {
+        // shortest longitude step, crossing the antimeridian when that is shorter
+        let mut d = w[1].x - w[0].x;
+        if d > half {
+            d = d - turn;
+        } else if d < -half {
+            d = d + turn;
+        }
+
+        lon = lon + d;
+        coords.push((lon, w[1].y).into());
+    }
+
+    if ((lon - first.x).abs() - turn).abs() > half {
+        return None;
+    }
+
+    // pole on the side of the mean latitude
+    let mean = ls.0.iter().fold(T::zero(), |acc, c| acc + c.y) / T::from(ls.0.len()).unwrap();
+    let pole = T::from(90.0).unwrap();
+    let pole = if mean >= T::zero() { pole } else { -pole };
+
+    coords.push((lon, pole).into());
+    coords.push((first.x, pole).into());
+    coords.push(first);
+
+    Some(coords.into())
+}
@@ -0,0 +1,29 @@
+use crate::{ClipError, ClipRect, IntersectionBackend, MaybeSync};
+use geo_types::{CoordFloat, Geometry};
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips geometry given as GeoJSON geometry object, returning the output as GeoJSON geometry.
+    pub fn clip_geojson_str(&self, geojson: &str) -> Result<Option<String>, ClipError> {
+        let parse_error = |e: geojson::Error| ClipError::Parse(e.to_string());
+
+        let input: geojson::Geometry = geojson.parse().map_err(parse_error)?;
+        let g = Geometry::<T>::try_from(input).map_err(parse_error)?;
+
+        Ok(self
+            .try_clip(&g)?
+            .map(|clipped| geojson::Geometry::new(geojson::Value::from(&clipped)).to_string()))
+    }
+
+    // Clips the geometry of a GeoJSON feature, keeping its id, properties and foreign members.
+    // Features without geometry, or clipping to nothing, give None.
+    pub fn clip_geojson(&self, feature: &geojson::Feature) -> Option<geojson::Feature> {
+        let g = Geometry::<T>::try_from(feature.geometry.clone()?).ok()?;
+        let clipped = self.clip(&g)?;
+
+        Some(geojson::Feature {
+            bbox: None,
+            geometry: Some(geojson::Geometry::new(geojson::Value::from(&clipped))),
+            ..feature.clone()
+        })
+    }
+}
@@ -1,6 +1,6 @@
 use crate::rect::Rect;
-use geo_types::{Coord, CoordFloat, Line, LineString, Polygon};
-use log::debug;
+use crate::util;
+use geo_types::{Coord, CoordFloat, Line, LineString, MultiLineString, MultiPolygon, Polygon};
 
 // Coord extension trait
 pub trait CoordExt<T: CoordFloat> {
@@ -32,6 +32,8 @@ impl<T: CoordFloat> CoordExt<T> for Coord<T> {
 // Line extension trait
 pub trait LineExt<T: CoordFloat> {
     fn intersection(&self, other: &Line<T>) -> Option<Coord<T>>;
+    fn overlap(&self, other: &Line<T>) -> Option<Line<T>>;
+    fn contacts(&self, other: &Line<T>) -> [Option<(Coord<T>, T, T)>; 2];
     fn is_vertical(&self) -> bool;
     fn is_ortho(&self) -> bool;
     fn swap_axes(self) -> Self;
@@ -52,10 +54,7 @@ impl<T: CoordFloat> LineExt<T> for Line<T> {
         // If A is not vertical line, invert axes
         if !a.is_vertical() {
             debug!("invert");
-            return a
-                .swap_axes()
-                .intersection(&b.swap_axes())
-                .and_then(|p| Some(p.yx()));
+            return a.swap_axes().intersection(&b.swap_axes()).map(|p| p.yx());
         }
 
         // Check if B is also vertical:
@@ -89,7 +88,95 @@ impl<T: CoordFloat> LineExt<T> for Line<T> {
             return None;
         }
 
-        Some(b.start + (dx_c, dx_c * slope_b).into())
+        // Plain IEEE operations in fixed order: Rust does not contract the multiply-add into FMA
+        // even when the target supports it, so the result is the same on every platform. Pinned
+        // by `test_intersection_unfused`, which CI also runs with FMA enabled.
+        // The intersection lies exactly on A, so a second clip finds it inside.
+        Some(Coord {
+            x: a.start.x,
+            y: b.start.y + dx_c * slope_b,
+        })
+    }
+
+    // Shared part of lines B and A, where A (self) is axis-aligned line, for collinear lines
+    // that `intersection` leaves out. The part is directed as B. Lines sharing only an end
+    // point do not overlap.
+    fn overlap(&self, b: &Self) -> Option<Self> {
+        let a = self;
+
+        if !a.is_ortho() {
+            panic!("non-orthogonal A");
+        }
+
+        if !a.is_vertical() {
+            return a.swap_axes().overlap(&b.swap_axes()).map(|l| l.swap_axes());
+        }
+
+        if !b.is_vertical() || b.start.x != a.start.x {
+            return None;
+        }
+
+        let lo = a.start.y.min(a.end.y).max(b.start.y.min(b.end.y));
+        let hi = a.start.y.max(a.end.y).min(b.start.y.max(b.end.y));
+        if lo >= hi {
+            return None;
+        }
+
+        let (from, to) = if b.start.y <= b.end.y {
+            (lo, hi)
+        } else {
+            (hi, lo)
+        };
+        Some(Line::new((a.start.x, from), (a.start.x, to)))
+    }
+
+    // Points shared by lines A (self) and B in any direction, with the parameter of each point
+    // along A and along B: the crossing or touching point, or the two ends of the shared part of
+    // collinear lines. End points touching the other line are given exactly.
+    fn contacts(&self, b: &Self) -> [Option<(Coord<T>, T, T)>; 2] {
+        let a = self;
+
+        // parameter of point p on line l
+        let along = |l: &Line<T>, p: &Coord<T>| {
+            let d = l.delta();
+            ((p.x - l.start.x) * d.x + (p.y - l.start.y) * d.y) / (d.x * d.x + d.y * d.y)
+        };
+
+        let mut out = [None, None];
+        let mut push = |c: Coord<T>| {
+            if out.iter().flatten().any(|(d, _, _)| *d == c) {
+                return;
+            }
+            if let Some(slot) = out.iter_mut().find(|slot| slot.is_none()) {
+                *slot = Some((c, along(a, &c), along(b, &c)));
+            }
+        };
+
+        // end points on the other line, which give all the contacts of collinear lines
+        for c in [a.start, a.end] {
+            if util::point_on_segment(&c, b) {
+                push(c);
+            }
+        }
+        for c in [b.start, b.end] {
+            if util::point_on_segment(&c, a) {
+                push(c);
+            }
+        }
+
+        if out[0].is_some() {
+            return out;
+        }
+
+        let (o1, o2) = (util::orientation(a, &b.start), util::orientation(a, &b.end));
+        let (o3, o4) = (util::orientation(b, &a.start), util::orientation(b, &a.end));
+
+        if o1 * o2 < T::zero() && o3 * o4 < T::zero() {
+            let t = o3 / (o3 - o4);
+            out[0] = Some((a.start + a.delta() * t, t, o1 / (o1 - o2)));
+        }
+
+        out
     }
 
     fn is_vertical(&self) -> bool {
@@ -121,6 +208,29 @@ impl<T: CoordFloat> Reverse<T> for LineString<T> {
     }
 }
 
+impl<T: CoordFloat> Reverse<T> for MultiLineString<T> {
+    fn reverse(self) -> Self {
+        Self::new(self.into_iter().map(|ls| ls.reverse()).collect())
+    }
+}
+
+// Reverses exterior and interiors, flipping the orientation of all rings
+impl<T: CoordFloat> Reverse<T> for Polygon<T> {
+    fn reverse(self) -> Self {
+        let (exterior, interiors) = self.into_inner();
+        Self::new(
+            exterior.reverse(),
+            interiors.into_iter().map(|ls| ls.reverse()).collect(),
+        )
+    }
+}
+
+impl<T: CoordFloat> Reverse<T> for MultiPolygon<T> {
+    fn reverse(self) -> Self {
+        Self::new(self.into_iter().map(|poly| poly.reverse()).collect())
+    }
+}
+
 pub trait PolygonExt<T: CoordFloat> {
     fn put_hole(&mut self, ls: LineString<T>, rect: &Rect<T>);
 }
@@ -136,9 +246,13 @@ impl<T: CoordFloat> PolygonExt<T> for Polygon<T> {
             self.exterior_mut(|ext| {
                 let start = ls.0[0];
                 let end = ls.0[ls.0.len() - 1];
-                let num_corners = rect
-                    .corner_nodes_between(rect.perimeter_index(&start), rect.perimeter_index(&end))
-                    .len();
+                let (Some(p_start), Some(p_end)) =
+                    (rect.perimeter_index(&start), rect.perimeter_index(&end))
+                else {
+                    debug!("cut hole ends off perimeter");
+                    return;
+                };
+                let num_corners = rect.corner_nodes_between(p_start, p_end).len();
 
                 debug!("{start:?} -> {end:?} corners={num_corners}");
 
@@ -0,0 +1,72 @@
+use crate::{util, ClipRect, IntersectionBackend, MaybeSync, Relation};
+use geo_types::{CoordFloat, Geometry};
+
+// Previous clip of a geometry, kept between frames by `clip_with_hysteresis`.
+pub struct ClipMemo<T: CoordFloat> {
+    // window bounds the result was clipped with
+    bounds: [T; 4],
+
+    // relation to the margin-expanded window at the time of clipping
+    relation: Relation,
+
+    clipped: Option<Geometry<T>>,
+}
+
+impl<T: CoordFloat + MaybeSync> ClipMemo<T> {
+    // Clipped geometry held by the memo
+    pub fn clipped(&self) -> Option<&Geometry<T>> {
+        self.clipped.as_ref()
+    }
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips geometry, reusing the previous result from `memo` if none of the window bounds moved
+    // more than `margin` since then and the geometry's relation (inside, crossing, outside) to
+    // the window expanded by `margin` is unchanged. Avoids flicker of geometries near the
+    // window edges when viewport moves in small steps.
+    pub fn clip_with_hysteresis(
+        &self,
+        g: &Geometry<T>,
+        margin: T,
+        memo: &mut Option<ClipMemo<T>>,
+    ) -> Option<Geometry<T>> {
+        let r = self.rect();
+        let bounds = [r.x0, r.y0, r.x1, r.y1];
+        let relation = self.box_relation(g, margin);
+
+        let reusable = memo.as_ref().is_some_and(|m| {
+            m.relation == relation
+                && m.bounds
+                    .iter()
+                    .zip(bounds.iter())
+                    .all(|(a, b)| (*a - *b).abs() < margin)
+        });
+
+        if !reusable {
+            *memo = Some(ClipMemo {
+                bounds,
+                relation,
+                clipped: self.clip(g),
+            });
+        }
+
+        memo.as_ref().and_then(|m| m.clipped.clone())
+    }
+
+    // Relation of the geometry bounding box to the window expanded by margin
+    pub(crate) fn box_relation(&self, g: &Geometry<T>, margin: T) -> Relation {
+        let r = self.rect();
+        let (x0, y0) = (r.x0.min(r.x1) - margin, r.y0.min(r.y1) - margin);
+        let (x1, y1) = (r.x0.max(r.x1) + margin, r.y0.max(r.y1) + margin);
+
+        match util::bounding_box(g) {
+            Some((min, max)) if min.x >= x0 && min.y >= y0 && max.x <= x1 && max.y <= y1 => {
+                Relation::Inside
+            }
+            Some((min, max)) if min.x <= x1 && min.y <= y1 && max.x >= x0 && max.y >= y0 => {
+                Relation::Crossing
+            }
+            _ => Relation::Outside,
+        }
+    }
+}
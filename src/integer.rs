@@ -0,0 +1,345 @@
+use crate::stats::StatsSink;
+use crate::{util, ClipError, ClipRect};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use geo_types::{Coord, Geometry, Line, LineString, MultiLineString, MultiPolygon, Point, Polygon};
+
+// Clipping rectangle for fixed-point integer coordinates, such as MVT tile space.
+//
+// Edge crossings are computed exactly with integer cross products. The only division is done
+// when placing the intersection point, which is rounded to the nearest integer. Coordinates on
+// the crossed rect side are exact.
+//
+// Clipped polygon pieces are sewn together with the float clipper on integer valued f64
+// coordinates. These are exact for coordinates up to 2^53 in magnitude, which bounds the
+// supported range, see `ClipRectI64::MAX_COORD`. Within it the cross products fit in i128.
+pub struct ClipRectI64 {
+    pub x0: i64,
+    pub y0: i64,
+    pub x1: i64,
+    pub y1: i64,
+
+    // float clipper used for sewing
+    sewing: ClipRect<f64>,
+}
+
+// Rational number num / den with positive denominator
+#[derive(Clone, Copy)]
+struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+impl Ratio {
+    fn new(num: i128, den: i128) -> Self {
+        if den < 0 {
+            Self {
+                num: -num,
+                den: -den,
+            }
+        } else {
+            Self { num, den }
+        }
+    }
+
+    // Numerators and denominators are coordinate differences, so the products fit in i128 for
+    // coordinates within `ClipRectI64::MAX_COORD`
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+// Rect side that bounds a segment parameter
+#[derive(Clone, Copy)]
+enum Side {
+    None,
+    X(i64),
+    Y(i64),
+}
+
+impl ClipRectI64 {
+    // Largest coordinate magnitude clipped, for the rect bounds as well as the input. Larger
+    // coordinates are not exact as f64.
+    pub const MAX_COORD: i64 = 1 << 53;
+
+    pub fn new(x0: i64, y0: i64, x1: i64, y1: i64) -> Self {
+        Self {
+            x0,
+            y0,
+            x1,
+            y1,
+            sewing: ClipRect::new(x0 as f64, y0 as f64, x1 as f64, y1 as f64),
+        }
+    }
+
+    fn contains_coord(&self, c: &Coord<i64>) -> bool {
+        self.x0 <= c.x && c.x <= self.x1 && self.y0 <= c.y && c.y <= self.y1
+    }
+
+    pub fn clip_point(&self, p: &Point<i64>) -> Option<Point<i64>> {
+        if self.contains_coord(&p.0) {
+            Some(*p)
+        } else {
+            None
+        }
+    }
+
+    fn in_range(&self, c: &Coord<i64>) -> bool {
+        let max = Self::MAX_COORD;
+        [self.x0, self.y0, self.x1, self.y1, c.x, c.y]
+            .iter()
+            .all(|v| (-max..=max).contains(v))
+    }
+
+    // Liang-Barsky clipping with exact rational segment parameters.
+    // Segments touching the rect at a single point, or out of the supported range, are left out.
+    pub fn clip_segment(&self, seg: &Line<i64>) -> Option<Line<i64>> {
+        if !self.in_range(&seg.start) || !self.in_range(&seg.end) {
+            return None;
+        }
+
+        let (sx, sy) = (seg.start.x as i128, seg.start.y as i128);
+        let (dx, dy) = (seg.dx() as i128, seg.dy() as i128);
+
+        if dx == 0 && dy == 0 {
+            return None;
+        }
+
+        // parameters t where the segment enters and exits the rect: t * p <= q for each side
+        let mut enter = (Ratio::new(0, 1), Side::None);
+        let mut exit = (Ratio::new(1, 1), Side::None);
+
+        for (p, q, side) in [
+            (-dx, sx - self.x0 as i128, Side::X(self.x0)),
+            (dx, self.x1 as i128 - sx, Side::X(self.x1)),
+            (-dy, sy - self.y0 as i128, Side::Y(self.y0)),
+            (dy, self.y1 as i128 - sy, Side::Y(self.y1)),
+        ] {
+            if p == 0 {
+                // parallel to the side and outside of it
+                if q < 0 {
+                    return None;
+                }
+                continue;
+            }
+
+            let t = Ratio::new(q, p);
+            if p < 0 {
+                if t.cmp(&enter.0) == Ordering::Greater {
+                    enter = (t, side);
+                }
+            } else if t.cmp(&exit.0) == Ordering::Less {
+                exit = (t, side);
+            }
+        }
+
+        if enter.0.cmp(&exit.0) != Ordering::Less {
+            return None;
+        }
+
+        Some(Line::new(
+            self.point_at(seg, enter.0, enter.1),
+            self.point_at(seg, exit.0, exit.1),
+        ))
+    }
+
+    // Point along segment at parameter t, snapped exactly onto the bounding side
+    fn point_at(&self, seg: &Line<i64>, t: Ratio, side: Side) -> Coord<i64> {
+        let at = |start: i64, d: i64| start + round_div(d as i128 * t.num, t.den) as i64;
+
+        match side {
+            Side::None => {
+                if t.num == 0 {
+                    seg.start
+                } else {
+                    seg.end
+                }
+            }
+            Side::X(x) => (x, at(seg.start.y, seg.dy())).into(),
+            Side::Y(y) => (at(seg.start.x, seg.dx()), y).into(),
+        }
+    }
+
+    pub fn clip_segments(&self, segments: &[Line<i64>]) -> Vec<Vec<Line<i64>>> {
+        util::group_segments(
+            segments
+                .iter()
+                .filter_map(|seg| self.clip_segment(seg))
+                .collect(),
+        )
+    }
+
+    fn clip_linestring(&self, g: &LineString<i64>) -> MultiLineString<i64> {
+        self.clip_segments(&g.lines().collect::<Vec<Line<i64>>>())
+            .into_iter()
+            .map(|segs| {
+                segs.iter()
+                    .map(|seg| seg.start)
+                    .chain(segs.last().map(|seg| seg.end))
+                    .collect::<LineString<i64>>()
+            })
+            .collect()
+    }
+
+    // Clips counter-clockwise ring given in integer valued f64 coordinates
+    fn clip_ring_ccw(&self, g: &LineString<f64>) -> Vec<LineString<f64>> {
+        let ring = to_i64(g);
+
+        let pieces = self
+            .clip_segments(&ring.lines().collect::<Vec<Line<i64>>>())
+            .into_iter()
+            .map(|segs| util::segments_to_linestring(segs.iter().map(to_f64_line).collect()))
+            .collect();
+
+        self.sewing
+            .sew_ring(pieces, || self.ring_covers_center(&ring))
+    }
+
+    // Crossing test of rect center against ring, in doubled coordinates to keep it integer
+    fn ring_covers_center(&self, ring: &LineString<i64>) -> bool {
+        let cx = self.x0 as i128 + self.x1 as i128;
+        let cy = self.y0 as i128 + self.y1 as i128;
+
+        ring.lines()
+            .filter(|l| {
+                let (ax, ay) = (2 * l.start.x as i128, 2 * l.start.y as i128);
+                let (bx, by) = (2 * l.end.x as i128, 2 * l.end.y as i128);
+
+                if (ay > cy) == (by > cy) {
+                    return false;
+                }
+
+                // side of center relative to edge, oriented upwards
+                let cross = (bx - ax) * (cy - ay) - (by - ay) * (cx - ax);
+                (cross > 0) == (by > ay)
+            })
+            .count()
+            % 2
+            == 1
+    }
+
+    fn clip_polygon(&self, g: &Polygon<i64>) -> MultiPolygon<i64> {
+        let poly = Polygon::new(
+            to_f64(g.exterior()),
+            g.interiors().iter().map(to_f64).collect(),
+        );
+
+        self.sewing
+            .try_clip_polygon_with(&poly, &|ls| self.clip_ring_ccw(ls), &StatsSink::default())
+            .unwrap_or_else(|_| MultiPolygon::new(vec![]))
+            .into_iter()
+            .map(|poly| {
+                Polygon::new(
+                    to_i64(poly.exterior()),
+                    poly.interiors().iter().map(to_i64).collect(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn clip(&self, g: &Geometry<i64>) -> Option<Geometry<i64>> {
+        self.try_clip(g).unwrap_or(None)
+    }
+
+    // Fallible variant of `clip`, reporting coordinates of the rect or the input beyond
+    // `MAX_COORD` as `ClipError::CoordOutOfRange`
+    pub fn try_clip(&self, g: &Geometry<i64>) -> Result<Option<Geometry<i64>>, ClipError> {
+        let mut in_range = self.in_range(&Coord { x: 0, y: 0 });
+        for_each_coord(g, &mut |c| in_range &= self.in_range(c));
+
+        if !in_range {
+            return Err(ClipError::CoordOutOfRange);
+        }
+
+        Ok(self.clip_in_range(g))
+    }
+
+    fn clip_in_range(&self, g: &Geometry<i64>) -> Option<Geometry<i64>> {
+        use Geometry::*;
+
+        match g {
+            Point(g) => self.clip_point(g).map(Point),
+            Line(g) => self.clip_segment(g).map(Line),
+            LineString(g) => {
+                let g = self.clip_linestring(g);
+                if g.0.is_empty() {
+                    None
+                } else if g.0.len() == 1 {
+                    Some(LineString(g.into_iter().next().unwrap()))
+                } else {
+                    Some(MultiLineString(g))
+                }
+            }
+            Polygon(g) => {
+                let g = self.clip_polygon(g);
+                if g.0.is_empty() {
+                    None
+                } else if g.0.len() == 1 {
+                    Some(Polygon(g.into_iter().next().unwrap()))
+                } else {
+                    Some(MultiPolygon(g))
+                }
+            }
+            MultiPoint(g) => Some(MultiPoint(
+                g.iter().filter_map(|p| self.clip_point(p)).collect(),
+            )),
+            MultiLineString(g) => Some(MultiLineString(
+                g.iter().flat_map(|ls| self.clip_linestring(ls)).collect(),
+            )),
+            MultiPolygon(g) => {
+                let polys: Vec<_> = g.iter().flat_map(|poly| self.clip_polygon(poly)).collect();
+                if polys.is_empty() {
+                    None
+                } else {
+                    Some(MultiPolygon(polys.into()))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+// Division rounding half away from zero, for positive denominator
+fn round_div(num: i128, den: i128) -> i128 {
+    if num >= 0 {
+        (num + den / 2) / den
+    } else {
+        -((-num + den / 2) / den)
+    }
+}
+
+fn to_f64_line(l: &Line<i64>) -> Line<f64> {
+    Line::new(
+        (l.start.x as f64, l.start.y as f64),
+        (l.end.x as f64, l.end.y as f64),
+    )
+}
+
+fn to_f64(ls: &LineString<i64>) -> LineString<f64> {
+    ls.0.iter().map(|c| (c.x as f64, c.y as f64)).collect()
+}
+
+// Sewn coordinates are integer valued and within range, so the cast is exact
+fn to_i64(ls: &LineString<f64>) -> LineString<i64> {
+    ls.0.iter().map(|c| (c.x as i64, c.y as i64)).collect()
+}
+
+fn for_each_coord(g: &Geometry<i64>, f: &mut impl FnMut(&Coord<i64>)) {
+    fn poly(p: &Polygon<i64>) -> impl Iterator<Item = &Coord<i64>> {
+        p.exterior().0.iter().chain(p.interiors().iter().flatten())
+    }
+
+    match g {
+        Geometry::Point(p) => f(&p.0),
+        Geometry::Line(l) => [l.start, l.end].iter().for_each(f),
+        Geometry::LineString(ls) => ls.0.iter().for_each(f),
+        Geometry::Polygon(p) => poly(p).for_each(f),
+        Geometry::MultiPoint(mp) => mp.iter().for_each(|p| f(&p.0)),
+        Geometry::MultiLineString(mls) => mls.iter().flatten().for_each(f),
+        Geometry::MultiPolygon(mp) => mp.iter().flat_map(poly).for_each(f),
+        Geometry::GeometryCollection(gc) => gc.iter().for_each(|g| for_each_coord(g, f)),
+        Geometry::Rect(r) => [r.min(), r.max()].iter().for_each(f),
+        Geometry::Triangle(t) => t.to_array().iter().for_each(f),
+    }
+}
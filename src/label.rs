@@ -0,0 +1,67 @@
+use crate::{util, ClipRect, IntersectionBackend, MaybeSync};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use geo_types::{Coord, CoordFloat, Point, Polygon};
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips polygon, pairing each output part with a point inside it for label placement.
+    pub fn clip_with_labels(&self, g: &Polygon<T>) -> Vec<(Polygon<T>, Point<T>)> {
+        self.clip_polygon(g)
+            .into_iter()
+            .map(|poly| {
+                let label = label_point(&poly);
+                (poly, label.into())
+            })
+            .collect()
+    }
+}
+
+// Centroid of the exterior when it falls inside the polygon. Otherwise the middle of the widest
+// interior span on a horizontal line through the centroid.
+fn label_point<T: CoordFloat>(poly: &Polygon<T>) -> Coord<T> {
+    let ext = poly.exterior();
+    let two = T::one() + T::one();
+    let six = two + two + two;
+
+    let area = util::signed_area(&ext.0);
+    let centroid = ext.lines().fold(Coord::zero(), |acc, l| {
+        let cross = l.start.x * l.end.y - l.end.x * l.start.y;
+        acc + (l.start + l.end) * cross
+    }) / (six * area);
+
+    let inside = |c: &Coord<T>| {
+        util::point_in_ring(c, ext) == Some(true)
+            && poly
+                .interiors()
+                .iter()
+                .all(|ls| util::point_in_ring(c, ls) == Some(false))
+    };
+
+    if area != T::zero() && inside(&centroid) {
+        return centroid;
+    }
+
+    let y = if area != T::zero() {
+        centroid.y
+    } else {
+        ext.0[0].y
+    };
+
+    let mut xs: Vec<T> = [ext]
+        .into_iter()
+        .chain(poly.interiors())
+        .flat_map(|ls| ls.lines())
+        .filter(|l| (l.start.y > y) != (l.end.y > y))
+        .map(|l| l.start.x + (y - l.start.y) / (l.end.y - l.start.y) * (l.end.x - l.start.x))
+        .collect();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    xs.chunks_exact(2)
+        .max_by(|a, b| {
+            (a[1] - a[0])
+                .partial_cmp(&(b[1] - b[0]))
+                .unwrap_or(Ordering::Equal)
+        })
+        .map(|span| ((span[0] + span[1]) / two, y).into())
+        .unwrap_or(ext.0[0])
+}
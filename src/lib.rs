@@ -1,86 +1,616 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+// Debug logging through `log`, compiled out without std
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "std")]
+        log::debug!($($arg)*);
+        #[cfg(not(feature = "std"))]
+        let _ = core::format_args!($($arg)*);
+    }};
+}
+
+mod area;
+pub mod backend;
+#[cfg(feature = "boolean")]
+mod boolean;
+pub mod boundary;
+pub mod circle;
+mod clippable;
+pub mod convex;
+pub mod exclusion;
+pub mod geographic;
+#[cfg(feature = "geojson")]
+mod geojson_str;
 pub mod geom;
+pub mod hysteresis;
+pub mod integer;
+mod label;
+mod ordered;
+pub mod oriented;
+pub mod point_index;
+pub mod polygon;
+mod predicate;
 pub mod rect;
+mod split;
+mod stats;
+pub mod stream;
+pub mod tile_geometry;
+#[cfg(feature = "tiles")]
+pub mod tiles;
+mod touching;
+mod trace;
 mod util;
+#[cfg(feature = "validate")]
+pub mod validate;
+#[cfg(feature = "wkt")]
+mod wkt_str;
 
-use geo_types::{CoordFloat, Geometry, Line, LineString, MultiLineString, MultiPolygon, Polygon};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "robust")]
+pub use backend::RobustIntersection;
+pub use backend::{FloatIntersection, IntersectionBackend};
+pub use boundary::BoundarySegment;
+pub use circle::ClipCircle;
+pub use clippable::Clippable;
+pub use convex::ClipConvex;
+use core::cmp::Ordering;
+use core::fmt;
+pub use exclusion::ExclusionClipRect;
+use geo_types::{
+    Coord, CoordFloat, Geometry, Line, LineString, MultiLineString, MultiPolygon, Polygon,
+};
+pub use geographic::ClipRectGeo;
 use geom::{CoordExt, Reverse};
-use log::debug;
+pub use hysteresis::ClipMemo;
+pub use integer::ClipRectI64;
+pub use oriented::OrientedRect;
+pub use point_index::PointIndex;
+pub use polygon::ClipPolygon;
+pub use predicate::{ClipRelation, Relation};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use rect::Perimeter;
 pub use rect::Rect;
+pub use split::Side;
+pub use stats::ClipStats;
+use stats::StatsSink;
+pub use stream::StreamingRingClipper;
+pub use tile_geometry::TileGeometry;
+use trace::SewQueue;
+pub use trace::{SewAction, SewStep};
+
+// Role of a contour in the clipped output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winding {
+    Outer,
+    Hole,
+}
+
+// Winding order of output polygon rings, see `ClipRect::with_winding`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindingOrder {
+    // Exteriors counter-clockwise and holes clockwise, by OGC rules
+    CcwExterior,
+    // Exteriors clockwise and holes counter-clockwise, as in shapefiles
+    CwExterior,
+    // Rings keep the winding of the input rings
+    Preserve,
+}
+
+impl WindingOrder {
+    // Whether an exterior or hole ring is to wind counter-clockwise, None when kept as is
+    fn ccw(self, exterior: bool) -> Option<bool> {
+        match self {
+            WindingOrder::CcwExterior => Some(exterior),
+            WindingOrder::CwExterior => Some(!exterior),
+            WindingOrder::Preserve => None,
+        }
+    }
+}
+
+// Bound for types shared between threads with the `rayon` feature, and for any type without it
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Send + Sync {}
+#[cfg(feature = "rayon")]
+impl<X: Send + Sync> MaybeSync for X {}
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+#[cfg(not(feature = "rayon"))]
+impl<X> MaybeSync for X {}
+
+// Handling of input geometries having NaN coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NanPolicy {
+    // Report `ClipError::NanCoord`
+    Reject,
+    // Skip the geometry, clipping it to nothing
+    Skip,
+    // Replace NaN values with zero before clipping
+    Zero,
+}
+
+// Errors from fallible clipping
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipError {
+    // Interior ring overlaps the rect while no part of the exterior does,
+    // meaning the hole extends beyond its shell. Reported only in strict mode.
+    HoleOutsideShell,
+
+    // Input text could not be parsed into a geometry
+    Parse(String),
+
+    // Input has NaN coordinates, see `NanPolicy`
+    NanCoord,
+
+    // Clipping window has zero width or height without being a point.
+    // Reported by `try_clip`, and by the other clips in strict mode.
+    DegenerateRect,
+
+    // Input has infinite coordinates.
+    // Reported by `try_clip`, and by the other clips in strict mode.
+    InfiniteCoord,
+
+    // Polygon ring has less than 4 coordinates.
+    // Reported by `try_clip`, and by the other clips in strict mode.
+    TooFewPoints,
+
+    // Clipping window polygon is not convex, see `ClipConvex`
+    NotConvex,
+
+    // Integer coordinates beyond the exactly clipped range, see `ClipRectI64::MAX_COORD`
+    CoordOutOfRange,
+}
+
+impl fmt::Display for ClipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipError::HoleOutsideShell => write!(f, "interior ring extends outside exterior"),
+            ClipError::Parse(e) => write!(f, "invalid input: {e}"),
+            ClipError::NanCoord => write!(f, "input has NaN coordinates"),
+            ClipError::DegenerateRect => write!(f, "clipping window has zero width or height"),
+            ClipError::InfiniteCoord => write!(f, "input has infinite coordinates"),
+            ClipError::TooFewPoints => write!(f, "polygon ring has less than 4 coordinates"),
+            ClipError::NotConvex => write!(f, "clipping window is not convex"),
+            ClipError::CoordOutOfRange => write!(f, "coordinates out of the supported range"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ClipError {}
 
 // Abstraction over crate::rect::Rect for handling complex geo types.
-pub struct ClipRect<T: CoordFloat> {
+// Edge intersections are computed by the backend B, see `with_backend`.
+pub struct ClipRect<T: CoordFloat, B = FloatIntersection> {
     inner: Rect<T>,
+    perimeter: Perimeter<T>,
+    strict: bool,
+    join_touching: bool,
+    degenerate_as_lines: bool,
+    precision: Option<T>,
+    winding: WindingOrder,
+    check_holes: bool,
+    nan_policy: NanPolicy,
+    backend: B,
 }
 
-impl<T: CoordFloat> ClipRect<T> {
+impl<T: CoordFloat + MaybeSync> ClipRect<T> {
     pub fn new(x0: T, y0: T, x1: T, y1: T) -> Self {
+        let inner = Rect::new(x0, y0, x1, y1);
+
         Self {
-            inner: Rect::new(x0, y0, x1, y1),
+            perimeter: Perimeter::new(&inner),
+            inner,
+            strict: false,
+            join_touching: false,
+            degenerate_as_lines: false,
+            precision: None,
+            winding: WindingOrder::Preserve,
+            check_holes: false,
+            nan_policy: NanPolicy::Reject,
+            backend: FloatIntersection,
+        }
+    }
+}
+
+impl<T: CoordFloat + MaybeSync> From<geo_types::Rect<T>> for ClipRect<T> {
+    fn from(r: geo_types::Rect<T>) -> Self {
+        Self::new(r.min().x, r.min().y, r.max().x, r.max().y)
+    }
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Replaces the backend computing rect edge intersections, such as `RobustIntersection`
+    // with the `robust` feature for exact predicates.
+    pub fn with_backend<C: IntersectionBackend<T>>(self, backend: C) -> ClipRect<T, C> {
+        ClipRect {
+            inner: self.inner,
+            perimeter: self.perimeter,
+            strict: self.strict,
+            join_touching: self.join_touching,
+            degenerate_as_lines: self.degenerate_as_lines,
+            precision: self.precision,
+            winding: self.winding,
+            check_holes: self.check_holes,
+            nan_policy: self.nan_policy,
+            backend,
+        }
+    }
+
+    // Clipper for another rect with all settings of this one
+    pub(crate) fn with_bounds(&self, x0: T, y0: T, x1: T, y1: T) -> Self
+    where
+        B: Clone,
+    {
+        let mut inner = Rect::new(x0, y0, x1, y1);
+        inner.tolerance = self.inner.tolerance;
+
+        ClipRect {
+            perimeter: Perimeter::new(&inner),
+            inner,
+            strict: self.strict,
+            join_touching: self.join_touching,
+            degenerate_as_lines: self.degenerate_as_lines,
+            precision: self.precision,
+            winding: self.winding,
+            check_holes: self.check_holes,
+            nan_policy: self.nan_policy,
+            backend: self.backend.clone(),
         }
     }
 
-    fn clip_linestring(&self, g: &LineString<T>) -> MultiLineString<T> {
+    // In strict mode, malformed input detected during clipping is reported as error by
+    // `try_clip`, instead of being silently dropped.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    // Polygon parts touching each other at a single boundary point are returned separately by
+    // default. With join_touching, they are kept as one polygon pinched at the point, which is
+    // not valid by OGC rules but expected by some consumers.
+    pub fn with_join_touching(mut self, join_touching: bool) -> Self {
+        self.join_touching = join_touching;
+        self
+    }
+
+    // Polygons with zero-area exterior, having all coordinates collinear, are skipped by
+    // default. With degenerate_as_lines, `clip` clips their exterior as a linestring instead.
+    // Degenerate parts of multipolygons are always skipped.
+    pub fn with_degenerate_as_lines(mut self, degenerate_as_lines: bool) -> Self {
+        self.degenerate_as_lines = degenerate_as_lines;
+        self
+    }
+
+    // With check_holes, interior rings not lying inside the exterior are dropped before clipping,
+    // as clipping them could place the clipped hole wrong. In strict mode they are reported as
+    // `ClipError::HoleOutsideShell` instead.
+    pub fn with_check_holes(mut self, check_holes: bool) -> Self {
+        self.check_holes = check_holes;
+        self
+    }
+
+    // Input with NaN coordinates is rejected as `ClipError::NanCoord` by default, clipping to
+    // nothing in `clip`.
+    pub fn with_nan_policy(mut self, nan_policy: NanPolicy) -> Self {
+        self.nan_policy = nan_policy;
+        self
+    }
+
+    // Winding order of output polygon rings. Rings are flipped as needed by their signed area
+    // after clipping, except with `Preserve`, which leaves them in the winding of the input rings
+    // and skips the signed area pass.
+    // The default is `Preserve`, keeping the input orientation.
+    pub fn with_winding(mut self, winding: WindingOrder) -> Self {
+        self.winding = winding;
+        self
+    }
+
+    // Precision model snapping coordinates to a grid of 1 / scale, as `round(c * scale) / scale`.
+    // Input is snapped before clipping and output after it, along with the rect bounds, so
+    // adjacent windows clipping the same input produce identical shared boundaries.
+    pub fn with_precision_model(mut self, scale: T) -> Self {
+        let snap = |v: T| (v * scale).round() / scale;
+        let r = &self.inner;
+
+        let tolerance = r.tolerance;
+        self.inner = Rect::new(snap(r.x0), snap(r.y0), snap(r.x1), snap(r.y1));
+        self.inner.tolerance = tolerance;
+        self.perimeter = Perimeter::new(&self.inner);
+        self.precision = Some(scale);
+        self
+    }
+
+    // Tolerance for matching points to the rect boundary: containment, boundary segments and
+    // corners, and perimeter positions used in sewing. See `Rect::with_tolerance`.
+    // Suits the tolerance to the coordinate units, such as degrees or meters.
+    pub fn with_tolerance(mut self, eps: T) -> Self {
+        self.inner.tolerance = Some(eps.abs());
+        self.perimeter = Perimeter::new(&self.inner);
+        self
+    }
+
+    // Underlying clipping rectangle
+    pub fn rect(&self) -> &Rect<T> {
+        &self.inner
+    }
+
+    // Checks if the two windows clip alike: every bound differs by at most eps, and the
+    // settings changing the output, such as tolerance, precision model and winding order, are
+    // the same. Backends are not compared.
+    // Bounds are normalized on construction, so windows with swapped corners are equal.
+    pub fn approx_eq(&self, other: &ClipRect<T>, eps: T) -> bool {
+        let (a, b) = (&self.inner, &other.inner);
+
+        let bounds_eq = [(a.x0, b.x0), (a.y0, b.y0), (a.x1, b.x1), (a.y1, b.y1)]
+            .into_iter()
+            .all(|(a, b)| (a - b).abs() <= eps);
+
+        bounds_eq
+            && a.tolerance == b.tolerance
+            && self.precision == other.precision
+            && self.winding == other.winding
+            && self.strict == other.strict
+            && self.nan_policy == other.nan_policy
+            && self.join_touching == other.join_touching
+            && self.degenerate_as_lines == other.degenerate_as_lines
+            && self.check_holes == other.check_holes
+    }
+
+    // Clips linestring into the pieces within the rect, without the `Geometry` round trip of
+    // `clip`. Input is clipped as is: no NaN policy, precision model or fast paths apply.
+    pub fn clip_linestring(&self, g: &LineString<T>) -> MultiLineString<T> {
+        self.clip_linestring_counted(g, &StatsSink::default())
+    }
+
+    fn clip_linestring_counted(&self, g: &LineString<T>, stats: &StatsSink) -> MultiLineString<T> {
+        let g = util::dedup_coords(g);
+
+        // groups ending inside the rect keep the line end points
         self.inner
-            .clip_segments(&g.lines().collect::<Vec<Line<T>>>())
+            .clip_segments_tagged_with(&g.lines().collect::<Vec<Line<T>>>(), &self.backend)
             .into_iter()
-            .map(util::segments_to_linestring)
+            .map(|(group, start, end)| {
+                stats.add(|s| s.intersections += start as usize + end as usize);
+                util::segments_to_linestring(group)
+            })
             .collect()
     }
 
+    // Clips linestring in place, reusing its coordinate buffer.
+    // Returns true if the result is a single piece written into g. Otherwise returns false,
+    // leaving g empty when nothing survived and untouched for multi-piece results.
+    pub fn clip_in_place(&self, g: &mut LineString<T>) -> bool {
+        let lines = util::dedup_coords(g).lines().collect::<Vec<Line<T>>>();
+        let mut pieces = vec![];
+        self.inner
+            .clip_segments_grouped(&lines, &self.backend, &mut pieces);
+
+        if pieces.len() > 1 {
+            return false;
+        }
+
+        g.0.clear();
+
+        match pieces.pop() {
+            Some(segs) => {
+                g.0.extend(segs.iter().map(|seg| seg.start));
+                g.0.extend(segs.last().map(|seg| seg.end));
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Total length of the linestring within the rect, summed per segment without building the
+    // clipped geometry. Parts running along the rect boundary count as inside.
+    pub fn clipped_length(&self, g: &LineString<T>) -> T {
+        g.lines()
+            .filter_map(|l| {
+                let (t0, t1) = self.inner.segment_span(&l)?;
+                Some((t1 - t0) * l.dx().hypot(l.dy()))
+            })
+            .fold(T::zero(), |sum, len| sum + len)
+    }
+
     // Clips and sews polygon ring back together by using corner points when necessary.
-    fn clip_polygon_ring(&self, g: &LineString<T>) -> Vec<LineString<T>> {
-        let input_lines = g.lines().collect::<Vec<Line<T>>>();
+    // Sewing walks the rect corners counter-clockwise, so clockwise rings are reversed for
+    // sewing and the output is reversed back to the input winding.
+    // The counter-clockwise ring clipper is given as argument, to allow clippers with their own
+    // segment arithmetic.
+    fn clip_polygon_ring_with(
+        &self,
+        g: &LineString<T>,
+        clip_ccw: &impl Fn(&LineString<T>) -> Vec<LineString<T>>,
+    ) -> Vec<LineString<T>> {
+        let g = util::dedup_coords(g);
+
+        let rings = if util::signed_area(&g.0) < T::zero() {
+            clip_ccw(&g.into_owned().reverse())
+                .into_iter()
+                .map(|ls| ls.reverse())
+                .collect()
+        } else {
+            clip_ccw(&g)
+        };
+
+        // input vertices on the perimeter come out next to the edge intersections at them
+        rings
+            .into_iter()
+            .map(|ls| util::close_exact(util::dedup_coords(&ls).into_owned()))
+            .collect()
+    }
+
+    fn clip_polygon_ring_ccw(&self, g: &LineString<T>, stats: &StatsSink) -> Vec<LineString<T>> {
+        self.clip_polygon_ring_ccw_traced(g, stats, |_, _, _| {})
+    }
 
-        let mut queue: Vec<(f64, LineString<T>)> = self
+    // Ring clipping reporting each sewing step, see `sew_ring_traced`
+    pub(crate) fn clip_polygon_ring_ccw_traced(
+        &self,
+        g: &LineString<T>,
+        stats: &StatsSink,
+        step: impl FnMut(&LineString<T>, &SewQueue<T>, SewAction),
+    ) -> Vec<LineString<T>> {
+        let input_lines = g.lines().collect::<Vec<Line<T>>>();
+        let groups = self
             .inner
-            .clip_segments(&input_lines)
+            .clip_segments_tagged_with(&input_lines, &self.backend);
+
+        self.sew_groups(groups, &input_lines, stats, step)
+    }
+
+    // Sews the tagged groups clipped from the ring made of the input lines, see
+    // `Rect::clip_segments_tagged`
+    pub(crate) fn sew_groups(
+        &self,
+        mut groups: Vec<(Vec<Line<T>>, bool, bool)>,
+        input_lines: &[Line<T>],
+        stats: &StatsSink,
+        step: impl FnMut(&LineString<T>, &SewQueue<T>, SewAction),
+    ) -> Vec<LineString<T>> {
+        // A ring group not made by crossing the rect boundary is the whole ring inside the rect,
+        // unless cut where the ring touches a rect corner, see `sew_ring_traced`
+        if let [(group, false, false)] = groups.as_slice() {
+            if !self.perimeter.at_corner(&group[0].start) {
+                let (group, _, _) = groups.pop().unwrap();
+                return vec![util::segments_to_linestring(group)];
+            }
+        }
+
+        let mut crossings = 0;
+        let pieces = groups
+            .into_iter()
+            .map(|(group, start, end)| {
+                crossings += start as usize + end as usize;
+                util::segments_to_linestring(group)
+            })
+            .collect();
+
+        // a closed ring enters the rect as many times as it leaves
+        if crossings % 2 == 1 {
+            stats.suspect();
+        }
+        stats.add(|s| s.intersections += crossings);
+
+        self.sew_ring_traced(pieces, || self.inner.is_contained(input_lines), stats, step)
+    }
+
+    // Sews clipped pieces of a ring together along the rect perimeter.
+    // `contained` is evaluated only when there are no pieces, to check whether the ring covers
+    // the whole rect.
+    pub(crate) fn sew_ring(
+        &self,
+        pieces: Vec<LineString<T>>,
+        contained: impl FnOnce() -> bool,
+    ) -> Vec<LineString<T>> {
+        self.sew_ring_traced(pieces, contained, &StatsSink::default(), |_, _, _| {})
+    }
+
+    // Sewing calling `step` before each action with the piece taken from the queue and the
+    // pieces left in it.
+    fn sew_ring_traced(
+        &self,
+        pieces: Vec<LineString<T>>,
+        contained: impl FnOnce() -> bool,
+        stats: &StatsSink,
+        mut step: impl FnMut(&LineString<T>, &SewQueue<T>, SewAction),
+    ) -> Vec<LineString<T>> {
+        // Pieces keyed by starting point perimeter index. Perimeter indices are non-negative, so
+        // their bit patterns order the same as the values. Pieces meeting the rect at the same
+        // corner are ordered by the direction they leave toward, see `Perimeter::corner_order`.
+        // Sequence number keeps pieces starting at the same point apart.
+        // Open pieces are cut at the rect boundary, so their ends are on the perimeter. Open
+        // pieces with an end off the perimeter cannot be sewn, and are dropped. Closed pieces
+        // are moved to output as they are.
+        let key = |p: f64| (p + 0.0).to_bits();
+        let n = pieces.len();
+        let mut queue: SewQueue<T> = pieces
             .into_iter()
-            .map(util::segments_to_linestring)
-            .map(|g| (self.inner.perimeter_index(&g[0]), g))
+            .enumerate()
+            .filter_map(|(i, g)| match self.perimeter.index(&g[0]) {
+                Some(p) => {
+                    let toward = g.0.iter().find(|c| **c != g[0]).unwrap_or(&g[0]);
+                    let order = self.perimeter.corner_order(p, &g[0], toward);
+                    Some(((key(p), key(order), n - i), g))
+                }
+                None if g.is_closed() => Some(((key(0.0), key(0.0), n - i), g)),
+                None => {
+                    debug!("piece head off perimeter, dropped");
+                    stats.suspect();
+                    None
+                }
+            })
             .collect();
 
         // When no intersections are found, check if clipping rectangle is fully contained by the
         // subject polygon. In that case, bounds of the clipping rectangle.
-        if queue.is_empty() && self.inner.is_contained(&input_lines) {
+        if queue.is_empty() && contained() {
             debug!("clipping rect inside geom");
+            stats.add(|s| s.corner_nodes_inserted += self.inner.lines.len());
             return vec![util::segments_to_linestring(self.inner.lines.to_vec())];
         }
 
-        // sort elements with starting point perimeter index
-        queue.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+        // Checks if perimeter position a is reached before b walking counter-clockwise from i,
+        // like `Rect::is_index_closer` with the corner order breaking ties
+        let is_closer = |i: (u64, u64), a: (u64, u64), b: (u64, u64)| (a < i, a) < (b < i, b);
 
         // begin connect loop
         let mut output = vec![];
 
-        while !queue.is_empty() {
+        // take the element with the smallest perimeter index
+        while let Some(((k_a, o_a, seq), mut a)) = queue.pop_first() {
             debug!("step");
             util::print_queue(&queue);
 
-            // pop last element of the vector, containing the smallest perimeter index
-            let (p_a, mut a) = queue.pop().unwrap();
+            let last = a.0.last().unwrap();
+            let tail = self.perimeter.index(last).map(|p| {
+                let from = a.0.iter().rev().find(|c| *c != last).unwrap_or(last);
+                (p, self.perimeter.corner_order(p, last, from))
+            });
 
-            if a.is_closed() {
+            // Closed pieces are pushed as they are, except ones leaving a rect corner before
+            // arriving back to it in corner order: these are rings cut at the corner, turned
+            // inside out of the polygon, and get sewn around the rect.
+            if a.is_closed() && tail.is_none_or(|(_, o_tail)| key(o_tail) <= o_a) {
                 debug!("push");
+                step(&a, &queue, SewAction::Push);
                 output.push(a);
                 continue;
             }
 
-            // Check if head point of a is closer than next in queue
-            let p_tail = self.inner.perimeter_index(&a.0.last().unwrap());
+            let p_a = f64::from_bits(k_a);
+            let Some((p_tail, o_tail)) = tail else {
+                debug!("piece tail off perimeter, dropped");
+                stats.suspect();
+                continue;
+            };
             debug!("p_tail={p_tail}");
 
-            // Find next value with greater perimeter index than the p_tail
-            if let Some(next) = queue
-                .iter()
-                .enumerate()
-                .rev()
-                .find(|(_, (p_idx, _))| self.inner.is_index_closer(p_tail, *p_idx, p_a))
-                .map(|(idx, _)| idx)
-            {
-                let (p_b, b) = queue.remove(next);
+            // Next piece walking counter-clockwise from the tail, wrapping around the perimeter
+            // start. Joined if reached before the head of a itself.
+            let at_tail = (key(p_tail), key(o_tail));
+            let next = queue
+                .range((at_tail.0, at_tail.1, 0)..)
+                .next()
+                .or_else(|| queue.first_key_value())
+                .map(|(k, _)| *k)
+                .filter(|(k_b, o_b, _)| is_closer(at_tail, (*k_b, *o_b), (k_a, o_a)));
+
+            if let Some(k_b) = next {
+                step(&a, &queue, SewAction::Join);
+                let b = queue.remove(&k_b).unwrap();
+                let p_b = f64::from_bits(k_b.0);
                 debug!("join lines {p_b}, {b:?}");
                 // create a new segment passed from corner nodes
-                let corners = self.inner.corner_nodes_between(p_tail, p_b);
+                let corners = self.corners_between(&a, at_tail, &b, (k_b.0, k_b.1));
+                stats.add(|s| s.corner_nodes_inserted += corners.len());
 
                 // connect last point of C to first point of A
                 debug!("connect: {a:?} -> {corners:?} -> {b:?}");
@@ -88,17 +618,17 @@ impl<T: CoordFloat> ClipRect<T> {
                 // join C-B-A and push back into queue
                 a.0.extend(corners);
                 a.0.extend(b);
-
-                queue.push((p_a, a));
+                queue.insert((k_a, o_a, seq), a);
             } else {
                 // Close line with self
                 debug!("close line {p_a} -> {p_tail}");
+                step(&a, &queue, SewAction::Close);
 
-                let corners = self.inner.corner_nodes_between(p_tail, p_a);
+                let corners = self.corners_between(&a, at_tail, &a, (k_a, o_a));
+                stats.add(|s| s.corner_nodes_inserted += corners.len());
                 a.0.extend(corners);
-                a.0.push(a[0].clone());
-
-                queue.push((p_a, a));
+                a.0.push(a[0]);
+                output.push(a);
             }
         }
 
@@ -106,63 +636,574 @@ impl<T: CoordFloat> ClipRect<T> {
         output
     }
 
-    fn clip_polygon(&self, g: &Polygon<T>) -> MultiPolygon<T> {
-        let mut polys: Vec<Polygon<T>> = self
-            .clip_polygon_ring(g.exterior())
+    // Corner nodes passed walking from the tail of `a` to the head of `b`, given as perimeter
+    // index and corner order bits. Walking from a corner back to the same corner goes all the
+    // way around when the head leaves before the tail arrives in corner order. Corners the
+    // pieces already end or start at are left out, so pieces meeting the rect at a corner don't
+    // get the corner twice.
+    fn corners_between(
+        &self,
+        a: &LineString<T>,
+        (k_tail, o_tail): (u64, u64),
+        b: &LineString<T>,
+        (k_head, o_head): (u64, u64),
+    ) -> Vec<Coord<T>> {
+        let (tail, head) = (a.0.last(), b.0.first());
+        let (p_tail, mut p_head) = (f64::from_bits(k_tail), f64::from_bits(k_head));
+        if k_head == k_tail && o_head < o_tail {
+            p_head += 4.0;
+        }
+
+        self.inner
+            .corner_nodes_between(p_tail, p_head)
             .into_iter()
-            .filter_map(|ls| {
-                if ls.points().len() >= 3 {
-                    Some(Polygon::new(ls, vec![]))
+            .filter(|c| Some(c) != tail && Some(c) != head)
+            .collect()
+    }
+
+    // Clips polygon into the parts within the rect, like `clip_linestring` for polygons.
+    // Errors reported in strict mode give no parts.
+    pub fn clip_polygon(&self, g: &Polygon<T>) -> MultiPolygon<T> {
+        self.try_clip_polygon(g, &StatsSink::default())
+            .unwrap_or_else(|_| MultiPolygon::new(vec![]))
+    }
+
+    // Holes are clipped only when some part of the exterior survives: with no exterior there is
+    // no polygon. In strict mode, holes overlapping the rect without a surviving exterior are
+    // reported as `ClipError::HoleOutsideShell`.
+    fn try_clip_polygon(
+        &self,
+        g: &Polygon<T>,
+        stats: &StatsSink,
+    ) -> Result<MultiPolygon<T>, ClipError> {
+        self.try_clip_polygon_with(g, &|ls| self.clip_polygon_ring_ccw(ls, stats), stats)
+    }
+
+    // Polygon clipping with pluggable ring clipper, used by clippers with their own segment
+    // arithmetic.
+    pub(crate) fn try_clip_polygon_with(
+        &self,
+        g: &Polygon<T>,
+        clip_ccw: &impl Fn(&LineString<T>) -> Vec<LineString<T>>,
+        stats: &StatsSink,
+    ) -> Result<MultiPolygon<T>, ClipError> {
+        if self.strict
+            && [g.exterior()]
+                .into_iter()
+                .chain(g.interiors())
+                .any(|ls| ls.0.len() < 4)
+        {
+            return Err(ClipError::TooFewPoints);
+        }
+
+        let checked;
+        let g = if self.check_holes {
+            let (inside, outside): (Vec<_>, Vec<_>) = g
+                .interiors()
+                .iter()
+                .cloned()
+                .partition(|ls| util::hole_in_shell(ls, g.exterior()));
+
+            if self.strict && !outside.is_empty() {
+                return Err(ClipError::HoleOutsideShell);
+            }
+
+            checked = Polygon::new(g.exterior().clone(), inside);
+            &checked
+        } else {
+            g
+        };
+
+        let mut polys: Vec<Polygon<T>> = vec![];
+        let mut holes = vec![];
+
+        let any_exterior = self.for_each_ring_with(g, clip_ccw, stats, |ls, kind| match kind {
+            Winding::Outer => polys.push(Polygon::new(ls, vec![])),
+            Winding::Hole => holes.push(ls),
+        });
+
+        // place interiors to polys
+        for hole in holes {
+            if polys.len() == 1 {
+                // single poly -> no need to find
+                polys[0].interiors_push(hole);
+            } else {
+                // find parent poly
+                let c = util::find_coord_inside(&hole, &self.inner);
+                match polys
+                    .iter_mut()
+                    .find(|poly| c.is_some_and(|c| c.is_inside(poly.exterior())))
+                {
+                    Some(poly) => {
+                        debug!("is inside");
+                        poly.interiors_push(hole);
+                    }
+                    // hole in no shell is lost
+                    None => stats.suspect(),
+                }
+            }
+        }
+
+        if !any_exterior
+            && self.strict
+            && g.interiors().iter().any(|ls| {
+                self.clip_polygon_ring_with(ls, clip_ccw)
+                    .iter()
+                    .any(util::is_ring)
+            })
+        {
+            return Err(ClipError::HoleOutsideShell);
+        }
+
+        Ok(polys.into())
+    }
+
+    // Calls f for each clipped exterior ring, then for each clipped hole.
+    // Returns false when no part of the exterior survives, in which case holes are skipped.
+    fn for_each_ring_with(
+        &self,
+        g: &Polygon<T>,
+        clip_ccw: &impl Fn(&LineString<T>) -> Vec<LineString<T>>,
+        stats: &StatsSink,
+        mut f: impl FnMut(LineString<T>, Winding),
+    ) -> bool {
+        // collinear exterior has no inside to clip
+        if util::is_collinear(&g.exterior().0) {
+            stats.add(|s| s.rings_dropped += 1 + g.interiors().len());
+            return false;
+        }
+
+        let clip_ring = |ls: &LineString<T>| self.clip_polygon_ring_with(ls, clip_ccw);
+
+        let mut any_exterior = false;
+        clip_ring(g.exterior())
+            .into_iter()
+            .flat_map(|ls| {
+                if self.join_touching {
+                    vec![ls]
                 } else {
-                    None
+                    self.split_pinched(ls)
                 }
             })
-            .collect();
+            .filter(|ls| self.keeps_ring(ls))
+            .for_each(|ls| {
+                any_exterior = true;
+                f(self.orient_ring(ls, true), Winding::Outer);
+            });
+
+        if !any_exterior {
+            stats.add(|s| s.rings_dropped += 1 + g.interiors().len());
+            return false;
+        }
 
-        // clip and place interiors to polys
-        if !polys.is_empty() {
-            g.interiors()
+        for hole in g.interiors() {
+            let mut kept = false;
+            clip_ring(hole)
                 .into_iter()
-                .map(|ls| self.clip_polygon_ring(&ls.clone().reverse()))
-                .flatten()
-                .filter_map(|ls| {
-                    if ls.points().len() >= 3 {
-                        Some(ls)
-                    } else {
-                        None
-                    }
-                })
-                .for_each(|hole| {
-                    if polys.len() == 1 {
-                        // single poly -> no need to find
-                        polys[0].interiors_push(hole.reverse());
-                    } else {
-                        // find parent poly
-                        for poly in polys.iter_mut() {
-                            if let Some(c) = util::find_coord_inside(&hole, &self.inner) {
-                                debug!("coord inside");
-                                if c.is_inside(poly.exterior()) {
-                                    debug!("is inside");
-                                    poly.interiors_push(hole.reverse());
-                                    break;
-                                }
-                            }
-                        }
-                    }
+                .filter(|ls| self.keeps_ring(ls))
+                .for_each(|ls| {
+                    kept = true;
+                    f(self.orient_ring(ls, false), Winding::Hole)
                 });
+
+            if !kept {
+                stats.add(|s| s.rings_dropped += 1);
+            }
+        }
+
+        true
+    }
+
+    // Clipped ring is kept in the output when it is closed and has some area. Rings cut by the
+    // rect collapsing to slivers along its boundary are dropped: their area over their extent,
+    // the mean width of the ring, is within the tolerance. Rings off the boundary are kept as
+    // they are, to leave e.g. self-intersecting input for validation.
+    pub(crate) fn keeps_ring(&self, ls: &LineString<T>) -> bool {
+        if !util::is_ring(ls) {
+            return false;
+        }
+
+        let (min, max) = ls.0.iter().fold((ls.0[0], ls.0[0]), |(min, max), c| {
+            (
+                (min.x.min(c.x), min.y.min(c.y)).into(),
+                (max.x.max(c.x), max.y.max(c.y)).into(),
+            )
+        });
+        let extent = (max.x - min.x).max(max.y - min.y);
+        let area = util::signed_area(&ls.0).abs();
+
+        (extent > T::zero() && !self.inner.near(area / extent, T::zero()))
+            || !ls.0.iter().any(|c| self.perimeter.index(c).is_some())
+    }
+
+    // Winds output exterior or hole ring by the winding order
+    pub(crate) fn orient_ring(&self, ls: LineString<T>, exterior: bool) -> LineString<T> {
+        match self.winding.ccw(exterior) {
+            Some(ccw) if (util::signed_area(&ls.0) > T::zero()) != ccw => ls.reverse(),
+            _ => ls,
+        }
+    }
+
+    // Clips polygon calling f for each output ring as it is finished, without collecting the
+    // rings into polygons. Exterior rings come first, followed by the holes.
+    pub fn clip_polygon_for_each<F: FnMut(&LineString<T>, Winding)>(
+        &self,
+        g: &Polygon<T>,
+        mut f: F,
+    ) {
+        let stats = StatsSink::default();
+        self.for_each_ring_with(
+            g,
+            &|ls| self.clip_polygon_ring_ccw(ls, &stats),
+            &stats,
+            |ls, kind| f(&ls, kind),
+        );
+    }
+
+    // Clips polygon, optionally treating the largest-area ring as the exterior regardless of its
+    // position in the polygon. Guards against inputs with mis-ordered rings.
+    pub fn clip_polygon_with_convention(
+        &self,
+        g: &Polygon<T>,
+        shell_is_largest: bool,
+    ) -> MultiPolygon<T> {
+        if !shell_is_largest {
+            return self.clip_polygon(g);
+        }
+
+        // NaN areas cannot be compared, so the NaN policy is applied first
+        let geom = Geometry::Polygon(g.clone());
+        let g = match self.nan_checked(&geom) {
+            Ok(Some(Cow::Owned(Geometry::Polygon(poly)))) => Cow::Owned(poly),
+            Ok(Some(_)) => Cow::Borrowed(g),
+            _ => return MultiPolygon::new(vec![]),
+        };
+
+        let mut rings: Vec<LineString<T>> = [g.exterior().clone()]
+            .into_iter()
+            .chain(g.interiors().iter().cloned())
+            .collect();
+
+        let area = |ls: &LineString<T>| util::signed_area(&ls.0).abs();
+        let largest = (0..rings.len())
+            .max_by(|&a, &b| {
+                area(&rings[a])
+                    .partial_cmp(&area(&rings[b]))
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap();
+
+        if largest == 0 {
+            return self.clip_polygon(&g);
+        }
+
+        let exterior = rings.remove(largest);
+        self.clip_polygon(&Polygon::new(exterior, rings))
+    }
+
+    // Clips convex polygon with Sutherland–Hodgman, producing a single output polygon.
+    // Faster than the general path, but the result is undefined for non-convex input.
+    // Same as `clip_sutherland_hodgman`: rings come out in the winding order, and holes are
+    // clipped each on its own.
+    pub fn clip_convex_polygon(&self, g: &Polygon<T>) -> Option<Polygon<T>> {
+        self.clip_sutherland_hodgman(g)
+    }
+
+    // Clips polygon into a flat list of contours tagged as outer or hole, without the polygon
+    // nesting. Contours are open: the closing coordinate is left out, as tessellators close
+    // contours themselves.
+    pub fn clip_contours(&self, g: &Polygon<T>) -> Vec<(Vec<Coord<T>>, Winding)> {
+        let mut contours = vec![];
+
+        for poly in self.clip_polygon(g) {
+            let (exterior, interiors) = poly.into_inner();
+            contours.push((util::open_ring(exterior), Winding::Outer));
+            contours.extend(
+                interiors
+                    .into_iter()
+                    .map(|ls| (util::open_ring(ls), Winding::Hole)),
+            );
+        }
+
+        contours
+    }
+
+    // Point window keeps the point itself if the geometry passes through it.
+    fn clip_to_point(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        let c = self.inner.lines[0].start;
+
+        let on_lines = |ls: &LineString<T>| ls.lines().any(|l| util::point_on_segment(&c, &l));
+        let on_polygon = |poly: &Polygon<T>| {
+            util::point_in_ring(&c, poly.exterior()).unwrap_or(true)
+                && poly
+                    .interiors()
+                    .iter()
+                    .all(|ls| !util::point_in_ring(&c, ls).unwrap_or(false))
+        };
+
+        let hit = match g {
+            Geometry::Point(p) => p.0 == c,
+            Geometry::Line(l) => util::point_on_segment(&c, l),
+            Geometry::LineString(ls) => on_lines(ls),
+            Geometry::Polygon(poly) => on_polygon(poly),
+            Geometry::MultiPoint(mp) => mp.iter().any(|p| p.0 == c),
+            Geometry::MultiLineString(mls) => mls.iter().any(on_lines),
+            Geometry::MultiPolygon(mp) => mp.iter().any(on_polygon),
+            _ => false,
+        };
+
+        if hit {
+            Some(Geometry::Point(c.into()))
+        } else {
+            None
+        }
+    }
+
+    // Like `clip`, but a polygon result covering the whole window is returned as two-coordinate
+    // `Geometry::Rect` instead of five-coordinate polygon.
+    pub fn clip_compact(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        match self.clip(g)? {
+            Geometry::Polygon(poly) if self.is_window(&poly) => {
+                Some(Geometry::Rect(geo_types::Rect::new(
+                    (self.inner.x0, self.inner.y0),
+                    (self.inner.x1, self.inner.y1),
+                )))
+            }
+            g => Some(g),
         }
+    }
+
+    // Checks if polygon is exactly the clipping window
+    fn is_window(&self, poly: &Polygon<T>) -> bool {
+        let ext = poly.exterior();
 
-        polys.into()
+        poly.interiors().is_empty()
+            && ext.0.len() == 5
+            && ext.is_closed()
+            && self.inner.lines.iter().all(|l| ext.0.contains(&l.start))
     }
 
+    // Clips geometry, giving None where `try_clip` reports an error. Malformed input is
+    // clipped for what it can give unless in strict mode.
     pub fn clip(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        self.try_clip_counted(g, &StatsSink::default(), self.strict)
+            .unwrap_or(None)
+    }
+
+    // Clips each geometry, keeping output aligned with input by index: geometries clipping to
+    // nothing give None in their slot.
+    // With the `rayon` feature, geometries are clipped in parallel.
+    pub fn clip_all(&self, geoms: &[Geometry<T>]) -> Vec<Option<Geometry<T>>> {
+        #[cfg(feature = "rayon")]
+        return geoms.par_iter().map(|g| self.clip(g)).collect();
+
+        #[cfg(not(feature = "rayon"))]
+        geoms.iter().map(|g| self.clip(g)).collect()
+    }
+
+    // Clips multipolygon lazily, one source polygon at a time, yielding the parts of each.
+    // Output stays aligned with the source polygons: polygons clipping to nothing yield an
+    // empty multipolygon.
+    pub fn clip_polygons_iter<'a>(
+        &'a self,
+        mp: &'a MultiPolygon<T>,
+    ) -> impl Iterator<Item = MultiPolygon<T>> + 'a {
+        mp.iter().map(|poly| self.clip_polygon(poly))
+    }
+
+    // Clips geometries from a fallible stream, such as a parser, passing errors through.
+    pub fn clip_results<'a, E, I>(
+        &'a self,
+        it: I,
+    ) -> impl Iterator<Item = Result<Option<Geometry<T>>, E>> + 'a
+    where
+        I: Iterator<Item = Result<Geometry<T>, E>> + 'a,
+    {
+        it.map(|g| g.map(|g| self.clip(&g)))
+    }
+
+    // Fallible variant of `clip`, reporting a degenerate window, infinite coordinates and
+    // short polygon rings whether strict or not. This is the `clip_checked` of the original
+    // request, named apart from `clip_checked` of the `validate` feature, which reports
+    // `ValidationError` instead.
+    pub fn try_clip(&self, g: &Geometry<T>) -> Result<Option<Geometry<T>>, ClipError> {
+        self.try_clip_counted(g, &StatsSink::default(), true)
+    }
+
+    // Fallible clipping counting clip events into stats
+    pub(crate) fn try_clip_counted(
+        &self,
+        g: &Geometry<T>,
+        stats: &StatsSink,
+        report_input: bool,
+    ) -> Result<Option<Geometry<T>>, ClipError> {
+        match self.checked_input(g, report_input)? {
+            Some(g) => self.try_clip_snapped(&g, stats),
+            None => Ok(None),
+        }
+    }
+
+    // Input as clipped, see `nan_checked`. A degenerate window is always reported, and
+    // malformed input when `report_input` is set, as in strict mode.
+    pub(crate) fn checked_input<'g>(
+        &self,
+        g: &'g Geometry<T>,
+        report_input: bool,
+    ) -> Result<Option<Cow<'g, Geometry<T>>>, ClipError> {
+        let r = &self.inner;
+        if (r.x0 == r.x1) != (r.y0 == r.y1) {
+            return Err(ClipError::DegenerateRect);
+        }
+
+        if report_input {
+            let mut infinite = false;
+            util::for_each_coord(g, &mut |c| {
+                infinite |= c.x.is_infinite() || c.y.is_infinite();
+            });
+
+            if infinite {
+                return Err(ClipError::InfiniteCoord);
+            }
+
+            if has_short_ring(g) {
+                return Err(ClipError::TooFewPoints);
+            }
+        }
+
+        self.nan_checked(g)
+    }
+
+    // Input as clipped under the NaN policy: None when skipped, and a copy with NaN values
+    // zeroed by `NanPolicy::Zero`
+    fn nan_checked<'g>(
+        &self,
+        g: &'g Geometry<T>,
+    ) -> Result<Option<Cow<'g, Geometry<T>>>, ClipError> {
+        let mut nan = false;
+        util::for_each_coord(g, &mut |c| nan |= c.x.is_nan() || c.y.is_nan());
+
+        if !nan {
+            return Ok(Some(Cow::Borrowed(g)));
+        }
+
+        match self.nan_policy {
+            NanPolicy::Reject => Err(ClipError::NanCoord),
+            NanPolicy::Skip => Ok(None),
+            NanPolicy::Zero => {
+                let zero = |v: T| if v.is_nan() { T::zero() } else { v };
+
+                let mut g = g.clone();
+                util::for_each_coord_mut(&mut g, &mut |c| *c = (zero(c.x), zero(c.y)).into());
+                Ok(Some(Cow::Owned(g)))
+            }
+        }
+    }
+
+    // Clipping with input and output snapped to the precision model, if any
+    fn try_clip_snapped(
+        &self,
+        g: &Geometry<T>,
+        stats: &StatsSink,
+    ) -> Result<Option<Geometry<T>>, ClipError> {
+        let Some(scale) = self.precision else {
+            return self.try_clip_geometry(g, stats);
+        };
+
+        let mut g = g.clone();
+        snap_geometry(&mut g, scale);
+
+        Ok(self.try_clip_geometry(&g, stats)?.map(|mut g| {
+            snap_geometry(&mut g, scale);
+            g
+        }))
+    }
+
+    // Settles clipping from the geometry bounding box alone, without walking its segments.
+    // Geometry with bounding box disjoint from the rect clips to nothing. Geometry with bounding
+    // box strictly inside the rect is passed through as is, leaving the coordinates free of
+    // float drift. Input validated in strict mode takes the full path.
+    fn bbox_fast_path(&self, g: &Geometry<T>) -> Option<Option<Geometry<T>>> {
+        if self.strict || self.check_holes {
+            return None;
+        }
+
+        let r = &self.inner;
+        let (min, max) = util::bounding_box(g)?;
+
+        let eps = r.tolerance.unwrap_or(T::zero());
+        if min.x > r.x1 + eps || min.y > r.y1 + eps || max.x < r.x0 - eps || max.y < r.y0 - eps {
+            Some(None)
+        } else if self.contains(g) {
+            // geometry touching the boundary may be sewn anew, only geometry off it passes as is
+            let off_boundary = min.x > r.x0 && min.y > r.y0 && max.x < r.x1 && max.y < r.y1;
+            (off_boundary && self.passes_unchanged(g)).then(|| Some(g.clone()))
+        } else {
+            None
+        }
+    }
+
+    // Contained geometry clipping would return as is. Input the pipeline normalizes, such as
+    // rings in the wrong orientation or repeated vertices, takes the full path.
+    fn passes_unchanged(&self, g: &Geometry<T>) -> bool {
         use Geometry::*;
 
+        let line_ok = |ls: &geo_types::LineString<T>| {
+            ls.0.len() > 1 && matches!(util::dedup_coords(ls), Cow::Borrowed(_))
+        };
+        let ring_ok = |ls: &geo_types::LineString<T>, exterior: bool| {
+            let area = util::signed_area(&ls.0);
+            util::is_ring(ls)
+                && util::closes_exact(ls)
+                && line_ok(ls)
+                && area != T::zero()
+                && self
+                    .winding
+                    .ccw(exterior)
+                    .is_none_or(|ccw| (area > T::zero()) == ccw)
+        };
+        let polygon_ok = |poly: &geo_types::Polygon<T>| {
+            ring_ok(poly.exterior(), true)
+                && poly.interiors().iter().all(|ls| ring_ok(ls, false))
+                && !(self.degenerate_as_lines && util::is_collinear(&poly.exterior().0))
+        };
+
         match g {
-            Point(g) => self.inner.clip_point(g).and_then(|p| Some(Point(p))),
-            Line(g) => self.inner.clip_segment(g).and_then(|l| Some(Line(l))),
+            Point(_) | Line(_) | MultiPoint(_) => true,
+            LineString(ls) => line_ok(ls),
+            MultiLineString(mls) => mls.iter().all(line_ok),
+            Polygon(poly) => polygon_ok(poly),
+            MultiPolygon(mp) => mp.iter().all(polygon_ok),
+            _ => false,
+        }
+    }
+
+    fn try_clip_geometry(
+        &self,
+        g: &Geometry<T>,
+        stats: &StatsSink,
+    ) -> Result<Option<Geometry<T>>, ClipError> {
+        use Geometry::*;
+
+        if self.inner.is_point() {
+            return Ok(self.clip_to_point(g));
+        }
+
+        if let Some(clipped) = self.bbox_fast_path(g) {
+            if clipped.is_none() {
+                stats.add(|s| s.rings_dropped += self.count_rings(g));
+            }
+            return Ok(clipped);
+        }
+
+        let clipped = match g {
+            Point(g) => self.inner.clip_point(g).map(|p| Point(p)),
+            Line(g) => self.inner.clip_segment_with(g, &self.backend).map(|l| {
+                stats.add(|s| s.intersections += (l.start != g.start) as usize);
+                stats.add(|s| s.intersections += (l.end != g.end) as usize);
+                Line(l)
+            }),
             LineString(g) => {
-                let g = self.clip_linestring(g);
+                let g = self.clip_linestring_counted(g, stats);
                 if g.0.is_empty() {
                     None
                 } else if g.0.len() == 1 {
@@ -171,8 +1212,11 @@ impl<T: CoordFloat> ClipRect<T> {
                     Some(MultiLineString(g))
                 }
             }
+            Polygon(g) if self.degenerate_as_lines && util::is_collinear(&g.exterior().0) => {
+                return self.try_clip_geometry(&LineString(g.exterior().clone()), stats);
+            }
             Polygon(g) => {
-                let g = self.clip_polygon(g);
+                let g = self.try_clip_polygon(g, stats)?;
                 if g.0.is_empty() {
                     None
                 } else if g.0.len() == 1 {
@@ -181,23 +1225,48 @@ impl<T: CoordFloat> ClipRect<T> {
                     Some(MultiPolygon(g))
                 }
             }
-            MultiPoint(g) => Some(MultiPoint(
-                g.into_iter()
+            Rect(g) => return self.try_clip_geometry(&Polygon(g.to_polygon()), stats),
+            Triangle(g) => return self.try_clip_geometry(&Polygon(g.to_polygon()), stats),
+            MultiPoint(g) => {
+                let points: Vec<_> = g
+                    .into_iter()
                     .filter_map(|p| self.inner.clip_point(p))
-                    .collect(),
-            )),
-            MultiLineString(g) => Some(MultiLineString(
-                g.into_iter()
-                    .map(|ls| self.clip_linestring(ls))
-                    .flatten()
-                    .collect(),
-            )),
-            MultiPolygon(g) => {
-                let polys: Vec<_> = g
+                    .collect();
+                (!points.is_empty()).then_some(MultiPoint(geo_types::MultiPoint(points)))
+            }
+            MultiLineString(g) => {
+                let lines: Vec<_> = g
                     .into_iter()
-                    .map(|poly| self.clip_polygon(poly))
-                    .flatten()
+                    .flat_map(|ls| self.clip_linestring_counted(ls, stats))
                     .collect();
+                (!lines.is_empty()).then_some(MultiLineString(geo_types::MultiLineString(lines)))
+            }
+            MultiPolygon(g) => {
+                // Parts are clipped independently, so parts nested inside holes of other parts
+                // (island in a lake) stay as separate solid polygons. With the `rayon` feature,
+                // parts are clipped in parallel and collected in input order.
+                // Parts clipped in parallel count into sinks of their own.
+                #[cfg(feature = "rayon")]
+                let parts: Vec<_> = {
+                    let on = stats.is_on();
+                    g.0.par_iter()
+                        .map(|poly| {
+                            let part_stats = StatsSink::new(on);
+                            (self.try_clip_polygon(poly, &part_stats), part_stats)
+                        })
+                        .collect()
+                };
+                #[cfg(not(feature = "rayon"))]
+                let parts: Vec<_> = g
+                    .iter()
+                    .map(|poly| (self.try_clip_polygon(poly, stats), StatsSink::default()))
+                    .collect();
+
+                let mut polys = vec![];
+                for (part, part_stats) in parts {
+                    stats.merge(part_stats);
+                    polys.extend(part?);
+                }
 
                 if polys.is_empty() {
                     None
@@ -205,7 +1274,79 @@ impl<T: CoordFloat> ClipRect<T> {
                     Some(MultiPolygon(polys.into()))
                 }
             }
-            _ => None,
+            GeometryCollection(g) => {
+                let mut members = vec![];
+                for g in g {
+                    members.extend(self.try_clip_geometry(g, stats)?);
+                }
+
+                if members.is_empty() {
+                    None
+                } else {
+                    Some(GeometryCollection(members.into()))
+                }
+            }
+        };
+
+        Ok(clipped)
+    }
+}
+
+// Windows are (de)serialized by their bounds only, as `{"x0":..,"y0":..,"x1":..,"y1":..}`.
+// Deserializing goes through `ClipRect::new`, normalizing swapped bounds.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Bounds<T> {
+    x0: T,
+    y0: T,
+    x1: T,
+    y1: T,
+}
+
+#[cfg(feature = "serde")]
+impl<T: CoordFloat + serde::Serialize, B> serde::Serialize for ClipRect<T, B> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let r = &self.inner;
+        Bounds {
+            x0: r.x0,
+            y0: r.y0,
+            x1: r.x1,
+            y1: r.y1,
         }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: CoordFloat + MaybeSync + serde::Deserialize<'de>> serde::Deserialize<'de>
+    for ClipRect<T>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let b = Bounds::deserialize(deserializer)?;
+        Ok(ClipRect::new(b.x0, b.y0, b.x1, b.y1))
+    }
+}
+
+// Snaps coordinates to the precision model grid, see `ClipRect::with_precision_model`
+pub(crate) fn snap_geometry<T: CoordFloat>(g: &mut Geometry<T>, scale: T) {
+    util::for_each_coord_mut(g, &mut |c| {
+        c.x = (c.x * scale).round() / scale;
+        c.y = (c.y * scale).round() / scale;
+    });
+}
+
+// Checks if any polygon ring of geometry has less than 4 coordinates
+fn has_short_ring<T: CoordFloat>(g: &Geometry<T>) -> bool {
+    let short = |poly: &Polygon<T>| {
+        core::iter::once(poly.exterior())
+            .chain(poly.interiors())
+            .any(|ls| ls.0.len() < 4)
+    };
+
+    match g {
+        Geometry::Polygon(poly) => short(poly),
+        Geometry::MultiPolygon(mp) => mp.iter().any(short),
+        Geometry::GeometryCollection(gc) => gc.iter().any(has_short_ring),
+        _ => false,
     }
 }
@@ -0,0 +1,47 @@
+use crate::geom::Reverse;
+use crate::{ClipRect, IntersectionBackend, MaybeSync};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use geo_types::{Coord, CoordFloat, LineString};
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips linestring, ordering the pieces for continuous path output such as pen plotters.
+    // Starting from the first piece, the piece with an end point nearest to the current
+    // position is taken next, reversed when its end is the nearer one. Greedy, so the total
+    // jump distance is reduced but not guaranteed minimal.
+    pub fn clip_linestring_ordered(&self, g: &LineString<T>) -> Vec<LineString<T>> {
+        let mut pieces = self.clip_linestring(g).0;
+        if pieces.is_empty() {
+            return pieces;
+        }
+
+        let mut ordered = vec![pieces.remove(0)];
+
+        while !pieces.is_empty() {
+            let pos = *ordered.last().unwrap().0.last().unwrap();
+
+            // nearest piece end, and whether it is the last point
+            let (i, reverse, _) = pieces
+                .iter()
+                .enumerate()
+                .flat_map(|(i, ls)| {
+                    [
+                        (i, false, dist(&pos, &ls[0])),
+                        (i, true, dist(&pos, ls.0.last().unwrap())),
+                    ]
+                })
+                .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal))
+                .unwrap();
+
+            let ls = pieces.remove(i);
+            ordered.push(if reverse { ls.reverse() } else { ls });
+        }
+
+        ordered
+    }
+}
+
+fn dist<T: CoordFloat>(a: &Coord<T>, b: &Coord<T>) -> T {
+    (a.x - b.x).hypot(a.y - b.y)
+}
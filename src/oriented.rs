@@ -0,0 +1,60 @@
+use crate::{util, ClipRect, MaybeSync};
+use geo_types::{Coord, CoordFloat, Geometry};
+
+// Rectangular clipping window rotated around its center.
+// Geometry is rotated into the window frame, clipped against the axis aligned window there and
+// rotated back, so the sewn corner nodes land on the rotated corners.
+pub struct OrientedRect<T: CoordFloat> {
+    center: Coord<T>,
+    // sine and cosine of the rotation
+    sin: T,
+    cos: T,
+    window: ClipRect<T>,
+}
+
+impl<T: CoordFloat + MaybeSync> OrientedRect<T> {
+    // Window with the given center, half width and half height along its own axes, rotated
+    // counter-clockwise by angle radians
+    pub fn new(center: Coord<T>, half_width: T, half_height: T, angle: T) -> Self {
+        let (hw, hh) = (half_width.abs(), half_height.abs());
+
+        Self {
+            center,
+            sin: angle.sin(),
+            cos: angle.cos(),
+            window: ClipRect::new(-hw, -hh, hw, hh),
+        }
+    }
+
+    // Window corners in world coordinates, counter-clockwise from the local bottom left
+    pub fn corners(&self) -> [Coord<T>; 4] {
+        let r = self.window.rect();
+        [(r.x0, r.y0), (r.x1, r.y0), (r.x1, r.y1), (r.x0, r.y1)].map(|c| self.to_world(c.into()))
+    }
+
+    pub fn clip(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        let mut local = match g {
+            Geometry::Rect(r) => Geometry::Polygon(r.to_polygon()),
+            g => g.clone(),
+        };
+        util::for_each_coord_mut(&mut local, &mut |c| *c = self.to_local(*c));
+
+        let mut clipped = self.window.clip(&local)?;
+        util::for_each_coord_mut(&mut clipped, &mut |c| *c = self.to_world(*c));
+
+        Some(clipped)
+    }
+
+    fn to_local(&self, c: Coord<T>) -> Coord<T> {
+        let (dx, dy) = (c.x - self.center.x, c.y - self.center.y);
+        (dx * self.cos + dy * self.sin, dy * self.cos - dx * self.sin).into()
+    }
+
+    fn to_world(&self, c: Coord<T>) -> Coord<T> {
+        (
+            self.center.x + c.x * self.cos - c.y * self.sin,
+            self.center.y + c.x * self.sin + c.y * self.cos,
+        )
+            .into()
+    }
+}
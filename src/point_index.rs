@@ -0,0 +1,109 @@
+use crate::{ClipRect, MaybeSync};
+use alloc::vec;
+use alloc::vec::Vec;
+use geo_types::{Coord, CoordFloat, Point};
+use num_traits::Float;
+
+// Grid bucket index over a point set, built once and clipped against many windows.
+// Only buckets overlapping the window are scanned.
+pub struct PointIndex<T: CoordFloat> {
+    points: Vec<Coord<T>>,
+
+    // grid origin, cell size and dimensions
+    min: Coord<T>,
+    cell: Coord<T>,
+    cols: usize,
+    rows: usize,
+
+    // point indices per cell, row by row
+    buckets: Vec<Vec<usize>>,
+}
+
+impl<T: CoordFloat + MaybeSync> PointIndex<T> {
+    // Builds grid with roughly one point per cell
+    pub fn new(points: &[Point<T>]) -> Self {
+        let points: Vec<Coord<T>> = points.iter().map(|p| p.0).collect();
+
+        let (mut min, mut max) = match points.first() {
+            Some(c) => (*c, *c),
+            None => (Coord::zero(), Coord::zero()),
+        };
+        for c in &points {
+            min = (min.x.min(c.x), min.y.min(c.y)).into();
+            max = (max.x.max(c.x), max.y.max(c.y)).into();
+        }
+
+        let n = Float::ceil(Float::sqrt(points.len() as f64)).max(1.0) as usize;
+        let (cols, rows) = (n, n);
+
+        // keep cells non-zero sized for collapsed extents
+        let size = |d: T, n: usize| {
+            let d = d / T::from(n).unwrap();
+            if d > T::zero() {
+                d
+            } else {
+                T::one()
+            }
+        };
+        let cell = (size(max.x - min.x, cols), size(max.y - min.y, rows)).into();
+
+        let mut index = Self {
+            points: vec![],
+            min,
+            cell,
+            cols,
+            rows,
+            buckets: vec![vec![]; cols * rows],
+        };
+
+        for (i, c) in points.iter().enumerate() {
+            let (col, row) = (index.col(c.x), index.row(c.y));
+            index.buckets[row * cols + col].push(i);
+        }
+        index.points = points;
+
+        index
+    }
+
+    fn col(&self, x: T) -> usize {
+        let i = ((x - self.min.x) / self.cell.x).floor();
+        i.max(T::zero()).to_usize().unwrap_or(0).min(self.cols - 1)
+    }
+
+    fn row(&self, y: T) -> usize {
+        let i = ((y - self.min.y) / self.cell.y).floor();
+        i.max(T::zero()).to_usize().unwrap_or(0).min(self.rows - 1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    // Returns indices of points inside the rect in ascending order
+    pub fn clip(&self, rect: &ClipRect<T>) -> Vec<usize> {
+        let r = rect.rect();
+        let (x0, x1) = (r.x0.min(r.x1), r.x0.max(r.x1));
+        let (y0, y1) = (r.y0.min(r.y1), r.y0.max(r.y1));
+
+        let mut out = vec![];
+        if self.points.is_empty() {
+            return out;
+        }
+
+        for row in self.row(y0)..=self.row(y1) {
+            for col in self.col(x0)..=self.col(x1) {
+                out.extend(self.buckets[row * self.cols + col].iter().filter(|i| {
+                    let c = self.points[**i];
+                    x0 <= c.x && c.x <= x1 && y0 <= c.y && c.y <= y1
+                }));
+            }
+        }
+
+        out.sort_unstable();
+        out
+    }
+}
@@ -0,0 +1,360 @@
+use crate::geom::{LineExt, Reverse};
+use crate::util;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use geo_types::{
+    Coord, CoordFloat, Geometry, Line, LineString, MultiLineString, MultiPolygon, Polygon,
+};
+
+// Clipping window bounded by a general polygon, which may be concave and have holes.
+// Polygons are clipped Weiler–Atherton style: the points where input and window rings meet are
+// inserted into the rings of both, the parts of each ring inside the other polygon are kept, and
+// the kept parts are traced into output rings, switching between input and window at the
+// inserted points. Parts shared by both boundaries are kept once when the polygons lie on the
+// same side of them. Lines are cut at the window boundary, keeping the pieces inside or along it.
+pub struct ClipPolygon<T: CoordFloat> {
+    // closed window rings, exteriors counter-clockwise and holes clockwise
+    rings: Vec<LineString<T>>,
+    // bounding box of the window, None for empty windows
+    bbox: Option<(Coord<T>, Coord<T>)>,
+}
+
+// Coordinate bits, for looking up points shared by the rings
+type Key = (u64, u64);
+
+fn key<T: CoordFloat>(c: &Coord<T>) -> Key {
+    let bits = |v: T| (v.to_f64().unwrap() + 0.0).to_bits();
+    (bits(c.x), bits(c.y))
+}
+
+impl<T: CoordFloat> ClipPolygon<T> {
+    pub fn new(window: impl Into<MultiPolygon<T>>) -> Self {
+        let window = window.into();
+        let rings = polygon_rings(&window);
+        let bbox = util::bounding_box(&Geometry::MultiPolygon(window));
+
+        Self { rings, bbox }
+    }
+
+    pub fn clip(&self, g: &Geometry<T>) -> Option<Geometry<T>> {
+        use Geometry::*;
+
+        let (min, max) = self.bbox?;
+        let (g_min, g_max) = util::bounding_box(g)?;
+        if g_min.x > max.x || g_min.y > max.y || g_max.x < min.x || g_max.y < min.y {
+            return None;
+        }
+
+        match g {
+            Point(p) => self.contains(&p.0).then_some(Point(*p)),
+            MultiPoint(mp) => {
+                let points: Vec<_> = mp.iter().filter(|p| self.contains(&p.0)).copied().collect();
+                (!points.is_empty()).then(|| MultiPoint(points.into()))
+            }
+            Line(l) => lines_geometry(self.clip_linestring(&(*l).into())),
+            LineString(ls) => lines_geometry(self.clip_linestring(ls)),
+            MultiLineString(mls) => {
+                lines_geometry(mls.iter().flat_map(|ls| self.clip_linestring(ls)).collect())
+            }
+            Polygon(poly) => polygons_geometry(self.clip_polygon(poly)),
+            MultiPolygon(mp) => {
+                polygons_geometry(mp.iter().flat_map(|poly| self.clip_polygon(poly)).collect())
+            }
+            GeometryCollection(gc) => {
+                let members: Vec<_> = gc.iter().filter_map(|g| self.clip(g)).collect();
+                (!members.is_empty()).then(|| GeometryCollection(members.into()))
+            }
+            Rect(r) => polygons_geometry(self.clip_polygon(&r.to_polygon())),
+            Triangle(t) => polygons_geometry(self.clip_polygon(&t.to_polygon())),
+        }
+    }
+
+    // Coordinate is inside the window or on its boundary
+    pub fn contains(&self, c: &Coord<T>) -> bool {
+        locate(c, &self.rings) != Some(false)
+    }
+
+    // Clips linestring into the pieces within the window
+    pub fn clip_linestring(&self, ls: &LineString<T>) -> Vec<LineString<T>> {
+        let edges = ring_edges(&self.rings);
+        let mut pieces: Vec<LineString<T>> = vec![];
+
+        for l in util::dedup_coords(ls).lines() {
+            let mut cuts = vec![];
+            for e in &edges {
+                cuts.extend(l.contacts(e).into_iter().flatten().map(|(c, t, _)| (t, c)));
+            }
+
+            for piece in cut(&l, cuts) {
+                if !self.contains(&midpoint(&piece)) {
+                    continue;
+                }
+
+                match pieces.last_mut() {
+                    Some(ls) if ls.0.last() == Some(&piece.start) => ls.0.push(piece.end),
+                    _ => pieces.push(LineString::new(vec![piece.start, piece.end])),
+                }
+            }
+        }
+
+        pieces
+    }
+
+    // Clips polygon into the parts within the window, keeping the window holes out of the
+    // output. Output exteriors are counter-clockwise and holes clockwise.
+    pub fn clip_polygon(&self, g: &Polygon<T>) -> MultiPolygon<T> {
+        let subject = polygon_rings(&g.clone().into());
+        let (subject_edges, window_edges) = (ring_edges(&subject), ring_edges(&self.rings));
+
+        // points where the rings meet, by edge
+        let mut subject_cuts: Vec<Vec<(T, Coord<T>)>> = vec![vec![]; subject_edges.len()];
+        let mut window_cuts: Vec<Vec<(T, Coord<T>)>> = vec![vec![]; window_edges.len()];
+
+        for (i, a) in subject_edges.iter().enumerate() {
+            for (j, b) in window_edges.iter().enumerate() {
+                if !bboxes_meet(a, b) {
+                    continue;
+                }
+
+                for (c, t, u) in a.contacts(b).into_iter().flatten() {
+                    subject_cuts[i].push((t, c));
+                    window_cuts[j].push((u, c));
+                }
+            }
+        }
+
+        let fragments = |edges: &[Line<T>], cuts: Vec<Vec<(T, Coord<T>)>>| -> Vec<Line<T>> {
+            edges
+                .iter()
+                .zip(cuts)
+                .flat_map(|(l, cuts)| cut(l, cuts))
+                .collect()
+        };
+        let subject_parts = fragments(&subject_edges, subject_cuts);
+        let window_parts = fragments(&window_edges, window_cuts);
+
+        let keys = |parts: &[Line<T>]| -> BTreeSet<(Key, Key)> {
+            parts.iter().map(|l| (key(&l.start), key(&l.end))).collect()
+        };
+        let (subject_keys, window_keys) = (keys(&subject_parts), keys(&window_parts));
+
+        // Shared boundary parts running the same way have both polygons on the same side, and
+        // are kept from the input. Parts running opposite ways lie between the polygons.
+        let kept = subject_parts
+            .into_iter()
+            .filter(|l| {
+                let (start, end) = (key(&l.start), key(&l.end));
+                if window_keys.contains(&(start, end)) {
+                    true
+                } else if window_keys.contains(&(end, start)) {
+                    false
+                } else {
+                    locate(&midpoint(l), &self.rings) == Some(true)
+                }
+            })
+            .chain(window_parts.into_iter().filter(|l| {
+                let (start, end) = (key(&l.start), key(&l.end));
+                !subject_keys.contains(&(start, end))
+                    && !subject_keys.contains(&(end, start))
+                    && locate(&midpoint(l), &subject) == Some(true)
+            }))
+            .collect();
+
+        assemble(trace(kept))
+    }
+}
+
+// Rings of the polygons without repeated coordinates, exteriors counter-clockwise and holes
+// clockwise. Rings without area are left out.
+fn polygon_rings<T: CoordFloat>(mp: &MultiPolygon<T>) -> Vec<LineString<T>> {
+    let ring = |ls: &LineString<T>, ccw: bool| {
+        let mut ls = util::dedup_coords(ls).into_owned();
+        ls.close();
+
+        let area = util::signed_area(&ls.0);
+        if ls.0.len() < 4 || area == T::zero() {
+            None
+        } else if (area > T::zero()) != ccw {
+            Some(ls.reverse())
+        } else {
+            Some(ls)
+        }
+    };
+
+    mp.iter()
+        .flat_map(|poly| {
+            [ring(poly.exterior(), true)]
+                .into_iter()
+                .chain(poly.interiors().iter().map(|ls| ring(ls, false)))
+        })
+        .flatten()
+        .collect()
+}
+
+fn ring_edges<T: CoordFloat>(rings: &[LineString<T>]) -> Vec<Line<T>> {
+    rings.iter().flat_map(|ls| ls.lines()).collect()
+}
+
+fn bboxes_meet<T: CoordFloat>(a: &Line<T>, b: &Line<T>) -> bool {
+    a.start.x.min(a.end.x) <= b.start.x.max(b.end.x)
+        && b.start.x.min(b.end.x) <= a.start.x.max(a.end.x)
+        && a.start.y.min(a.end.y) <= b.start.y.max(b.end.y)
+        && b.start.y.min(b.end.y) <= a.start.y.max(a.end.y)
+}
+
+// Line cut at the points given with their parameters along it
+fn cut<T: CoordFloat>(l: &Line<T>, mut cuts: Vec<(T, Coord<T>)>) -> Vec<Line<T>> {
+    cuts.push((T::zero(), l.start));
+    cuts.push((T::one(), l.end));
+    cuts.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    // end points come first and last, ahead of cuts at the same parameter
+    let mut coords = vec![l.start];
+    coords.extend(
+        cuts.into_iter()
+            .map(|(_, c)| c)
+            .filter(|c| *c != l.start && *c != l.end),
+    );
+    coords.push(l.end);
+    coords.dedup();
+
+    coords.windows(2).map(|w| Line::new(w[0], w[1])).collect()
+}
+
+fn midpoint<T: CoordFloat>(l: &Line<T>) -> Coord<T> {
+    let two = T::one() + T::one();
+    ((l.start.x + l.end.x) / two, (l.start.y + l.end.y) / two).into()
+}
+
+// Even-odd location of coordinate among rings: None on a ring boundary
+fn locate<T: CoordFloat>(c: &Coord<T>, rings: &[LineString<T>]) -> Option<bool> {
+    let mut inside = false;
+    for ls in rings {
+        inside ^= util::point_in_ring(c, ls)?;
+    }
+    Some(inside)
+}
+
+// Links kept ring parts into closed rings. Where several parts leave a point, the one turning
+// most to the left is taken, which keeps polygons touching at a point apart.
+fn trace<T: CoordFloat>(parts: Vec<Line<T>>) -> Vec<LineString<T>> {
+    let mut starts: BTreeMap<Key, Vec<usize>> = BTreeMap::new();
+    for (i, l) in parts.iter().enumerate() {
+        starts.entry(key(&l.start)).or_default().push(i);
+    }
+
+    // turn from a to b, with going back the way a came last
+    let turn = |a: &Line<T>, b: &Line<T>| {
+        let (u, v) = (a.delta(), b.delta());
+        let (cross, dot) = (u.x * v.y - u.y * v.x, u.x * v.x + u.y * v.y);
+        if cross == T::zero() && dot < T::zero() {
+            -T::infinity()
+        } else {
+            cross.atan2(dot)
+        }
+    };
+
+    let mut used = vec![false; parts.len()];
+    let mut rings = vec![];
+
+    for first in 0..parts.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+
+        let mut coords = vec![parts[first].start];
+        let mut current = first;
+
+        loop {
+            let a = parts[current];
+            coords.push(a.end);
+
+            let next = starts
+                .get(&key(&a.end))
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|&i| !used[i] || i == first)
+                .max_by(|&i, &j| {
+                    turn(&a, &parts[i])
+                        .partial_cmp(&turn(&a, &parts[j]))
+                        .unwrap_or(Ordering::Equal)
+                });
+
+            match next {
+                Some(i) if i == first => {
+                    rings.push(LineString::new(coords));
+                    break;
+                }
+                Some(i) => {
+                    used[i] = true;
+                    current = i;
+                }
+                None => {
+                    debug!("open ring part chain dropped");
+                    break;
+                }
+            }
+        }
+    }
+
+    rings
+}
+
+// Counter-clockwise rings are exteriors, and clockwise rings are holes of the smallest exterior
+// around them
+fn assemble<T: CoordFloat>(rings: Vec<LineString<T>>) -> MultiPolygon<T> {
+    let (exteriors, holes): (Vec<_>, Vec<_>) = rings
+        .into_iter()
+        .filter(|ls| ls.0.len() > 3)
+        .map(|ls| (util::signed_area(&ls.0), ls))
+        .filter(|(area, _)| *area != T::zero())
+        .partition(|(area, _)| *area > T::zero());
+
+    let mut polys: Vec<(T, Polygon<T>)> = exteriors
+        .into_iter()
+        .map(|(area, ls)| (area, Polygon::new(ls, vec![])))
+        .collect();
+
+    for (_, hole) in holes {
+        let parent = polys
+            .iter_mut()
+            .filter(|(_, poly)| ring_in_ring(&hole, poly.exterior()))
+            .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        match parent {
+            Some((_, poly)) => poly.interiors_push(hole),
+            None => debug!("hole outside shells dropped"),
+        }
+    }
+
+    polys.into_iter().map(|(_, poly)| poly).collect()
+}
+
+// Ring lies inside the other, which it may touch
+fn ring_in_ring<T: CoordFloat>(ls: &LineString<T>, other: &LineString<T>) -> bool {
+    ls.0.iter()
+        .copied()
+        .chain(ls.lines().map(|l| midpoint(&l)))
+        .find_map(|c| util::point_in_ring(&c, other))
+        .unwrap_or(false)
+}
+
+fn lines_geometry<T: CoordFloat>(mut pieces: Vec<LineString<T>>) -> Option<Geometry<T>> {
+    match pieces.len() {
+        0 => None,
+        1 => pieces.pop().map(Geometry::LineString),
+        _ => Some(Geometry::MultiLineString(MultiLineString::new(pieces))),
+    }
+}
+
+fn polygons_geometry<T: CoordFloat>(mut mp: MultiPolygon<T>) -> Option<Geometry<T>> {
+    match mp.0.len() {
+        0 => None,
+        1 => mp.0.pop().map(Geometry::Polygon),
+        _ => Some(Geometry::MultiPolygon(mp)),
+    }
+}
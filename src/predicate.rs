@@ -0,0 +1,167 @@
+use crate::{util, ClipRect, IntersectionBackend, MaybeSync};
+use alloc::vec::Vec;
+use geo_types::{Coord, CoordFloat, Geometry, Line, LineString, MultiPolygon, Polygon};
+
+// Relation of geometry to the clipping window, see `ClipRect::classify`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Inside,
+    Crossing,
+    Outside,
+}
+
+// Spatial relation of polygon to the clipping window, see `ClipRect::clip_relate`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipRelation<T: CoordFloat> {
+    // No common points
+    Disjoint,
+    // Common boundary points only, such as a shared edge
+    Touches,
+    // Interiors intersect, with the intersection
+    Overlaps(MultiPolygon<T>),
+    // Polygon covers the whole window
+    Contains,
+    // Polygon lies within the window
+    Within,
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Relates polygon to the window in DE-9IM terms, clipping it when interiors overlap.
+    // Distinguishes polygons touching the window boundary from disjoint ones.
+    pub fn clip_relate(&self, g: &Polygon<T>) -> ClipRelation<T> {
+        let geom = Geometry::Polygon(g.clone());
+        if !self.intersects_with(&geom, true) {
+            return ClipRelation::Disjoint;
+        }
+
+        let clipped = self.clip_polygon(g);
+        match clipped.0.as_slice() {
+            [] => ClipRelation::Touches,
+            _ if self.classify(&geom) == Relation::Inside => ClipRelation::Within,
+            [poly] if self.is_window(poly) => ClipRelation::Contains,
+            _ => ClipRelation::Overlaps(clipped),
+        }
+    }
+
+    // Classifies geometry by its bounding box against the window. Cheap, but a geometry with
+    // bounding box crossing the window may still miss it; see `classify_refined`.
+    pub fn classify(&self, g: &Geometry<T>) -> Relation {
+        self.box_relation(g, T::zero())
+    }
+
+    // Classification with `Crossing` bounding boxes refined by an exact intersection test.
+    // Geometry touching the window boundary counts as crossing.
+    pub fn classify_refined(&self, g: &Geometry<T>) -> Relation {
+        match self.classify(g) {
+            Relation::Crossing if !self.intersects_with(g, true) => Relation::Outside,
+            relation => relation,
+        }
+    }
+
+    // Checks if geometry has any points in common with the rect, including its boundary.
+    // Bounding boxes settle most cases, the rest is tested segment by segment without building
+    // the clipped output, so this is much cheaper than `clip(..).is_some()`.
+    pub fn intersects(&self, g: &Geometry<T>) -> bool {
+        match self.classify(g) {
+            Relation::Inside => true,
+            Relation::Outside => false,
+            Relation::Crossing => self.intersects_with(g, true),
+        }
+    }
+
+    // Checks if geometry lies entirely within the rect, its boundary included, so that it can be
+    // kept without clipping. As the rect is convex, no edge crosses the boundary when every
+    // vertex is inside. Empty geometry is not contained.
+    pub fn contains(&self, g: &Geometry<T>) -> bool {
+        let (mut any, mut all) = (false, true);
+        util::for_each_coord(g, &mut |c| {
+            any = true;
+            all &= self.rect().contains_coord(c);
+        });
+        any && all
+    }
+
+    // Checks if the rect lies fully inside the polygon, so that clipping the polygon gives the
+    // rect itself. Rings may touch the rect boundary from outside, but no ring may reach the
+    // rect interior, and a rect inside a hole is not inside the polygon.
+    pub fn rect_inside_polygon(&self, poly: &Polygon<T>) -> bool {
+        let lines = |ls: &LineString<T>| ls.lines().collect::<Vec<_>>();
+
+        ![poly.exterior()]
+            .into_iter()
+            .chain(poly.interiors())
+            .any(|ls| ls.lines().any(|l| self.segment_hits(&l, false)))
+            && self.rect().is_contained(&lines(poly.exterior()))
+            && !poly
+                .interiors()
+                .iter()
+                .any(|ls| self.rect().is_contained(&lines(ls)))
+    }
+
+    // Checks if geometry intersects the rect.
+    // With `touch_counts` false, geometry merely touching the rect boundary does not intersect:
+    // some of it has to reach the rect interior, as with DE-9IM interior intersection.
+    pub fn intersects_with(&self, g: &Geometry<T>, touch_counts: bool) -> bool {
+        use Geometry::*;
+
+        match g {
+            Point(p) => self.coord_hits(&p.0, touch_counts),
+            Line(l) => self.segment_hits(l, touch_counts),
+            LineString(ls) => self.linestring_hits(ls, touch_counts),
+            Polygon(poly) => self.polygon_hits(poly, touch_counts),
+            MultiPoint(mp) => mp.iter().any(|p| self.coord_hits(&p.0, touch_counts)),
+            MultiLineString(mls) => mls.iter().any(|ls| self.linestring_hits(ls, touch_counts)),
+            MultiPolygon(mp) => mp.iter().any(|poly| self.polygon_hits(poly, touch_counts)),
+            GeometryCollection(gc) => gc.iter().any(|g| self.intersects_with(g, touch_counts)),
+            Rect(r) => self.polygon_hits(&r.to_polygon(), touch_counts),
+            Triangle(t) => self.polygon_hits(&t.to_polygon(), touch_counts),
+        }
+    }
+
+    fn coord_hits(&self, c: &Coord<T>, touch_counts: bool) -> bool {
+        let r = self.rect();
+
+        if touch_counts {
+            r.x0 <= c.x && c.x <= r.x1 && r.y0 <= c.y && c.y <= r.y1
+        } else {
+            r.coord_inside(c)
+        }
+    }
+
+    // Segment hits the interior when the middle of its part within the rect is strictly inside
+    fn segment_hits(&self, l: &Line<T>, touch_counts: bool) -> bool {
+        match self.rect().segment_span(l) {
+            Some(_) if touch_counts => true,
+            Some((t0, t1)) if t0 < t1 => {
+                let t = (t0 + t1) / (T::one() + T::one());
+                self.rect().coord_inside(&(l.start + l.delta() * t))
+            }
+            _ => false,
+        }
+    }
+
+    fn linestring_hits(&self, ls: &LineString<T>, touch_counts: bool) -> bool {
+        match ls.0.as_slice() {
+            [c] => self.coord_hits(c, touch_counts),
+            _ => ls.lines().any(|l| self.segment_hits(&l, touch_counts)),
+        }
+    }
+
+    // Without ring edges hitting the rect, the rect is either fully inside or outside of the
+    // polygon, which is settled by testing the rect center.
+    fn polygon_hits(&self, poly: &Polygon<T>, touch_counts: bool) -> bool {
+        let r = self.rect();
+        let two = T::one() + T::one();
+        let center: Coord<T> = ((r.x0 + r.x1) / two, (r.y0 + r.y1) / two).into();
+
+        [poly.exterior()]
+            .into_iter()
+            .chain(poly.interiors())
+            .any(|ls| self.linestring_hits(ls, touch_counts))
+            || (util::point_in_ring(&center, poly.exterior()).unwrap_or(false)
+                && !poly
+                    .interiors()
+                    .iter()
+                    .any(|ls| util::point_in_ring(&center, ls).unwrap_or(true)))
+    }
+}
@@ -1,7 +1,17 @@
-use crate::geom::{CoordExt, LineExt};
+use crate::backend::{FloatIntersection, IntersectionBackend};
+use crate::geom::CoordExt;
 use crate::util::rough_eq;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "counters")]
+use core::sync::atomic::{AtomicUsize, Ordering};
 use geo_types::{Coord, CoordFloat, Line, Point};
-use log::debug;
+use num_traits::Float;
+
+// Number of edge intersection tests run by `Rect::clip_segment`.
+// Only available with the `counters` feature, used for verifying fast paths.
+#[cfg(feature = "counters")]
+pub static INTERSECTION_CALLS: AtomicUsize = AtomicUsize::new(0);
 
 pub struct Rect<T: CoordFloat> {
     // bounding coordinates
@@ -12,15 +22,18 @@ pub struct Rect<T: CoordFloat> {
 
     // rect lines
     pub lines: [Line<T>; 4],
+
+    // boundary comparison tolerance, see `with_tolerance`
+    pub(crate) tolerance: Option<T>,
 }
 
 impl<T: CoordFloat> Rect<T> {
     pub fn new(x0: T, y0: T, x1: T, y1: T) -> Self {
         debug!("{x0:?},{y0:?},{x1:?},{y1:?}");
 
-        // make sure to wind lines counter-clockwise
-        //let (x0, x1) = (x0.min(x1), x0.max(x1));
-        //let (y0, y1) = (y0.min(y1), y0.max(y1));
+        // make sure to wind lines counter-clockwise, whichever way the corners are given
+        let (x0, x1) = (x0.min(x1), x0.max(x1));
+        let (y0, y1) = (y0.min(y1), y0.max(y1));
 
         let lines = [
             Line::new((x0, y0), (x1, y0)),
@@ -35,9 +48,49 @@ impl<T: CoordFloat> Rect<T> {
             x1,
             y1,
             lines,
+            tolerance: None,
         }
     }
 
+    // Matches points to the boundary within eps: points within eps of the rect count as
+    // contained, and points within eps of an edge lie on it.
+    // By default containment is exact, and points are matched to edges within 0.00001, widened
+    // to the rounding of T for large values (see `util::rough_eq`).
+    pub fn with_tolerance(mut self, eps: T) -> Self {
+        self.tolerance = Some(eps.abs());
+        self
+    }
+
+    // Values are equal within the boundary tolerance
+    pub(crate) fn near(&self, a: T, b: T) -> bool {
+        near(self.tolerance, a, b)
+    }
+
+    // Edge line by index, as wound by the constructor: 0 = bottom, 1 = right, 2 = top, 3 = left.
+    // Panics on index out of 0..4.
+    pub fn edge(&self, index: usize) -> &Line<T> {
+        &self.lines[index]
+    }
+
+    // Unit normal of edge pointing inside the rect
+    pub fn edge_normal(&self, index: usize) -> Coord<T> {
+        let (zero, one) = (T::zero(), T::one());
+
+        match index {
+            0 => (zero, one),
+            1 => (-one, zero),
+            2 => (zero, -one),
+            3 => (one, zero),
+            _ => panic!("edge index out of range: {index}"),
+        }
+        .into()
+    }
+
+    // Rect is collapsed into a single point
+    pub fn is_point(&self) -> bool {
+        self.x0 == self.x1 && self.y0 == self.y1
+    }
+
     fn corner_points(&self) -> [&Coord<T>; 4] {
         [
             &self.lines[0].start,
@@ -47,8 +100,9 @@ impl<T: CoordFloat> Rect<T> {
         ]
     }
 
-    fn contains_coord(&self, c: &Coord<T>) -> bool {
-        self.x0 <= c.x && c.x <= self.x1 && self.y0 <= c.y && c.y <= self.y1
+    pub(crate) fn contains_coord(&self, c: &Coord<T>) -> bool {
+        let eps = self.tolerance.unwrap_or(T::zero());
+        self.x0 - eps <= c.x && c.x <= self.x1 + eps && self.y0 - eps <= c.y && c.y <= self.y1 + eps
     }
 
     pub fn coord_inside(&self, c: &Coord<T>) -> bool {
@@ -64,6 +118,26 @@ impl<T: CoordFloat> Rect<T> {
         !self.contains_coord(&s.start) && !self.contains_coord(&s.end)
     }
 
+    // Region code of a coordinate in Cohen-Sutherland style:
+    // one bit per rect side the coordinate lies strictly beyond.
+    pub(crate) fn outcode(&self, c: &Coord<T>) -> u8 {
+        let mut code = 0;
+
+        if c.x < self.x0 {
+            code |= 1;
+        } else if c.x > self.x1 {
+            code |= 2;
+        }
+
+        if c.y < self.y0 {
+            code |= 4;
+        } else if c.y > self.y1 {
+            code |= 8;
+        }
+
+        code
+    }
+
     fn is_corner(&self, p: &Coord<T>) -> bool {
         self.corner_points().into_iter().any(|corner| p == corner)
     }
@@ -77,28 +151,72 @@ impl<T: CoordFloat> Rect<T> {
     }
 
     pub fn clip_segment(&self, seg: &Line<T>) -> Option<Line<T>> {
+        self.clip_segment_with(seg, &FloatIntersection)
+    }
+
+    // Segment clipping with edge intersections computed by the given backend
+    pub fn clip_segment_with(
+        &self,
+        seg: &Line<T>,
+        backend: &impl IntersectionBackend<T>,
+    ) -> Option<Line<T>> {
+        self.clip_segment_reusing(seg, backend, 0)
+    }
+
+    // Segment clipping with the edge intersections at the segment start known beforehand: bit i
+    // of `start_sides` set means side i meets the segment at its start, see `start_sides`.
+    pub(crate) fn clip_segment_reusing(
+        &self,
+        seg: &Line<T>,
+        backend: &impl IntersectionBackend<T>,
+        start_sides: u8,
+    ) -> Option<Line<T>> {
         // Check if fully inside rect
-        if self.contains_segment(&seg) {
-            return Some(seg.clone());
+        if self.contains_segment(seg) {
+            return Some(*seg);
         }
 
-        // Find unique intersection points
-        let mut isects = self
-            .lines
-            .iter()
-            .filter_map(|side| side.intersection(seg))
-            .fold(vec![], |mut acc, p| {
-                if !acc.contains(&p) {
-                    acc.push(p);
-                }
-                acc
-            })
-            .into_iter();
+        // Find unique intersection points. A segment meets the rect boundary at two points at
+        // most, so they are kept in a fixed array.
+        let mut isects: [Option<Coord<T>>; 2] = [None, None];
+        for (i, side) in self.lines.iter().enumerate() {
+            let p = if start_sides & (1 << i) != 0 {
+                Some(seg.start)
+            } else {
+                #[cfg(feature = "counters")]
+                INTERSECTION_CALLS.fetch_add(1, Ordering::Relaxed);
+
+                backend.intersection(side, seg)
+            };
 
-        let p1 = isects.next();
-        let p2 = isects.next();
+            match (p, isects) {
+                (Some(p), [None, _]) => isects[0] = Some(p),
+                (Some(p), [Some(p1), None]) if p != p1 => isects[1] = Some(p),
+                _ => {}
+            }
+        }
+        let [p1, p2] = isects;
 
         match (p1, p2) {
+            // Two intersections with an endpoint inside:
+            // Rounding gives two points apart at a corner the segment exits through. The inside
+            // endpoint is kept as is, so that vertices inside the rect pass through clipping
+            // unchanged, and joined to the intersection nearer the other end.
+            (Some(p1), Some(p2)) if self.coord_inside(&seg.start) => {
+                let exit = |c: &Coord<T>| seg.end.manhattan_dist(c);
+                Some(Line::new(
+                    seg.start,
+                    if exit(&p1) <= exit(&p2) { p1 } else { p2 },
+                ))
+            }
+            (Some(p1), Some(p2)) if self.coord_inside(&seg.end) => {
+                let entry = |c: &Coord<T>| seg.start.manhattan_dist(c);
+                Some(Line::new(
+                    if entry(&p1) <= entry(&p2) { p1 } else { p2 },
+                    seg.end,
+                ))
+            }
+
             // Two intersections:
             // Create new segment from intersection points.
             // To preserve direction, check which original point is closer to first point
@@ -123,9 +241,9 @@ impl<T: CoordFloat> Rect<T> {
 
                 // Decide segment direction
                 if self.contains_coord(&seg.start) {
-                    Some(Line::new(seg.start.clone(), p1))
+                    Some(Line::new(seg.start, p1))
                 } else {
-                    Some(Line::new(p1, seg.end.clone()))
+                    Some(Line::new(p1, seg.end))
                 }
             }
 
@@ -134,84 +252,178 @@ impl<T: CoordFloat> Rect<T> {
         }
     }
 
+    // Side meeting a segment at its start as a bit, given the region code of its end: the side
+    // the start lies on, while the segment runs across the side into the rect. The start is the
+    // edge intersection on this side, so it needs not be computed. Starts at corners are left
+    // to the edge intersections, as the segment may leave through the other side there.
+    pub(crate) fn start_sides(&self, seg: &Line<T>, code_end: u8) -> u8 {
+        let (s, d) = (seg.start, seg.end - seg.start);
+        let within_x = self.x0 < s.x && s.x < self.x1;
+        let within_y = self.y0 < s.y && s.y < self.y1;
+
+        // side index, the region code bit beyond it, and whether the segment starts across it
+        [
+            (0, 4, s.y == self.y0 && within_x && d.y != T::zero()),
+            (1, 2, s.x == self.x1 && within_y && d.x != T::zero()),
+            (2, 8, s.y == self.y1 && within_x && d.y != T::zero()),
+            (3, 1, s.x == self.x0 && within_y && d.x != T::zero()),
+        ]
+        .into_iter()
+        .filter(|&(_, beyond, across)| across && code_end & beyond == 0)
+        .fold(0, |sides, (i, _, _)| sides | 1 << i)
+    }
+
     // Returns vector of grouped continuous segments.
     pub fn clip_segments(&self, segments: &[Line<T>]) -> Vec<Vec<Line<T>>> {
-        // Get clipped segments
-        let segments: Vec<Line<T>> = segments
-            .into_iter()
-            .filter_map(|seg| self.clip_segment(seg))
-            .collect();
+        let mut groups = vec![];
+        self.clip_segments_into(segments, &mut groups);
+        groups
+    }
 
-        // Early return on empty segments list
-        if segments.is_empty() {
-            return vec![];
-        }
+    // Like `clip_segments`, writing the groups into a buffer reused between calls. The buffer
+    // is cleared first, and the group vectors left in it from earlier calls are refilled.
+    pub fn clip_segments_into(&self, segments: &[Line<T>], groups: &mut Vec<Vec<Line<T>>>) {
+        self.clip_segments_grouped(segments, &FloatIntersection, groups)
+    }
 
-        // Find first splitpoint
-        // since we must assume first and last segments are connected.
-        let seg_len = segments.len();
-        let mut offset = 0;
-        for i in 0..segments.len() {
-            if segments[i].end != segments[(i + 1) % seg_len].start {
-                offset = (i + 1) % seg_len;
-                break;
-            }
-        }
+    // Grouped continuous segments as (group, started by crossing, ended by crossing). A group
+    // end not made by crossing the rect boundary is an end point of the input segments.
+    pub fn clip_segments_tagged(&self, segments: &[Line<T>]) -> Vec<(Vec<Line<T>>, bool, bool)> {
+        self.clip_segments_tagged_with(segments, &FloatIntersection)
+    }
 
-        // Group segments, starting from offset and looping around
-        let groups = segments
-            .into_iter()
-            .cycle()
-            .skip(offset)
-            .take(seg_len)
-            .fold(vec![], |mut acc: Vec<Vec<Line<T>>>, seg: Line<T>| {
-                if let Some(segs) = acc.last_mut() {
-                    if let Some(last) = segs.last() {
-                        if last.end == seg.start {
-                            // Continue segment group
-                            segs.push(seg);
-                        } else {
-                            // Start another group
-                            acc.push(vec![seg]);
-                        }
-                    }
-                } else {
-                    acc.push(vec![seg]);
-                }
+    // Tagged segment clipping with edge intersections computed by the given backend
+    pub fn clip_segments_tagged_with(
+        &self,
+        segments: &[Line<T>],
+        backend: &impl IntersectionBackend<T>,
+    ) -> Vec<(Vec<Line<T>>, bool, bool)> {
+        self.clip_segments_tagged_split(segments, backend, |_| {})
+    }
+
+    // Tagged grouping passing the parts of segments outside the rect to `outside`, see
+    // `clip_segments_split`
+    pub(crate) fn clip_segments_tagged_split(
+        &self,
+        segments: &[Line<T>],
+        backend: &impl IntersectionBackend<T>,
+        outside: impl FnMut(Line<T>),
+    ) -> Vec<(Vec<Line<T>>, bool, bool)> {
+        let input_start = segments.first().map(|seg| seg.start);
+        let input_end = segments.last().map(|seg| seg.end);
 
-                acc
-            });
+        let mut groups = vec![];
+        self.clip_segments_split(segments, backend, &mut groups, outside);
+
+        // Closed input has no end points: its groups end by crossing unless the whole input was
+        // kept as one closed group.
+        let closed = input_start == input_end;
 
         groups
+            .into_iter()
+            .map(|group| {
+                let (start, end) = (group[0].start, group[group.len() - 1].end);
+                if closed {
+                    (group, start != end, start != end)
+                } else {
+                    (group, Some(start) != input_start, Some(end) != input_end)
+                }
+            })
+            .collect()
     }
 
-    // Indexes a point along the rect perimeter in 0..4
-    // Can be used to sort intersection points.
-    // Returns -1.0 on points not on perimeter.
-    pub fn perimeter_index(&self, p: &Coord<T>) -> f64 {
-        let mut f: f64 = 0.0;
-        let corners = self.corner_points();
+    // Clips segments, grouping consecutive connected ones as they are clipped. As the input may
+    // be a ring, the group at the end of the input is joined with the group at its start when
+    // they connect, and the group at the start is moved last otherwise.
+    pub(crate) fn clip_segments_grouped(
+        &self,
+        segments: &[Line<T>],
+        backend: &impl IntersectionBackend<T>,
+        groups: &mut Vec<Vec<Line<T>>>,
+    ) {
+        self.clip_segments_split(segments, backend, groups, |_| {})
+    }
 
-        for i in 0..4 {
-            let c1 = corners[i];
-            let c2 = corners[(i + 1) % 4];
+    // Grouped clipping passing the parts of segments outside the rect to `outside` in input
+    // order, cut at the same edge intersections as the parts inside. Segments missing the rect
+    // are outside as a whole.
+    pub(crate) fn clip_segments_split(
+        &self,
+        segments: &[Line<T>],
+        backend: &impl IntersectionBackend<T>,
+        groups: &mut Vec<Vec<Line<T>>>,
+        mut outside: impl FnMut(Line<T>),
+    ) {
+        let mut used = 0;
+
+        // Consecutive segments share an endpoint, so its region code is carried over to the
+        // next segment instead of being computed twice. A segment starting on the rect boundary
+        // meets the side there, so that edge intersection is taken as is instead of being run.
+        // Segments with both endpoints beyond the same rect side are rejected
+        // without running any edge intersections.
+        let mut prev: Option<(Coord<T>, u8)> = None;
+        for seg in segments {
+            let code_start = match prev {
+                Some((c, code)) if c == seg.start => code,
+                _ => self.outcode(&seg.start),
+            };
+            let code_end = self.outcode(&seg.end);
+            prev = Some((seg.end, code_end));
+
+            if code_start & code_end != 0 {
+                outside(*seg);
+                continue;
+            }
 
-            if i % 2 == 0 {
-                if rough_eq(p.y, c1.y) {
-                    f += (p.x - c1.x).to_f64().unwrap() / (c2.x - c1.x).to_f64().unwrap();
-                    break;
-                }
+            let start_sides = if code_start == 0 {
+                self.start_sides(seg, code_end)
             } else {
-                if rough_eq(p.x, c1.x) {
-                    f += (p.y - c1.y).to_f64().unwrap() / (c2.y - c1.y).to_f64().unwrap();
-                    break;
+                0
+            };
+
+            let Some(clipped) = self.clip_segment_reusing(seg, backend, start_sides) else {
+                outside(*seg);
+                continue;
+            };
+
+            if clipped.start != seg.start {
+                outside(Line::new(seg.start, clipped.start));
+            }
+            if clipped.end != seg.end {
+                outside(Line::new(clipped.end, seg.end));
+            }
+            let seg = clipped;
+
+            match groups[..used].last_mut() {
+                Some(group) if group.last().map(|l| l.end) == Some(seg.start) => group.push(seg),
+                _ => {
+                    if used < groups.len() {
+                        groups[used].clear();
+                    } else {
+                        groups.push(vec![]);
+                    }
+                    groups[used].push(seg);
+                    used += 1;
                 }
             }
+        }
 
-            f += 1.0;
+        groups.truncate(used);
+
+        if used > 1 {
+            groups.rotate_left(1);
+            if groups[used - 2].last().map(|l| l.end) == Some(groups[used - 1][0].start) {
+                let first = groups.pop().unwrap();
+                groups[used - 2].extend(first);
+            }
         }
+    }
 
-        f
+    // Indexes a point along the rect perimeter in 0..4
+    // Can be used to sort intersection points.
+    // Returns None on points not on the perimeter.
+    pub fn perimeter_index(&self, p: &Coord<T>) -> Option<f64> {
+        Perimeter::new(self).index(p)
     }
 
     // Returns true if perimeter index a is closer to i than b
@@ -237,29 +449,152 @@ impl<T: CoordFloat> Rect<T> {
         debug!("nodes between: {a}, {b}");
 
         // truncate to indexes
-        let i = a.ceil() as usize;
+        let i = Float::ceil(a) as usize;
         let j = b as usize;
         debug!("a={a}, b={b}, i={i}, j={j}");
 
         (i..=j).map(|i| self.lines[i % 4].start).collect()
     }
 
+    // Checks if segment crosses the ray cast from the rect center along positive x axis.
+    // Used in ray casting the rect against polygon rings. Crossings are counted half-open in y,
+    // so rings passing through ray at a vertex or threading the rect corners count correctly.
+    pub(crate) fn center_ray_crosses(&self, l: &Line<T>) -> bool {
+        let two = T::one() + T::one();
+        let (cx, cy) = ((self.x0 + self.x1) / two, (self.y0 + self.y1) / two);
+
+        (l.start.y > cy) != (l.end.y > cy)
+            && cx < l.start.x + (cy - l.start.y) / (l.end.y - l.start.y) * (l.end.x - l.start.x)
+    }
+
+    // Parameter range (t0, t1) of the segment part within the closed rect, Liang-Barsky style.
+    // Touching at a single point gives t0 == t1.
+    pub(crate) fn segment_span(&self, seg: &Line<T>) -> Option<(T, T)> {
+        let (dx, dy) = (seg.dx(), seg.dy());
+        let (mut t0, mut t1) = (T::zero(), T::one());
+
+        for (p, q) in [
+            (-dx, seg.start.x - self.x0),
+            (dx, self.x1 - seg.start.x),
+            (-dy, seg.start.y - self.y0),
+            (dy, self.y1 - seg.start.y),
+        ] {
+            if p == T::zero() {
+                if q < T::zero() {
+                    return None;
+                }
+            } else if p < T::zero() {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+
+        if t0 <= t1 {
+            Some((t0, t1))
+        } else {
+            None
+        }
+    }
+
+    // Checks if the rect lies inside the ring made of the given lines, by ray casting from the
+    // rect center. Only meaningful when no line crosses the rect, as when clipping the ring
+    // leaves no pieces: the rect is then either fully inside the ring or fully outside it.
     pub fn is_contained(&self, lines: &[Line<T>]) -> bool {
-        let beam_x = Line::new((self.x0, self.y0), (T::infinity(), self.y0));
-        let beam_y = Line::new((self.x0, self.y0), (self.x0, T::infinity()));
+        lines.iter().filter(|l| self.center_ray_crosses(l)).count() % 2 == 1
+    }
+}
 
-        let isects_x = lines
-            .into_iter()
-            .filter_map(|l| beam_x.intersection(l))
-            .count();
+// Rect corners and edge lengths for indexing points along the perimeter. Computed once per
+// clipper and shared by all rings instead of being set up again for every indexed point.
+pub(crate) struct Perimeter<T: CoordFloat> {
+    corners: [Coord<T>; 4],
+    spans: [f64; 4],
+    tolerance: Option<T>,
+}
 
-        let isects_y = lines
-            .into_iter()
-            .filter_map(|l| beam_y.intersection(l))
-            .count();
+impl<T: CoordFloat> Perimeter<T> {
+    pub(crate) fn new(r: &Rect<T>) -> Self {
+        let corners = r.corner_points().map(|c| *c);
+        let spans = core::array::from_fn(|i| {
+            let (c1, c2) = (corners[i], corners[(i + 1) % 4]);
+            if i % 2 == 0 {
+                (c2.x - c1.x).to_f64().unwrap()
+            } else {
+                (c2.y - c1.y).to_f64().unwrap()
+            }
+        });
+
+        Self {
+            corners,
+            spans,
+            tolerance: r.tolerance,
+        }
+    }
+
+    // See `Rect::perimeter_index`
+    pub(crate) fn index(&self, p: &Coord<T>) -> Option<f64> {
+        let near = |a: T, b: T| near(self.tolerance, a, b);
+        let within =
+            |v: T, a: T, b: T| (v >= a.min(b) && v <= a.max(b)) || near(v, a) || near(v, b);
 
-        //println!("isects={isects}");
+        (0..4).find_map(|i| {
+            let (c1, c2) = (self.corners[i], self.corners[(i + 1) % 4]);
+
+            let along = if i % 2 == 0 {
+                (near(p.y, c1.y) && within(p.x, c1.x, c2.x)).then(|| p.x - c1.x)
+            } else {
+                (near(p.x, c1.x) && within(p.y, c1.y, c2.y)).then(|| p.y - c1.y)
+            };
+
+            along.map(|d| i as f64 + d.to_f64().unwrap() / self.spans[i])
+        })
+    }
+
+    // Checks if point is at a rect corner
+    pub(crate) fn at_corner(&self, p: &Coord<T>) -> bool {
+        self.index(p).is_some_and(|i| Float::fract(i) == 0.0)
+    }
+
+    // Order of pieces meeting the rect at the same corner, by the direction they arrive from or
+    // leave toward, for points with perimeter index `i`. Goes from 0 along the edge before the
+    // corner to 1 along the edge after it, following the perimeter counter-clockwise. Points off
+    // the corners, and directions with no length, order as 0.
+    pub(crate) fn corner_order(&self, i: f64, at: &Coord<T>, toward: &Coord<T>) -> f64 {
+        if Float::fract(i) != 0.0 {
+            return 0.0;
+        }
+
+        let k = i as usize % 4;
+        let corner = self.corners[k];
+        let (next, prev) = (self.corners[(k + 1) % 4], self.corners[(k + 3) % 4]);
+        let d = *toward - *at;
+
+        // components of the direction along both edges at the corner
+        let along = |c: Coord<T>| {
+            let e = c - corner;
+            let len = e.x.abs() + e.y.abs();
+            if len == T::zero() {
+                return 0.0;
+            }
+            ((d.x * e.x + d.y * e.y) / len)
+                .max(T::zero())
+                .to_f64()
+                .unwrap()
+        };
+        let (after, before) = (along(next), along(prev));
+
+        if after + before > 0.0 {
+            after / (after + before)
+        } else {
+            0.0
+        }
+    }
+}
 
-        (isects_x % 2 == 1) && (isects_y % 2 == 1)
+fn near<T: CoordFloat>(tolerance: Option<T>, a: T, b: T) -> bool {
+    match tolerance {
+        Some(eps) => (a - b).abs() <= eps,
+        None => rough_eq(a, b),
     }
 }
@@ -0,0 +1,310 @@
+use crate::exclusion::ExclusionClipRect;
+use crate::geom::Reverse;
+use crate::stats::StatsSink;
+use crate::{snap_geometry, util, ClipError, ClipRect, IntersectionBackend, MaybeSync};
+use alloc::vec;
+use alloc::vec::Vec;
+use geo_types::{
+    CoordFloat, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPolygon,
+    Polygon,
+};
+
+// Side of the window a split member lies on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Inside,
+    Outside,
+}
+
+// Polygon ring split at the window boundary: the ring as given to the ring clippers, its
+// clipped rings on either side, and whether the boundary cut it
+struct SplitRing<T: CoordFloat> {
+    ring: LineString<T>,
+    inside: Vec<LineString<T>>,
+    outside: Vec<LineString<T>>,
+    cut: bool,
+}
+
+type Halves<T> = (Option<Geometry<T>>, Option<Geometry<T>>);
+type PolygonHalves<T> = (Vec<Polygon<T>>, Vec<Polygon<T>>);
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Splits geometry at the window boundary into the part inside and the part outside of it,
+    // returned as collection members tagged by the parallel side list. Sides with nothing on
+    // them are left out. Polygon parts on either side share their edges along the boundary.
+    pub fn clip_split_tagged(&self, g: &Geometry<T>) -> (GeometryCollection<T>, Vec<Side>)
+    where
+        B: Clone,
+    {
+        let mut members = vec![];
+        let mut sides = vec![];
+
+        let (inside, outside) = self.split(g);
+        for (g, side) in [(inside, Side::Inside), (outside, Side::Outside)] {
+            if let Some(g) = g {
+                members.push(g);
+                sides.push(side);
+            }
+        }
+
+        (GeometryCollection::new_from(members), sides)
+    }
+
+    // Splits geometry into the parts (inside, outside) of the window in one pass. Each segment
+    // is clipped once, and both parts are sewn from the same edge intersections, so they meet
+    // exactly. Both parts are clipped with the settings of the window, and input that `clip`
+    // rejects gives None on both sides.
+    pub fn split(&self, g: &Geometry<T>) -> Halves<T>
+    where
+        B: Clone,
+    {
+        let Ok(Some(mut g)) = self.checked_input(g, self.strict) else {
+            return (None, None);
+        };
+        if let Some(scale) = self.precision {
+            snap_geometry(g.to_mut(), scale);
+        }
+
+        let Some(excl) = self.outside_window(&g) else {
+            return (None, None);
+        };
+        let Ok(halves) = self.split_geometry(&g, &excl) else {
+            return (None, None);
+        };
+
+        match self.precision {
+            Some(scale) => {
+                let snapped = |mut g: Geometry<T>| {
+                    snap_geometry(&mut g, scale);
+                    g
+                };
+                (halves.0.map(snapped), halves.1.map(snapped))
+            }
+            None => halves,
+        }
+    }
+
+    // Part of geometry outside the window, or None when it lies fully inside. Polygons get the
+    // window cut out of them, as a hole when they surround it. Clipped with the settings of the
+    // window, such as its precision model and winding order.
+    pub fn clip_outside(&self, g: &Geometry<T>) -> Option<Geometry<T>>
+    where
+        B: Clone,
+    {
+        self.outside_window(g)?.clip(g)
+    }
+
+    // Window excluded from a rect enclosing both the window and geometry with some margin
+    fn outside_window(&self, g: &Geometry<T>) -> Option<ExclusionClipRect<T, B>>
+    where
+        B: Clone,
+    {
+        let (min, max) = util::bounding_box(g)?;
+
+        let r = self.rect();
+        let (x0, y0) = (min.x.min(r.x0), min.y.min(r.y0));
+        let (x1, y1) = (max.x.max(r.x1), max.y.max(r.y1));
+        let pad = (x1 - x0) + (y1 - y0) + T::one();
+
+        let outer = self.with_bounds(x0 - pad, y0 - pad, x1 + pad, y1 + pad);
+        let window = self.with_bounds(r.x0, r.y0, r.x1, r.y1);
+
+        Some(outer.with_exclusion(window))
+    }
+
+    fn split_geometry(
+        &self,
+        g: &Geometry<T>,
+        excl: &ExclusionClipRect<T, B>,
+    ) -> Result<Halves<T>, ClipError> {
+        use Geometry::*;
+
+        let r = &self.inner;
+        let halves = match g {
+            Point(p) => (
+                r.clip_point(p).map(Point),
+                (!r.coord_inside(&p.0)).then_some(Point(*p)),
+            ),
+            MultiPoint(mp) => {
+                let (inside, outside): (Vec<_>, Vec<_>) = (
+                    mp.iter().filter_map(|p| r.clip_point(p)).collect(),
+                    mp.iter()
+                        .filter(|p| !r.coord_inside(&p.0))
+                        .copied()
+                        .collect(),
+                );
+                (
+                    (!inside.is_empty()).then_some(MultiPoint(geo_types::MultiPoint(inside))),
+                    (!outside.is_empty()).then_some(MultiPoint(geo_types::MultiPoint(outside))),
+                )
+            }
+            Line(l) => {
+                let (inside, outside) =
+                    self.split_lines(&[geo_types::LineString::new(vec![l.start, l.end])]);
+                let inside = inside
+                    .first()
+                    .map(|ls| Line(geo_types::Line::new(ls[0], ls[1])));
+                (inside, lines(outside))
+            }
+            LineString(ls) => {
+                let (inside, outside) = self.split_lines(core::slice::from_ref(ls));
+                (lines(inside), lines(outside))
+            }
+            MultiLineString(mls) => {
+                let (inside, outside) = self.split_lines(&mls.0);
+                let multi = |pieces: Vec<_>| {
+                    (!pieces.is_empty())
+                        .then_some(MultiLineString(geo_types::MultiLineString(pieces)))
+                };
+                (multi(inside), multi(outside))
+            }
+            Polygon(poly) if self.degenerate_as_lines && util::is_collinear(&poly.exterior().0) => {
+                return self.split_geometry(&LineString(poly.exterior().clone()), excl);
+            }
+            Polygon(poly) => {
+                let (inside, outside) = self.split_polygon(poly, excl)?;
+                (polygons(inside), polygons(outside))
+            }
+            MultiPolygon(mp) => {
+                let (mut inside, mut outside) = (vec![], vec![]);
+                for poly in mp {
+                    let (a, b) = self.split_polygon(poly, excl)?;
+                    inside.extend(a);
+                    outside.extend(b);
+                }
+                let multi = |polys: Vec<_>| {
+                    (!polys.is_empty()).then_some(MultiPolygon(geo_types::MultiPolygon(polys)))
+                };
+                (multi(inside), multi(outside))
+            }
+            Rect(g) => return self.split_geometry(&Polygon(g.to_polygon()), excl),
+            Triangle(g) => return self.split_geometry(&Polygon(g.to_polygon()), excl),
+            GeometryCollection(gc) => {
+                let (mut inside, mut outside) = (vec![], vec![]);
+                for g in gc {
+                    let (a, b) = self.split_geometry(g, excl)?;
+                    inside.extend(a);
+                    outside.extend(b);
+                }
+                let collection = |members: Vec<_>| {
+                    (!members.is_empty())
+                        .then_some(GeometryCollection(geo_types::GeometryCollection(members)))
+                };
+                (collection(inside), collection(outside))
+            }
+        };
+
+        Ok(halves)
+    }
+
+    // Pieces of the linestrings (inside, outside) of the window. The parts of segments outside
+    // are joined into pieces where they connect, as in `ExclusionClipRect::clip`.
+    fn split_lines(&self, lines: &[LineString<T>]) -> (Vec<LineString<T>>, Vec<LineString<T>>) {
+        let (mut inside, mut outside) = (vec![], vec![]);
+
+        for ls in lines {
+            let segments: Vec<Line<T>> = util::dedup_coords(ls).lines().collect();
+
+            let mut groups = vec![];
+            let mut pieces: Vec<LineString<T>> = vec![];
+            self.inner
+                .clip_segments_split(&segments, &self.backend, &mut groups, |part| {
+                    match pieces.last_mut() {
+                        Some(ls) if ls.0.last() == Some(&part.start) => ls.0.push(part.end),
+                        _ => pieces.push(LineString::new(vec![part.start, part.end])),
+                    }
+                });
+
+            inside.extend(groups.into_iter().map(util::segments_to_linestring));
+            outside.extend(pieces);
+        }
+
+        (inside, outside)
+    }
+
+    // Parts of polygon (inside, outside) of the window, each ring split once for both
+    fn split_polygon(
+        &self,
+        poly: &Polygon<T>,
+        excl: &ExclusionClipRect<T, B>,
+    ) -> Result<PolygonHalves<T>, ClipError> {
+        let rings: Vec<SplitRing<T>> = [poly.exterior()]
+            .into_iter()
+            .chain(poly.interiors())
+            .map(|ls| self.split_ring(ls, excl))
+            .collect();
+
+        // rings are looked up as given to the ring clippers, falling back to clipping them anew
+        let find = |ls: &LineString<T>| rings.iter().find(|r| r.ring == *ls);
+        let stats = StatsSink::default();
+
+        let inside = self.try_clip_polygon_with(
+            poly,
+            &|ls| match find(ls) {
+                Some(r) => r.inside.clone(),
+                None => self.clip_polygon_ring_ccw(ls, &stats),
+            },
+            &stats,
+        )?;
+
+        let entered: Vec<bool> = rings.iter().map(|r| r.cut).collect();
+        let outside = excl.polygon_outside(
+            poly,
+            &|ls| match find(ls) {
+                Some(r) => r.outside.clone(),
+                None => excl.clip_ring_ccw(ls),
+            },
+            &entered,
+        )?;
+
+        Ok((inside.0, outside))
+    }
+
+    // Splits polygon ring wound counter-clockwise for sewing, as `clip_polygon_ring_with` gives
+    // it to the ring clippers. Rings the boundary does not cut are outside as a whole.
+    fn split_ring(&self, ls: &LineString<T>, excl: &ExclusionClipRect<T, B>) -> SplitRing<T> {
+        let ls = util::dedup_coords(ls).into_owned();
+        let ring = if util::signed_area(&ls.0) < T::zero() {
+            ls.reverse()
+        } else {
+            ls
+        };
+
+        let input_lines: Vec<Line<T>> = ring.lines().collect();
+        let mut parts = vec![];
+        let groups = self
+            .inner
+            .clip_segments_tagged_split(&input_lines, &self.backend, |l| parts.push(l));
+
+        let cut = parts != input_lines;
+        let inside = self.sew_groups(groups, &input_lines, &StatsSink::default(), |_, _, _| {});
+        let outside = if cut {
+            excl.sew_outside(parts)
+        } else {
+            vec![ring.clone()]
+        };
+
+        SplitRing {
+            ring,
+            inside,
+            outside,
+            cut,
+        }
+    }
+}
+
+fn lines<T: CoordFloat>(mut pieces: Vec<LineString<T>>) -> Option<Geometry<T>> {
+    match pieces.len() {
+        0 => None,
+        1 => pieces.pop().map(Geometry::LineString),
+        _ => Some(Geometry::MultiLineString(MultiLineString::new(pieces))),
+    }
+}
+
+fn polygons<T: CoordFloat>(mut polys: Vec<Polygon<T>>) -> Option<Geometry<T>> {
+    match polys.len() {
+        0 => None,
+        1 => polys.pop().map(Geometry::Polygon),
+        _ => Some(Geometry::MultiPolygon(MultiPolygon::new(polys))),
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{util, ClipRect, IntersectionBackend, MaybeSync};
+use core::cell::Cell;
+use geo_types::{CoordFloat, Geometry};
+
+// Counts of events while clipping, see `ClipRect::clip_detailed`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClipStats {
+    // polygons, linestrings or points in the output
+    pub parts: usize,
+    // rect corners added while sewing polygon rings along the rect boundary
+    pub corner_nodes_inserted: usize,
+    // points where lines and rings meet the rect boundary
+    pub intersections: usize,
+    // input polygon rings leaving nothing in the output, either clipped away or degenerate
+    pub rings_dropped: usize,
+}
+
+// Counts clip events as they happen. Plain clipping passes a sink that is off, which ignores
+// the counts. Signs of sewing gone wrong are noted either way.
+#[derive(Debug, Default)]
+pub(crate) struct StatsSink {
+    stats: Option<Cell<ClipStats>>,
+    // pieces left unsewn, ring crossing the rect boundary an odd number of times, or hole
+    // outside every output shell
+    suspect: Cell<bool>,
+}
+
+impl StatsSink {
+    pub(crate) fn new(on: bool) -> Self {
+        StatsSink {
+            stats: on.then(Cell::default),
+            suspect: Cell::new(false),
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    pub(crate) fn is_on(&self) -> bool {
+        self.stats.is_some()
+    }
+
+    pub(crate) fn add(&self, f: impl FnOnce(&mut ClipStats)) {
+        if let Some(cell) = &self.stats {
+            let mut stats = cell.get();
+            f(&mut stats);
+            cell.set(stats);
+        }
+    }
+
+    pub(crate) fn suspect(&self) {
+        debug!("clip output suspect");
+        self.suspect.set(true);
+    }
+
+    #[cfg(feature = "boolean")]
+    pub(crate) fn is_suspect(&self) -> bool {
+        self.suspect.get()
+    }
+
+    // Adds counts gathered apart, e.g. on another thread
+    pub(crate) fn merge(&self, other: StatsSink) {
+        if other.suspect.get() {
+            self.suspect.set(true);
+        }
+
+        let other = other.get();
+        self.add(|s| {
+            s.parts += other.parts;
+            s.corner_nodes_inserted += other.corner_nodes_inserted;
+            s.intersections += other.intersections;
+            s.rings_dropped += other.rings_dropped;
+        });
+    }
+
+    pub(crate) fn get(&self) -> ClipStats {
+        self.stats.as_ref().map(Cell::get).unwrap_or_default()
+    }
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips geometry, returning the output with counts of what happened while clipping, for
+    // debugging odd outputs. The counts are gathered during the clip.
+    pub fn clip_detailed(&self, g: &Geometry<T>) -> (Option<Geometry<T>>, ClipStats) {
+        let sink = StatsSink::new(true);
+        let clipped = self.try_clip_counted(g, &sink, self.strict).unwrap_or(None);
+
+        let stats = ClipStats {
+            parts: clipped.as_ref().map_or(0, count_parts),
+            ..sink.get()
+        };
+
+        (clipped, stats)
+    }
+
+    // Polygon rings of geometry, which all drop when it is clipped away as a whole
+    pub(crate) fn count_rings(&self, g: &Geometry<T>) -> usize {
+        use Geometry::*;
+
+        let rings = |poly: &geo_types::Polygon<T>| {
+            if self.degenerate_as_lines && util::is_collinear(&poly.exterior().0) {
+                0
+            } else {
+                1 + poly.interiors().len()
+            }
+        };
+
+        match g {
+            Polygon(poly) => rings(poly),
+            MultiPolygon(mp) => mp.iter().map(rings).sum(),
+            GeometryCollection(gc) => gc.iter().map(|g| self.count_rings(g)).sum(),
+            Rect(_) | Triangle(_) => 1,
+            Point(_) | Line(_) | LineString(_) | MultiPoint(_) | MultiLineString(_) => 0,
+        }
+    }
+}
+
+fn count_parts<T: CoordFloat>(g: &Geometry<T>) -> usize {
+    match g {
+        Geometry::MultiPoint(mp) => mp.0.len(),
+        Geometry::MultiLineString(mls) => mls.0.len(),
+        Geometry::MultiPolygon(mp) => mp.0.len(),
+        Geometry::GeometryCollection(gc) => gc.iter().map(count_parts).sum(),
+        _ => 1,
+    }
+}
@@ -0,0 +1,114 @@
+use crate::geom::Reverse;
+use crate::{util, ClipRect, MaybeSync};
+use alloc::vec;
+use alloc::vec::Vec;
+use geo_types::{Coord, CoordFloat, Line, LineString};
+
+// Clips a polygon ring from coordinates fed one at a time.
+// Only the clipped pieces inside the rect are retained, not the whole input ring.
+pub struct StreamingRingClipper<'a, T: CoordFloat> {
+    clip: &'a ClipRect<T>,
+
+    // previous coordinate with its region code
+    last: Option<(Coord<T>, u8)>,
+
+    // continuous groups of clipped segments
+    groups: Vec<Vec<Line<T>>>,
+
+    // rect center ray crossings, for checking if the ring covers the rect
+    crossings: usize,
+
+    // first coordinate and twice the signed area so far, for the ring winding
+    first: Option<Coord<T>>,
+    area: T,
+}
+
+impl<'a, T: CoordFloat + MaybeSync> StreamingRingClipper<'a, T> {
+    pub fn new(clip: &'a ClipRect<T>) -> Self {
+        Self {
+            clip,
+            last: None,
+            groups: vec![],
+            crossings: 0,
+            first: None,
+            area: T::zero(),
+        }
+    }
+
+    pub fn push_coord(&mut self, c: Coord<T>) {
+        // skip consecutive duplicates
+        if let Some((prev, _)) = self.last {
+            if prev == c {
+                return;
+            }
+        }
+
+        let rect = &self.clip.inner;
+        let code = rect.outcode(&c);
+        self.first.get_or_insert(c);
+
+        if let Some((prev, prev_code)) = self.last.replace((c, code)) {
+            let seg = Line::new(prev, c);
+
+            self.area = self.area + prev.x * c.y - c.x * prev.y;
+
+            self.crossings += rect.center_ray_crosses(&seg) as usize;
+
+            if prev_code & code != 0 {
+                return;
+            }
+
+            if let Some(seg) = rect.clip_segment(&seg) {
+                match self.groups.last_mut() {
+                    Some(group) if group.last().map(|l| l.end) == Some(seg.start) => {
+                        group.push(seg)
+                    }
+                    _ => self.groups.push(vec![seg]),
+                }
+            }
+        }
+    }
+
+    // Sews the clipped pieces into output rings, wound as the input ring.
+    pub fn finish(mut self) -> Vec<LineString<T>> {
+        // The ring is closed, so the first group continues the last one when they connect.
+        // Move it to the end to match the grouping of `Rect::clip_segments`.
+        if !self.groups.is_empty() {
+            let first = self.groups.remove(0);
+            match self.groups.last_mut() {
+                Some(last) if last.last().map(|l| l.end) == first.first().map(|l| l.start) => {
+                    last.extend(first)
+                }
+                _ => self.groups.push(first),
+            }
+        }
+
+        // Sewing works on counter-clockwise rings, so clockwise rings are sewn reversed and
+        // reversed back, as in `ClipRect::clip_polygon`
+        if let (Some(first), Some((last, _))) = (self.first, self.last) {
+            self.area = self.area + last.x * first.y - first.x * last.y;
+        }
+        let cw = self.area < T::zero();
+        if cw {
+            self.groups.reverse();
+            for group in &mut self.groups {
+                group.reverse();
+                group.iter_mut().for_each(|l| *l = l.reverse());
+            }
+        }
+
+        let pieces = self
+            .groups
+            .into_iter()
+            .map(util::segments_to_linestring)
+            .collect();
+
+        let crossings = self.crossings;
+        self.clip
+            .sew_ring(pieces, || crossings % 2 == 1)
+            .into_iter()
+            .map(|ls| if cw { ls.reverse() } else { ls })
+            .map(util::close_exact)
+            .collect()
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{util, ClipError, ClipRect, IntersectionBackend, MaybeSync, Winding};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+use geo_types::{Coord, CoordFloat, Geometry, LineString, Polygon};
+
+// Flat clip output in tile space, ready for building vertex buffers.
+// Coordinates are scaled to 0..extent with y axis pointing down, as in vector tiles.
+// Polygon rings are open: the closing coordinate is left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TileGeometry {
+    pub coords: Vec<[f32; 2]>,
+    // Polygon rings in coords, with the role of each
+    pub rings: Vec<Range<usize>>,
+    pub windings: Vec<Winding>,
+    // Line strings in coords
+    pub lines: Vec<Range<usize>>,
+    pub points: Vec<[f32; 2]>,
+}
+
+// Mapping of clip rect coordinates to tile space
+trait Scale: Fn(&Coord<f64>) -> [f32; 2] {}
+impl<F: Fn(&Coord<f64>) -> [f32; 2]> Scale for F {}
+
+impl TileGeometry {
+    fn push_coords(&mut self, coords: impl IntoIterator<Item = [f32; 2]>) -> Range<usize> {
+        let start = self.coords.len();
+        self.coords.extend(coords);
+        start..self.coords.len()
+    }
+
+    fn push_ring(&mut self, ls: LineString<f64>, winding: Winding, scale: &impl Scale) {
+        let range = self.push_coords(util::open_ring(ls).iter().map(scale));
+        self.rings.push(range);
+        self.windings.push(winding);
+    }
+
+    fn push_line(&mut self, coords: impl IntoIterator<Item = [f32; 2]>) {
+        let range = self.push_coords(coords);
+        self.lines.push(range);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rings.is_empty() && self.lines.is_empty() && self.points.is_empty()
+    }
+
+    fn push_polygon(&mut self, poly: Polygon<f64>, scale: &impl Scale) {
+        let (exterior, interiors) = poly.into_inner();
+        self.push_ring(exterior, Winding::Outer, scale);
+        for ls in interiors {
+            self.push_ring(ls, Winding::Hole, scale);
+        }
+    }
+
+    fn push_geometry(&mut self, g: Geometry<f64>, scale: &impl Scale) {
+        match g {
+            Geometry::Point(p) => self.points.push(scale(&p.0)),
+            Geometry::MultiPoint(mp) => self.points.extend(mp.iter().map(|p| scale(&p.0))),
+            Geometry::Line(l) => self.push_line([scale(&l.start), scale(&l.end)]),
+            Geometry::LineString(ls) => self.push_line(ls.coords().map(scale)),
+            Geometry::MultiLineString(mls) => {
+                for ls in mls.iter() {
+                    self.push_line(ls.coords().map(scale));
+                }
+            }
+            Geometry::Polygon(poly) => self.push_polygon(poly, scale),
+            Geometry::MultiPolygon(mp) => {
+                for poly in mp {
+                    self.push_polygon(poly, scale);
+                }
+            }
+            Geometry::GeometryCollection(gc) => {
+                for g in gc {
+                    self.push_geometry(g, scale);
+                }
+            }
+            // clip output has no rects or triangles
+            Geometry::Rect(_) | Geometry::Triangle(_) => {}
+        }
+    }
+}
+
+impl ClipRect<f64> {
+    // Clips geometry into flat tile geometry scaled to extent.
+    // Window with zero width or height has no tile space and is rejected.
+    pub fn clip_to_tile_geometry(
+        &self,
+        g: &Geometry<f64>,
+        extent: u32,
+    ) -> Result<Option<TileGeometry>, ClipError> {
+        let r = &self.inner;
+        if r.x0 == r.x1 || r.y0 == r.y1 {
+            return Err(ClipError::DegenerateRect);
+        }
+
+        let (sx, sy) = (extent as f64 / (r.x1 - r.x0), extent as f64 / (r.y1 - r.y0));
+        let scale = |c: &Coord<f64>| [((c.x - r.x0) * sx) as f32, ((r.y1 - c.y) * sy) as f32];
+
+        let mut tile = TileGeometry::default();
+        if let Some(clipped) = self.clip(g) {
+            tile.push_geometry(clipped, &scale);
+        }
+
+        Ok((!tile.is_empty()).then_some(tile))
+    }
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips polygon straight into an interleaved [x0, y0, x1, y1, ..] vertex buffer with the
+    // vertex count of each ring, scaled to extent as in `TileGeometry`. Rings are written as
+    // they are sewn, exteriors first, and are open.
+    pub fn clip_to_f32_buffer(
+        &self,
+        g: &Polygon<T>,
+        extent: f32,
+    ) -> Option<(Vec<f32>, Vec<usize>)> {
+        let r = &self.inner;
+        let extent = T::from(extent).unwrap();
+        let (sx, sy) = (extent / (r.x1 - r.x0), extent / (r.y1 - r.y0));
+
+        let mut buffer = vec![];
+        let mut counts = vec![];
+
+        self.clip_polygon_for_each(g, |ls, _| {
+            let ring = &ls.0[..ls.0.len() - 1];
+            for c in ring {
+                buffer.push(((c.x - r.x0) * sx).to_f32().unwrap());
+                buffer.push(((r.y1 - c.y) * sy).to_f32().unwrap());
+            }
+            counts.push(ring.len());
+        });
+
+        (!counts.is_empty()).then_some((buffer, counts))
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{ClipRect, MaybeSync};
+use geo_types::CoordFloat;
+
+// Half of the Web Mercator (EPSG:3857) world extent in meters
+const MERCATOR_EXTENT: f64 = 20037508.342789244;
+
+const GEOHASH_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+impl<T: CoordFloat + MaybeSync> ClipRect<T> {
+    // Web Mercator bounds of XYZ tile, with y growing southwards from the top of the map.
+    pub fn from_tile(z: u8, x: u32, y: u32) -> Self {
+        let size = 2.0 * MERCATOR_EXTENT / 2f64.powi(z as i32);
+
+        let x0 = -MERCATOR_EXTENT + x as f64 * size;
+        let y1 = MERCATOR_EXTENT - y as f64 * size;
+
+        Self::new(
+            T::from(x0).unwrap(),
+            T::from(y1 - size).unwrap(),
+            T::from(x0 + size).unwrap(),
+            T::from(y1).unwrap(),
+        )
+    }
+
+    // Lon/lat bounds of geohash cell.
+    // Returns None if the hash contains characters outside the geohash alphabet.
+    pub fn from_geohash(hash: &str) -> Option<Self> {
+        let (mut lon0, mut lon1) = (-180.0, 180.0);
+        let (mut lat0, mut lat1) = (-90.0, 90.0);
+
+        // bits alternate between longitude and latitude, starting from longitude
+        let mut is_lon = true;
+
+        for c in hash.bytes() {
+            let idx = GEOHASH_ALPHABET
+                .iter()
+                .position(|&a| a == c.to_ascii_lowercase())?;
+
+            for bit in (0..5).rev() {
+                let (lo, hi) = if is_lon {
+                    (&mut lon0, &mut lon1)
+                } else {
+                    (&mut lat0, &mut lat1)
+                };
+
+                let mid = (*lo + *hi) / 2.0;
+                if idx & (1 << bit) != 0 {
+                    *lo = mid;
+                } else {
+                    *hi = mid;
+                }
+
+                is_lon = !is_lon;
+            }
+        }
+
+        Some(Self::new(
+            T::from(lon0)?,
+            T::from(lat0)?,
+            T::from(lon1)?,
+            T::from(lat1)?,
+        ))
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{ClipRect, IntersectionBackend, MaybeSync};
+use alloc::vec;
+use alloc::vec::Vec;
+use geo_types::{Coord, CoordFloat, Line, LineString};
+
+// Sewing produces polygon parts touching each other at a single point on the rect boundary as
+// one ring pinched at the point. These are split into separate rings unless `join_touching` is
+// set.
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Splits closed ring where one of its vertices lies inside a boundary segment of the ring
+    pub(crate) fn split_pinched(&self, ls: LineString<T>) -> Vec<LineString<T>> {
+        let c = &ls.0[..ls.0.len().saturating_sub(1)];
+        let n = c.len();
+
+        let seg = |j: usize| Line::new(c[j], c[(j + 1) % n]);
+        let edge_segments: Vec<usize> = (0..n)
+            .filter(|j| self.boundary_edge(&seg(*j)).is_some())
+            .collect();
+
+        if edge_segments.is_empty() {
+            return vec![ls];
+        }
+
+        for k in 0..n {
+            for &j in &edge_segments {
+                // skip segments ending or starting at the vertex itself
+                if j == k || (j + 1) % n == k || !self.splits_boundary_segment(&c[k], &seg(j)) {
+                    continue;
+                }
+
+                // ring from the vertex to the segment, and from the segment back to the vertex
+                let a: Vec<Coord<T>> = (k..=k + (j + n - k) % n)
+                    .map(|i| c[i % n])
+                    .chain([c[k]])
+                    .collect();
+                let b: Vec<Coord<T>> = [c[k]]
+                    .into_iter()
+                    .chain((j + 1..=j + 1 + (k + n - j - 1) % n).map(|i| c[i % n]))
+                    .collect();
+
+                return [a, b]
+                    .into_iter()
+                    .flat_map(|ring| self.split_pinched(ring.into()))
+                    .collect();
+            }
+        }
+
+        vec![ls]
+    }
+
+    // Vertex lies strictly inside a segment running along the rect boundary
+    fn splits_boundary_segment(&self, p: &Coord<T>, seg: &Line<T>) -> bool {
+        let near = |a: T, b: T| self.rect().near(a, b);
+        let between = |v: T, a: T, b: T| v > a.min(b) && v < a.max(b) && !near(v, a) && !near(v, b);
+
+        match self.boundary_edge(seg) {
+            Some((edge, _)) if edge % 2 == 0 => {
+                near(p.y, seg.start.y) && between(p.x, seg.start.x, seg.end.x)
+            }
+            Some(_) => near(p.x, seg.start.x) && between(p.y, seg.start.y, seg.end.y),
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::stats::StatsSink;
+use crate::{ClipRect, IntersectionBackend, MaybeSync};
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use geo_types::{CoordFloat, LineString, Polygon};
+
+// Sewing queue: pieces keyed by the perimeter index bits of their starting point and the bits
+// of their order at a rect corner, with a sequence number for pieces starting at the same point
+// in the same direction.
+pub(crate) type SewQueue<T> = BTreeMap<(u64, u64, usize), LineString<T>>;
+
+// Action taken on the piece with the smallest perimeter index in a sewing step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SewAction {
+    // closed piece is moved to output
+    Push,
+    // next piece along the perimeter is appended to the piece
+    Join,
+    // piece is closed by walking the perimeter back to its start, and moved to output
+    Close,
+}
+
+// Sewing loop step: queue state with perimeter indices at the start of the step, and the
+// action taken. The first entry is the piece being acted on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SewStep<T: CoordFloat> {
+    pub queue: Vec<(f64, LineString<T>)>,
+    pub action: SewAction,
+}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips polygon, recording every step of sewing its rings, for debugging the sewing
+    // algorithm. Steps of all rings are listed in clipping order, exterior first.
+    pub fn clip_polygon_trace(&self, g: &Polygon<T>) -> Vec<SewStep<T>> {
+        let steps = RefCell::new(vec![]);
+
+        let record = |a: &LineString<T>, queue: &SewQueue<T>, action| {
+            // NaN for closed pieces off the perimeter
+            let head = self.rect().perimeter_index(&a.0[0]).unwrap_or(f64::NAN);
+            let state = [(head, a.clone())]
+                .into_iter()
+                .chain(
+                    queue
+                        .iter()
+                        .map(|((p, _, _), ls)| (f64::from_bits(*p), ls.clone())),
+                )
+                .collect();
+
+            steps.borrow_mut().push(SewStep {
+                queue: state,
+                action,
+            });
+        };
+
+        let stats = StatsSink::default();
+        self.for_each_ring_with(
+            g,
+            &|ls| self.clip_polygon_ring_ccw_traced(ls, &stats, record),
+            &stats,
+            |_, _| {},
+        );
+
+        steps.into_inner()
+    }
+}
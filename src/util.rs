@@ -1,7 +1,10 @@
-use geo_types::{Coord, CoordFloat, Line, LineString};
-use log::debug;
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use geo_types::{Coord, CoordFloat, CoordNum, Geometry, Line, LineString, Polygon};
 
 use crate::rect::Rect;
+use crate::trace::SewQueue;
 
 #[inline]
 pub(crate) fn segments_to_linestring<T: CoordFloat>(mut segments: Vec<Line<T>>) -> LineString<T> {
@@ -17,6 +20,213 @@ pub(crate) fn segments_to_linestring<T: CoordFloat>(mut segments: Vec<Line<T>>)
     }
 }
 
+// Groups clipped ring segments into continuous runs.
+// First and last segments are assumed to be connected, so grouping starts from the first split.
+pub(crate) fn group_segments<T: CoordNum>(segments: Vec<Line<T>>) -> Vec<Vec<Line<T>>> {
+    // Early return on empty segments list
+    if segments.is_empty() {
+        return vec![];
+    }
+
+    // Find first splitpoint
+    // since we must assume first and last segments are connected.
+    let seg_len = segments.len();
+    let mut offset = 0;
+    for i in 0..segments.len() {
+        if segments[i].end != segments[(i + 1) % seg_len].start {
+            offset = (i + 1) % seg_len;
+            break;
+        }
+    }
+
+    // Group segments, starting from offset and looping around
+    let groups = segments
+        .into_iter()
+        .cycle()
+        .skip(offset)
+        .take(seg_len)
+        .fold(vec![], |mut acc: Vec<Vec<Line<T>>>, seg: Line<T>| {
+            if let Some(segs) = acc.last_mut() {
+                if let Some(last) = segs.last() {
+                    if last.end == seg.start {
+                        // Continue segment group
+                        segs.push(seg);
+                    } else {
+                        // Start another group
+                        acc.push(vec![seg]);
+                    }
+                }
+            } else {
+                acc.push(vec![seg]);
+            }
+
+            acc
+        });
+
+    groups
+}
+
+// Calls f for every coordinate of the geometry
+pub(crate) fn for_each_coord<T: CoordFloat>(g: &Geometry<T>, f: &mut impl FnMut(&Coord<T>)) {
+    let polygon = |poly: &Polygon<T>, f: &mut dyn FnMut(&Coord<T>)| {
+        poly.exterior().0.iter().for_each(&mut *f);
+        poly.interiors()
+            .iter()
+            .for_each(|ls| ls.0.iter().for_each(&mut *f));
+    };
+
+    match g {
+        Geometry::Point(p) => f(&p.0),
+        Geometry::Line(l) => {
+            f(&l.start);
+            f(&l.end);
+        }
+        Geometry::LineString(ls) => ls.0.iter().for_each(f),
+        Geometry::Polygon(poly) => polygon(poly, f),
+        Geometry::MultiPoint(mp) => mp.iter().for_each(|p| f(&p.0)),
+        Geometry::MultiLineString(mls) => mls.iter().for_each(|ls| ls.0.iter().for_each(&mut *f)),
+        Geometry::MultiPolygon(mp) => mp.iter().for_each(|poly| polygon(poly, f)),
+        Geometry::GeometryCollection(gc) => gc.iter().for_each(|g| for_each_coord(g, f)),
+        Geometry::Rect(r) => {
+            f(&r.min());
+            f(&r.max());
+        }
+        Geometry::Triangle(t) => t.to_array().iter().for_each(f),
+    }
+}
+
+// Mutable variant of `for_each_coord`
+pub(crate) fn for_each_coord_mut<T: CoordFloat>(
+    g: &mut Geometry<T>,
+    f: &mut impl FnMut(&mut Coord<T>),
+) {
+    let polygon = |poly: &mut Polygon<T>, f: &mut dyn FnMut(&mut Coord<T>)| {
+        poly.exterior_mut(|ls| ls.0.iter_mut().for_each(&mut *f));
+        poly.interiors_mut(|rings| {
+            rings
+                .iter_mut()
+                .for_each(|ls| ls.0.iter_mut().for_each(&mut *f))
+        });
+    };
+
+    match g {
+        Geometry::Point(p) => f(&mut p.0),
+        Geometry::Line(l) => {
+            f(&mut l.start);
+            f(&mut l.end);
+        }
+        Geometry::LineString(ls) => ls.0.iter_mut().for_each(f),
+        Geometry::Polygon(poly) => polygon(poly, f),
+        Geometry::MultiPoint(mp) => mp.iter_mut().for_each(|p| f(&mut p.0)),
+        Geometry::MultiLineString(mls) => mls
+            .iter_mut()
+            .for_each(|ls| ls.0.iter_mut().for_each(&mut *f)),
+        Geometry::MultiPolygon(mp) => mp.iter_mut().for_each(|poly| polygon(poly, f)),
+        Geometry::GeometryCollection(gc) => gc.iter_mut().for_each(|g| for_each_coord_mut(g, f)),
+        Geometry::Rect(r) => {
+            let (mut min, mut max) = (r.min(), r.max());
+            f(&mut min);
+            f(&mut max);
+            *r = geo_types::Rect::new(min, max);
+        }
+        Geometry::Triangle(t) => {
+            f(&mut t.0);
+            f(&mut t.1);
+            f(&mut t.2);
+        }
+    }
+}
+
+// Bounding box of geometry as (min, max) coordinates
+pub(crate) fn bounding_box<T: CoordFloat>(g: &Geometry<T>) -> Option<(Coord<T>, Coord<T>)> {
+    let mut bbox: Option<(Coord<T>, Coord<T>)> = None;
+
+    for_each_coord(g, &mut |c| {
+        bbox = Some(match bbox {
+            Some((min, max)) => (
+                (min.x.min(c.x), min.y.min(c.y)).into(),
+                (max.x.max(c.x), max.y.max(c.y)).into(),
+            ),
+            None => (*c, *c),
+        })
+    });
+
+    bbox
+}
+
+// All coordinates lie on a single line, leaving a ring without area
+pub(crate) fn is_collinear<T: CoordFloat>(coords: &[Coord<T>]) -> bool {
+    match coords.iter().find(|c| **c != coords[0]) {
+        Some(c) => {
+            let l = Line::new(coords[0], *c);
+            coords.iter().all(|c| orientation(&l, c) == T::zero())
+        }
+        None => true,
+    }
+}
+
+// Makes the closing coordinate of a closed ring a bitwise copy of the opening one.
+// Closed rings compare equal with floats, which allows e.g. 0.0 and -0.0 as ends.
+pub(crate) fn close_exact<T: CoordFloat>(mut ls: LineString<T>) -> LineString<T> {
+    if ls.is_closed() {
+        if let (Some(first), Some(last)) = (ls.0.first().copied(), ls.0.last_mut()) {
+            *last = first;
+        }
+    }
+    ls
+}
+
+// Closing coordinate of the ring is a bitwise copy of the opening one, see `close_exact`
+pub(crate) fn closes_exact<T: CoordFloat>(ls: &LineString<T>) -> bool {
+    let bits = |c: &Coord<T>| (c.x.integer_decode(), c.y.integer_decode());
+    match (ls.0.first(), ls.0.last()) {
+        (Some(first), Some(last)) => bits(first) == bits(last),
+        _ => false,
+    }
+}
+
+// Ring is closed and has at least three distinct points
+#[inline]
+pub(crate) fn is_ring<T: CoordFloat>(ls: &LineString<T>) -> bool {
+    ls.0.len() >= 4 && ls.is_closed()
+}
+
+// Removes consecutive equal coordinates, which would produce zero-length segments. Nearby
+// coordinates are kept, as they may be real detail. Borrows the input when there is nothing
+// to remove.
+pub(crate) fn dedup_coords<T: CoordFloat>(ls: &LineString<T>) -> Cow<'_, LineString<T>> {
+    let is_dup = |a: &Coord<T>, b: &Coord<T>| a == b;
+
+    if !ls.0.windows(2).any(|w| is_dup(&w[0], &w[1])) {
+        return Cow::Borrowed(ls);
+    }
+
+    let mut coords: Vec<Coord<T>> = Vec::with_capacity(ls.0.len());
+    for c in &ls.0 {
+        match coords.last() {
+            Some(last) if is_dup(last, c) => {}
+            _ => coords.push(*c),
+        }
+    }
+
+    // keep the ring closed when the closing coordinate was removed as duplicate
+    if ls.is_closed() && coords.len() > 1 {
+        *coords.last_mut().unwrap() = coords[0];
+    }
+
+    Cow::Owned(LineString::new(coords))
+}
+
+// Returns ring coordinates without the closing coordinate.
+#[inline]
+pub(crate) fn open_ring<T: CoordFloat>(ls: LineString<T>) -> Vec<Coord<T>> {
+    let mut coords = ls.0;
+    if coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    coords
+}
+
 #[inline]
 pub(crate) fn find_coord_inside<'a, T: CoordFloat>(
     ls: &'a LineString<T>,
@@ -25,13 +235,100 @@ pub(crate) fn find_coord_inside<'a, T: CoordFloat>(
     ls.0.iter().find(|c| rect.coord_inside(c))
 }
 
-pub(crate) fn print_queue<T: CoordFloat>(queue: &Vec<(f64, LineString<T>)>) {
-    for (p_idx, ls) in queue.into_iter().rev() {
-        debug!("p_idx={p_idx}, {ls:?}");
+pub(crate) fn print_queue<T: CoordFloat>(queue: &SewQueue<T>) {
+    for ((p_idx, _, _), ls) in queue {
+        debug!("p_idx={}, {ls:?}", f64::from_bits(*p_idx));
+    }
+}
+
+// Shoelace formula. Positive for counter-clockwise rings.
+// Coordinates are taken relative to the first one, so that rings far off the origin keep their
+// area instead of losing it to the rounding of large products.
+pub(crate) fn signed_area<T: CoordFloat>(coords: &[Coord<T>]) -> T {
+    let two = T::one() + T::one();
+    let Some(&o) = coords.first() else {
+        return T::zero();
+    };
+
+    coords.windows(2).fold(T::zero(), |acc, w| {
+        let (a, b) = (w[0] - o, w[1] - o);
+        acc + (a.x * b.y - b.x * a.y)
+    }) / two
+}
+
+// Cross product of line direction and the vector from line start to coordinate.
+// Positive when the coordinate is left of the line.
+#[inline]
+pub(crate) fn orientation<T: CoordFloat>(l: &Line<T>, c: &Coord<T>) -> T {
+    (l.end.x - l.start.x) * (c.y - l.start.y) - (l.end.y - l.start.y) * (c.x - l.start.x)
+}
+
+// Checks if hole lies within the shell ring, allowing it to touch the shell
+pub(crate) fn hole_in_shell<T: CoordFloat>(hole: &LineString<T>, shell: &LineString<T>) -> bool {
+    !rings_cross(hole, shell, false)
+        && hole
+            .0
+            .iter()
+            .all(|c| point_in_ring(c, shell) != Some(false))
+}
+
+// Checks for proper crossings between edges of two rings.
+// Touching at vertices is allowed.
+pub(crate) fn rings_cross<T: CoordFloat>(a: &LineString<T>, b: &LineString<T>, same: bool) -> bool {
+    let lines_a: Vec<Line<T>> = a.lines().collect();
+    let lines_b: Vec<Line<T>> = b.lines().collect();
+
+    lines_a.iter().enumerate().any(|(i, la)| {
+        lines_b
+            .iter()
+            .enumerate()
+            .skip(if same { i + 1 } else { 0 })
+            .any(|(_, lb)| segments_cross(la, lb))
+    })
+}
+
+fn segments_cross<T: CoordFloat>(a: &Line<T>, b: &Line<T>) -> bool {
+    let o1 = orientation(a, &b.start);
+    let o2 = orientation(a, &b.end);
+    let o3 = orientation(b, &a.start);
+    let o4 = orientation(b, &a.end);
+
+    o1 * o2 < T::zero() && o3 * o4 < T::zero()
+}
+
+#[inline]
+pub(crate) fn point_on_segment<T: CoordFloat>(c: &Coord<T>, l: &Line<T>) -> bool {
+    orientation(l, c) == T::zero()
+        && c.x >= l.start.x.min(l.end.x)
+        && c.x <= l.start.x.max(l.end.x)
+        && c.y >= l.start.y.min(l.end.y)
+        && c.y <= l.start.y.max(l.end.y)
+}
+
+// Crossing number test. Returns None for points on the ring boundary.
+pub(crate) fn point_in_ring<T: CoordFloat>(c: &Coord<T>, ls: &LineString<T>) -> Option<bool> {
+    let mut inside = false;
+
+    for l in ls.lines() {
+        if point_on_segment(c, &l) {
+            return None;
+        }
+
+        if (l.start.y > c.y) != (l.end.y > c.y) {
+            let x = l.start.x + (c.y - l.start.y) / (l.end.y - l.start.y) * (l.end.x - l.start.x);
+            if c.x < x {
+                inside = !inside;
+            }
+        }
     }
+
+    Some(inside)
 }
 
+// Values are equal within 0.00001, or within 16 units of rounding of T at their magnitude when
+// that is wider, so that f32 values far from the origin still match.
 #[inline(always)]
 pub fn rough_eq<T: CoordFloat>(a: T, b: T) -> bool {
-    (a - b).abs() <= T::from(0.00001).unwrap()
+    let rounding = T::epsilon() * T::from(16).unwrap() * a.abs().max(b.abs());
+    (a - b).abs() <= rounding.max(T::from(0.00001).unwrap())
 }
@@ -0,0 +1,136 @@
+use crate::util::{point_in_ring, rings_cross, signed_area};
+use crate::{ClipRect, IntersectionBackend, MaybeSync};
+use alloc::vec::Vec;
+use core::fmt;
+use geo_types::{CoordFloat, Geometry, LineString, Polygon};
+
+// First violation of OGC simple feature rules found in clip output.
+// Rings are indexed with 0 being the exterior and 1.. the interiors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    TooFewPoints {
+        polygon: usize,
+        ring: usize,
+    },
+    UnclosedRing {
+        polygon: usize,
+        ring: usize,
+    },
+    SelfIntersection {
+        polygon: usize,
+        ring: usize,
+        other: usize,
+    },
+    WrongWinding {
+        polygon: usize,
+        ring: usize,
+    },
+    HoleOutsideShell {
+        polygon: usize,
+        ring: usize,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ValidationError::*;
+
+        match self {
+            TooFewPoints { polygon, ring } => {
+                write!(
+                    f,
+                    "polygon {polygon} ring {ring} has less than 4 coordinates"
+                )
+            }
+            UnclosedRing { polygon, ring } => {
+                write!(f, "polygon {polygon} ring {ring} is not closed")
+            }
+            SelfIntersection {
+                polygon,
+                ring,
+                other,
+            } => write!(f, "polygon {polygon} rings {ring} and {other} intersect"),
+            WrongWinding { polygon, ring } => {
+                write!(f, "polygon {polygon} ring {ring} has wrong winding")
+            }
+            HoleOutsideShell { polygon, ring } => {
+                write!(f, "polygon {polygon} hole {ring} is outside of the shell")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+impl<T: CoordFloat + MaybeSync, B: IntersectionBackend<T>> ClipRect<T, B> {
+    // Clips geometry and validates polygonal output against OGC simple feature rules:
+    // closed rings, no crossing edges, CCW exteriors with CW holes and holes inside shell.
+    pub fn clip_checked(&self, g: &Geometry<T>) -> Result<Option<Geometry<T>>, ValidationError> {
+        let clipped = self.clip(g);
+
+        match &clipped {
+            Some(Geometry::Polygon(poly)) => validate_polygon(0, poly)?,
+            Some(Geometry::MultiPolygon(mp)) => {
+                for (i, poly) in mp.iter().enumerate() {
+                    validate_polygon(i, poly)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(clipped)
+    }
+}
+
+pub fn validate_polygon<T: CoordFloat>(
+    polygon: usize,
+    poly: &Polygon<T>,
+) -> Result<(), ValidationError> {
+    use ValidationError::*;
+
+    let rings: Vec<&LineString<T>> = [poly.exterior()]
+        .into_iter()
+        .chain(poly.interiors())
+        .collect();
+
+    for (ring, ls) in rings.iter().enumerate() {
+        if ls.0.len() < 4 {
+            return Err(TooFewPoints { polygon, ring });
+        }
+        if !ls.is_closed() {
+            return Err(UnclosedRing { polygon, ring });
+        }
+    }
+
+    for ring in 0..rings.len() {
+        for other in ring..rings.len() {
+            if rings_cross(rings[ring], rings[other], ring == other) {
+                return Err(SelfIntersection {
+                    polygon,
+                    ring,
+                    other,
+                });
+            }
+        }
+    }
+
+    // exterior winds counter-clockwise, holes clockwise
+    for (ring, ls) in rings.iter().enumerate() {
+        if (ring == 0) != (signed_area(&ls.0) > T::zero()) {
+            return Err(WrongWinding { polygon, ring });
+        }
+    }
+
+    for ring in 1..rings.len() {
+        if rings[ring]
+            .0
+            .iter()
+            .any(|c| !point_in_ring(c, rings[0]).unwrap_or(true))
+        {
+            return Err(HoleOutsideShell { polygon, ring });
+        }
+    }
+
+    Ok(())
+}
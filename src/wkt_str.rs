@@ -0,0 +1,42 @@
+use crate::{util, ClipError, ClipRect, IntersectionBackend, MaybeSync};
+use geo_types::{CoordFloat, Geometry};
+use std::fmt::Display;
+use std::str::FromStr;
+use wkt::{ToWkt, TryFromWkt};
+
+impl<T: CoordFloat + MaybeSync + FromStr + Default + Display, B: IntersectionBackend<T>>
+    ClipRect<T, B>
+{
+    // Clips geometry given as WKT, returning the output rendered as WKT
+    pub fn clip_wkt(&self, wkt: &str) -> Result<Option<String>, ClipError> {
+        Ok(self.try_clip(&parse(wkt)?)?.map(|g| g.wkt_string()))
+    }
+
+    // Like `clip_wkt`, but geometry clipping leaves as it is, fully inside the rect interior and
+    // already normalized by winding order and vertex dedup, is returned as the input text so that
+    // coordinates keep their original digits.
+    pub fn clip_wkt_str(&self, wkt: &str) -> Result<Option<String>, ClipError> {
+        let g = parse(wkt)?;
+
+        let Some(clipped) = self.try_clip(&g)? else {
+            return Ok(None);
+        };
+
+        let r = self.rect();
+        let contained = self.precision.is_none()
+            && util::bounding_box(&g)
+                .is_some_and(|(min, max)| r.coord_inside(&min) && r.coord_inside(&max))
+            && self.passes_unchanged(&g)
+            && clipped == g;
+
+        if contained {
+            Ok(Some(wkt.trim().to_string()))
+        } else {
+            Ok(Some(clipped.wkt_string()))
+        }
+    }
+}
+
+fn parse<T: CoordFloat + FromStr + Default>(wkt: &str) -> Result<Geometry<T>, ClipError> {
+    Geometry::try_from_wkt_str(wkt).map_err(|e| ClipError::Parse(e.to_string()))
+}
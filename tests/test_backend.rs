@@ -0,0 +1,42 @@
+#![cfg(feature = "robust")]
+use geo::{wkt, Geometry, Line};
+use klippa::*;
+
+#[test]
+fn test_backend_near_corner() {
+    // segment passing just outside the top left corner of the window
+    let edge = Line::new((0.3, 0.7), (0.3, 0.1));
+    let seg = Line::new(
+        (-0.72155, -0.37908600000000003),
+        (0.3422513079999999, 0.74463099696),
+    );
+
+    // rounding tips the float backend into an intersection at the corner
+    assert_eq!(
+        FloatIntersection.intersection(&edge, &seg),
+        Some((0.3, 0.7).into())
+    );
+    assert_eq!(RobustIntersection.intersection(&edge, &seg), None);
+
+    let g = Geometry::LineString(vec![seg.start, seg.end].into());
+    let rect = ClipRect::new(0.3, 0.1, 1.0, 0.7);
+    // a lone corner touch is not a clipped part
+    assert_eq!(rect.clip(&g), None);
+    assert_eq!(rect.with_backend(RobustIntersection).clip(&g), None);
+}
+
+#[test]
+fn test_backend_agree() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let robust = ClipRect::new(0.0, 0.0, 4.0, 4.0).with_backend(RobustIntersection);
+
+    for g in [
+        Geometry::LineString(wkt! { LINESTRING(-1.0 1.0,2.0 1.0,2.0 3.0,5.0 3.0) }),
+        Geometry::Polygon(
+            wkt! { POLYGON((1. 1.,7. 1.,7. 3.,1. 3.,1. 1.),(2. 1.5,2. 2.5,4.5 2.5,4.5 1.5,2. 1.5)) },
+        ),
+        Geometry::Polygon(wkt! { POLYGON((-1. 1.,1. -1.,2. 0.,0. 2.,-1. 1.)) }),
+    ] {
+        assert_eq!(robust.clip(&g), rect.clip(&g));
+    }
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "boolean")]
+
+use geo::{wkt, Area, BoundingRect, Geometry};
+use klippa::*;
+
+#[test]
+fn test_clip_difference() {
+    let rect = ClipRect::new(1.0, 1.0, 5.0, 3.0);
+    let a = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)));
+    let b = wkt!(POLYGON((2. 2.,6. 2.,6. 6.,2. 6.,2. 2.)));
+
+    let diff = rect.clip_difference(&a, &b);
+
+    // a within window is 3x2, overlap with b within window is 2x1
+    assert_eq!(diff.0.len(), 1);
+    assert!((diff.unsigned_area() - 4.0_f64).abs() < 1e-9);
+
+    // b outside window
+    let b = wkt!(POLYGON((10. 10.,11. 10.,11. 11.,10. 11.,10. 10.)));
+    let diff = rect.clip_difference(&a, &b);
+    assert!((diff.unsigned_area() - 6.0_f64).abs() < 1e-9);
+}
+
+#[test]
+fn test_clip_and_dissolve() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // quadrants extending beyond the window
+    let polys = [
+        wkt!(POLYGON((-1. -1.,2. -1.,2. 2.,-1. 2.,-1. -1.))),
+        wkt!(POLYGON((2. -1.,5. -1.,5. 2.,2. 2.,2. -1.))),
+        wkt!(POLYGON((2. 2.,5. 2.,5. 5.,2. 5.,2. 2.))),
+        wkt!(POLYGON((-1. 2.,2. 2.,2. 5.,-1. 5.,-1. 2.))),
+    ];
+
+    let dissolved = rect.clip_and_dissolve(&polys);
+
+    assert_eq!(dissolved.0.len(), 1);
+    assert!(dissolved.0[0].interiors().is_empty());
+    assert!((dissolved.unsigned_area() - 16.0_f64).abs() < 1e-9);
+    assert_eq!(
+        dissolved.bounding_rect().unwrap(),
+        wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)))
+            .bounding_rect()
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_clip_with_fallback() {
+    // bowtie crossing itself inside the window
+    let g = Geometry::Polygon(wkt!(POLYGON((0. 0.,4. 4.,4. 0.,0. 4.,0. 0.))));
+    let rect = ClipRect::new(-1.0, 1.0, 5.0, 3.0);
+
+    // sewing does not handle the self-crossing and produces overlapping parts
+    let Some(clipped) = rect.clip(&g) else {
+        panic!("expected output");
+    };
+    assert!(clipped.unsigned_area() > 6.0);
+
+    // fallback splits the bowtie at its crossing point into two lobes
+    let Some(Geometry::MultiPolygon(fallback)) = rect.clip_with_fallback(&g) else {
+        panic!("expected multipolygon");
+    };
+    assert_eq!(fallback.0.len(), 2);
+    assert!((fallback.unsigned_area() - 6.0_f64).abs() < 1e-9);
+
+    // sound input is clipped as usual
+    let g = Geometry::Polygon(wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.))));
+    assert_eq!(rect.clip_with_fallback(&g), rect.clip(&g));
+}
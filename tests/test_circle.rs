@@ -0,0 +1,104 @@
+use geo::{wkt, Area, CoordsIter, Geometry};
+use klippa::*;
+
+fn on_circle(c: &geo_types::Coord<f64>, r: f64) -> bool {
+    ((c.x * c.x + c.y * c.y).sqrt() - r).abs() < 1e-12
+}
+
+#[test]
+fn test_circle_clip_line() {
+    let circle = ClipCircle::new((0.0, 0.0).into(), 2.0);
+
+    // chord through the center
+    let g = Geometry::Line(geo::Line::new((-3., 0.), (3., 0.)));
+    assert_eq!(
+        circle.clip(&g),
+        Some(Geometry::Line(geo::Line::new((-2., 0.), (2., 0.))))
+    );
+
+    // linestring leaving and re-entering the circle
+    let g = Geometry::LineString(wkt!(LINESTRING(-1. 0.,-1. 5.,1. 5.,1. 0.)));
+    let Some(Geometry::MultiLineString(mls)) = circle.clip(&g) else {
+        panic!("expected multilinestring");
+    };
+    assert_eq!(mls.0.len(), 2);
+    assert_eq!(mls.0[0].0[0], (-1.0, 0.0).into());
+    assert!(on_circle(&mls.0[0].0[1], 2.0));
+
+    assert_eq!(
+        circle.clip(&Geometry::Line(geo::Line::new((3., -3.), (3., 3.)))),
+        None
+    );
+    assert_eq!(
+        circle.clip(&Geometry::Point(wkt!(POINT(1. 1.)))),
+        Some(Geometry::Point(wkt!(POINT(1. 1.))))
+    );
+    assert_eq!(circle.clip(&Geometry::Point(wkt!(POINT(2. 2.)))), None);
+}
+
+#[test]
+fn test_circle_clip_polygon() {
+    let circle = ClipCircle::new((0.0, 0.0).into(), 2.0).with_segments(8);
+
+    // right half plane: half disk with one arc of 8 segments
+    let g = Geometry::Polygon(wkt!(POLYGON((0. -3.,3. -3.,3. 3.,0. 3.,0. -3.))));
+    let Some(Geometry::Polygon(poly)) = circle.clip(&g) else {
+        panic!("expected polygon");
+    };
+    assert_eq!(poly.exterior().0.len(), 2 + 7 + 1);
+    assert!(poly
+        .exterior()
+        .0
+        .iter()
+        .all(|c| on_circle(c, 2.0) || c.x == 0.0));
+    let half = 8.0 * 0.5 * 4.0 * (std::f64::consts::PI / 8.0).sin();
+    assert!((poly.unsigned_area() - half).abs() < 1e-9);
+
+    // covering polygon with a hole inside the circle
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((-5. -5.,5. -5.,5. 5.,-5. 5.,-5. -5.),(-0.5 -0.5,-0.5 0.5,0.5 0.5,0.5 -0.5,-0.5 -0.5))),
+    );
+    let Some(Geometry::Polygon(poly)) = circle.clip(&g) else {
+        panic!("expected polygon");
+    };
+    assert_eq!(poly.exterior().coords_count(), 9);
+    assert_eq!(poly.interiors().len(), 1);
+
+    // polygon inside is kept, polygon outside is dropped
+    let inside = Geometry::Polygon(wkt!(POLYGON((0. 0.,1. 0.,0. 1.,0. 0.))));
+    assert_eq!(circle.clip(&inside), Some(inside));
+    let outside = Geometry::Polygon(wkt!(POLYGON((3. 3.,4. 3.,4. 4.,3. 3.))));
+    assert_eq!(circle.clip(&outside), None);
+}
+
+#[test]
+fn test_circle_clip_polygon_parts() {
+    let circle = ClipCircle::new((0.0, 0.0).into(), 2.0);
+
+    // U shape crossing the circle with both arms
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((-1.5 -3.,1.5 -3.,1.5 3.,0.5 3.,0.5 -2.5,-0.5 -2.5,-0.5 3.,-1.5 3.,-1.5 -3.))),
+    );
+    let Some(Geometry::MultiPolygon(mp)) = circle.clip(&g) else {
+        panic!("expected multipolygon");
+    };
+    assert_eq!(mp.0.len(), 2);
+    for poly in &mp {
+        assert!(poly
+            .exterior()
+            .0
+            .iter()
+            .all(|c| c.x * c.x + c.y * c.y <= 4.0 + 1e-12));
+    }
+
+    // hole crossing the circle cuts a notch into the disk
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((-5. -5.,5. -5.,5. 5.,-5. 5.,-5. -5.),(1. -0.5,3. -0.5,3. 0.5,1. 0.5,1. -0.5))),
+    );
+    let Some(Geometry::Polygon(poly)) = circle.clip(&g) else {
+        panic!("expected polygon");
+    };
+    assert!(poly.interiors().is_empty());
+    assert!(poly.exterior().0.contains(&(1.0, 0.5).into()));
+    assert!(poly.unsigned_area() < 4.0 * std::f64::consts::PI - 1.0);
+}
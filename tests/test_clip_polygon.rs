@@ -0,0 +1,162 @@
+use geo::{wkt, Area, BooleanOps, Geometry, Polygon};
+use klippa::*;
+
+#[test]
+fn test_clip_polygon_concave_window() {
+    // U-shaped window with a hole in its base
+    let window = ClipPolygon::new(wkt!(POLYGON(
+        (0. 0.,6. 0.,6. 6.,4. 6.,4. 2.,2. 2.,2. 6.,0. 6.,0. 0.),
+        (2.5 0.5,3.5 0.5,3.5 1.5,2.5 1.5,2.5 0.5)
+    )));
+
+    // horizontal band split by the notch into the two arms
+    let band = Geometry::Polygon(wkt!(POLYGON((-1. 3.,7. 3.,7. 4.,-1. 4.,-1. 3.))));
+    let Some(Geometry::MultiPolygon(mp)) = window.clip(&band) else {
+        panic!("expected multipolygon");
+    };
+    assert_eq!(mp.0.len(), 2);
+    assert_eq!(mp.unsigned_area(), 4.0);
+
+    // band over the base keeps the window hole
+    let base = wkt!(POLYGON((-1. 0.,7. 0.,7. 2.,-1. 2.,-1. 0.)));
+    let clipped = window.clip_polygon(&base);
+    assert_eq!(clipped.unsigned_area(), 11.0);
+    assert_eq!(clipped.0[0].interiors().len(), 1);
+
+    // line crossing the arms and the notch
+    let line = Geometry::LineString(wkt!(LINESTRING(-1. 4.,7. 4.)));
+    let Some(Geometry::MultiLineString(mls)) = window.clip(&line) else {
+        panic!("expected multilinestring");
+    };
+    assert_eq!(mls.0.len(), 2);
+
+    // points in the notch and the hole are outside
+    assert_eq!(window.clip(&Geometry::Point(wkt!(POINT(3. 4.)))), None);
+    assert_eq!(window.clip(&Geometry::Point(wkt!(POINT(3. 1.)))), None);
+    assert!(window.clip(&Geometry::Point(wkt!(POINT(1. 4.)))).is_some());
+
+    // outside the window bounding box
+    assert_eq!(window.clip(&Geometry::Point(wkt!(POINT(9. 9.)))), None);
+}
+
+#[test]
+fn test_clip_polygon_shared_boundary() {
+    let window = ClipPolygon::new(wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.))));
+
+    // input equal to the window, in either winding
+    let g = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)));
+    assert_eq!(window.clip_polygon(&g).unsigned_area(), 16.0);
+    let g = wkt!(POLYGON((0. 0.,0. 4.,4. 4.,4. 0.,0. 0.)));
+    assert_eq!(window.clip_polygon(&g).unsigned_area(), 16.0);
+
+    // input beside the window, sharing an edge
+    let g = wkt!(POLYGON((4. 0.,8. 0.,8. 4.,4. 4.,4. 0.)));
+    assert!(window.clip_polygon(&g).0.is_empty());
+
+    // input sharing part of the window bottom edge and covering a corner
+    let g = wkt!(POLYGON((1. 0.,6. 0.,6. 2.,1. 2.,1. 0.)));
+    let mp = window.clip_polygon(&g);
+    assert_eq!(mp.0.len(), 1);
+    assert_eq!(mp.unsigned_area(), 6.0);
+    assert!(mp.0[0].exterior().is_closed());
+}
+
+#[test]
+fn test_clip_polygon_touching_parts() {
+    // window of two squares touching at a corner
+    let window = ClipPolygon::new(wkt!(MULTIPOLYGON(
+        ((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.)),
+        ((2. 2.,4. 2.,4. 4.,2. 4.,2. 2.))
+    )));
+
+    // input covering the window clips to the two squares apart
+    let g = wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.)));
+    let mp = window.clip_polygon(&g);
+    assert_eq!(mp.0.len(), 2);
+    assert!(mp.0.iter().all(|p| p.exterior().0.len() == 5));
+    assert_eq!(mp.unsigned_area(), 8.0);
+
+    // input with a hole around the window keeps only the hole rings out
+    let g = wkt!(POLYGON(
+        (-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.),
+        (0.5 0.5,1.5 0.5,1.5 1.5,0.5 1.5,0.5 0.5)
+    ));
+    let mp = window.clip_polygon(&g);
+    assert_eq!(mp.unsigned_area(), 7.0);
+    assert_eq!(mp.0.iter().map(|p| p.interiors().len()).sum::<usize>(), 1);
+}
+
+// Pseudo-random star polygons clipped against a concave window with a hole, compared with the
+// boolean intersection of geo, within the rounding of geo
+#[test]
+fn test_clip_polygon_stars() {
+    let mut seed: u64 = 0x2545f4914f6cdd1d;
+    let mut rand = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 10_000) as f64 / 10_000.0
+    };
+
+    let window_poly = wkt!(POLYGON(
+        (0. 0.,6. 0.,6. 6.,4. 6.,4. 2.,2. 2.,2. 6.,0. 6.,0. 0.),
+        (2.5 0.5,3.5 0.5,3.5 1.5,2.5 1.5,2.5 0.5)
+    ));
+    let window = ClipPolygon::new(window_poly.clone());
+
+    for _ in 0..200 {
+        let (cx, cy) = (rand() * 8.0 - 1.0, rand() * 8.0 - 1.0);
+        let n = 3 + (rand() * 12.0) as usize;
+
+        let mut coords: Vec<(f64, f64)> = (0..n)
+            .map(|i| {
+                let a = i as f64 / n as f64 * std::f64::consts::TAU;
+                let r = 0.5 + rand() * 3.0;
+                (cx + r * a.cos(), cy + r * a.sin())
+            })
+            .collect();
+        coords.push(coords[0]);
+        let g = Polygon::new(coords.into(), vec![]);
+
+        let clipped = window.clip_polygon(&g);
+        let expected = window_poly.intersection(&g);
+        assert!(
+            (clipped.unsigned_area() - expected.unsigned_area()).abs() < 1e-6,
+            "{:?}",
+            g
+        );
+
+        for poly in &clipped {
+            assert!(poly.exterior().is_closed());
+            assert!(poly.exterior().0.len() >= 4);
+        }
+    }
+}
+
+#[test]
+fn test_line_contacts() {
+    use geo::Line;
+    use klippa::geom::LineExt;
+
+    // crossing
+    let a = Line::new((0., 0.), (4., 4.));
+    let b = Line::new((0., 4.), (4., 0.));
+    assert_eq!(a.contacts(&b), [Some(((2., 2.).into(), 0.5, 0.5)), None]);
+
+    // touching at an end point, given exactly
+    let b = Line::new((1., 1.), (3., 0.));
+    assert_eq!(a.contacts(&b), [Some(((1., 1.).into(), 0.25, 0.)), None]);
+
+    // collinear lines share the ends of the common part
+    let b = Line::new((5., 5.), (2., 2.));
+    assert_eq!(
+        a.contacts(&b),
+        [
+            Some(((4., 4.).into(), 1., 1. / 3.)),
+            Some(((2., 2.).into(), 0.5, 1.))
+        ]
+    );
+
+    let b = Line::new((0., 1.), (4., 5.));
+    assert_eq!(a.contacts(&b), [None, None]);
+}
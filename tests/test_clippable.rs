@@ -0,0 +1,48 @@
+use geo::{wkt, Geometry};
+use klippa::*;
+
+#[derive(Debug, PartialEq)]
+struct Feature {
+    srid: u32,
+    name: String,
+    geom: Geometry<f64>,
+}
+
+impl Clippable<f64> for Feature {
+    fn geometry(&self) -> &Geometry<f64> {
+        &self.geom
+    }
+
+    fn rebuild(&self, geom: Geometry<f64>) -> Self {
+        Feature {
+            srid: self.srid,
+            name: self.name.clone(),
+            geom,
+        }
+    }
+}
+
+#[test]
+fn test_clip_feature() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let feature = Feature {
+        srid: 3067,
+        name: "road".to_string(),
+        geom: Geometry::LineString(wkt!(LINESTRING(-1. 2.,5. 2.))),
+    };
+    assert_eq!(
+        rect.clip_feature(&feature),
+        Some(Feature {
+            srid: 3067,
+            name: "road".to_string(),
+            geom: Geometry::LineString(wkt!(LINESTRING(0. 2.,4. 2.))),
+        })
+    );
+
+    let outside = Feature {
+        geom: Geometry::Point(wkt!(POINT(5. 5.))),
+        ..feature
+    };
+    assert_eq!(rect.clip_feature(&outside), None);
+}
@@ -0,0 +1,164 @@
+use geo::{wkt, Area};
+use geo_types::{Coord, Geometry, Polygon};
+use klippa::convex::*;
+use klippa::ClipError;
+
+fn ngon(n: usize, r: f64) -> Vec<Coord<f64>> {
+    (0..n)
+        .map(|i| {
+            let a = i as f64 / n as f64 * std::f64::consts::TAU;
+            (r * a.cos(), r * a.sin()).into()
+        })
+        .collect()
+}
+
+fn area(ring: &[Coord<f64>]) -> f64 {
+    (0..ring.len())
+        .map(|i| {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+#[test]
+fn test_convex_square() {
+    let window: Vec<Coord<f64>> = vec![
+        (0., 0.).into(),
+        (4., 0.).into(),
+        (4., 4.).into(),
+        (0., 4.).into(),
+    ];
+    let subject: Vec<Coord<f64>> = vec![
+        (2., 2.).into(),
+        (6., 2.).into(),
+        (6., 6.).into(),
+        (2., 6.).into(),
+    ];
+
+    let out = clip_ring(&subject, &window);
+    assert_eq!(
+        out,
+        vec![
+            (2., 2.).into(),
+            (4., 2.).into(),
+            (4., 4.).into(),
+            (2., 4.).into()
+        ] as Vec<Coord<f64>>
+    );
+
+    // clockwise window
+    let cw: Vec<Coord<f64>> = window.iter().rev().copied().collect();
+    assert!((area(&clip_ring(&subject, &cw)) - 4.0).abs() < 1e-9);
+
+    // disjoint
+    let subject: Vec<Coord<f64>> = vec![(5., 5.).into(), (6., 5.).into(), (6., 6.).into()];
+    assert!(clip_ring(&subject, &window).is_empty());
+}
+
+#[test]
+fn test_convex_ngon_buffers() {
+    let window = ngon(256, 1.0);
+    let subject: Vec<Coord<f64>> = vec![
+        (0., -2.).into(),
+        (2., -2.).into(),
+        (2., 2.).into(),
+        (0., 2.).into(),
+    ];
+
+    let alloc = clip_ring(&subject, &window);
+
+    let mut clipper = HalfPlaneClipper::new();
+    let reuse = clipper.clip(&subject, &window).unwrap().to_vec();
+    assert_eq!(alloc, reuse);
+
+    // right half of the 256-gon
+    assert!((area(&reuse) - area(&window) / 2.0).abs() < 1e-9);
+
+    // buffers are reused between calls
+    let again = clipper.clip(&subject, &window).unwrap();
+    assert_eq!(again, reuse.as_slice());
+}
+
+#[test]
+fn test_convex_max_edges() {
+    let window = ngon(256, 1.0);
+    let subject = ngon(8, 2.0);
+
+    let mut clipper = HalfPlaneClipper::new().with_max_edges(128);
+    assert!(clipper.clip(&subject, &window).is_none());
+    assert!(clipper.clip(&subject, &ngon(128, 1.0)).is_some());
+}
+
+#[test]
+fn test_clip_convex_triangle() {
+    let window = ClipConvex::new(&wkt!(POLYGON((0. 0.,4. 0.,0. 4.,0. 0.)))).unwrap();
+
+    // square overlapping the triangle corner
+    let g = Geometry::Polygon(wkt!(POLYGON((1. 1.,5. 1.,5. 5.,1. 5.,1. 1.))));
+    let Some(Geometry::Polygon(poly)) = window.clip(&g) else {
+        panic!("expected polygon");
+    };
+    assert!((poly.unsigned_area() - 2.0_f64).abs() < 1e-12);
+
+    // line crossing the hypotenuse
+    let g = Geometry::LineString(wkt!(LINESTRING(-1. 1.,1. 1.,5. 1.)));
+    assert_eq!(
+        window.clip(&g),
+        Some(Geometry::LineString(wkt!(LINESTRING(0. 1.,1. 1.,3. 1.))))
+    );
+
+    assert_eq!(
+        window.clip(&Geometry::Point(wkt!(POINT(1. 1.)))),
+        Some(Geometry::Point(wkt!(POINT(1. 1.))))
+    );
+    assert_eq!(window.clip(&Geometry::Point(wkt!(POINT(3. 3.)))), None);
+    assert_eq!(
+        window.clip(&Geometry::Polygon(wkt!(POLYGON((3. 3.,5. 3.,5. 5.,3. 3.))))),
+        None
+    );
+}
+
+#[test]
+fn test_clip_convex_hexagon() {
+    // clockwise hexagon window
+    let mut ring = ngon(6, 2.0);
+    ring.reverse();
+    ring.push(ring[0]);
+    let window = ClipConvex::new(&Polygon::new(ring.into(), vec![])).unwrap();
+
+    // covering polygon clips to the hexagon
+    let g = Geometry::Polygon(wkt!(POLYGON((-5. -5.,5. -5.,5. 5.,-5. 5.,-5. -5.))));
+    let clipped = window.clip(&g).unwrap();
+    assert!((clipped.unsigned_area() - area(&ngon(6, 2.0))).abs() < 1e-9);
+
+    // line leaving and re-entering through the window splits in two
+    let g = Geometry::LineString(wkt!(LINESTRING(-1. 0.,-1. 5.,1. 5.,1. 0.)));
+    assert!(matches!(window.clip(&g), Some(Geometry::MultiLineString(mls)) if mls.0.len() == 2));
+}
+
+#[test]
+fn test_clip_convex_rejects_non_convex() {
+    let concave = wkt!(POLYGON((0. 0.,4. 0.,2. 1.,4. 4.,0. 4.,0. 0.)));
+    assert!(matches!(
+        ClipConvex::new(&concave),
+        Err(ClipError::NotConvex)
+    ));
+
+    // pentagram turns the same way at every vertex but winds twice
+    let star: Vec<Coord<f64>> = (0..5).map(|i| ngon(5, 1.0)[i * 2 % 5]).collect();
+    let mut star_ring = star.clone();
+    star_ring.push(star[0]);
+    assert!(matches!(
+        ClipConvex::new(&Polygon::new(star_ring.into(), vec![])),
+        Err(ClipError::NotConvex)
+    ));
+
+    let holed = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.),(1. 1.,1. 2.,2. 2.,1. 1.)));
+    assert!(matches!(ClipConvex::new(&holed), Err(ClipError::NotConvex)));
+
+    // collinear and repeated vertices are fine
+    let square = wkt!(POLYGON((0. 0.,2. 0.,4. 0.,4. 0.,4. 4.,0. 4.,0. 0.)));
+    assert!(ClipConvex::new(&square).is_ok());
+}
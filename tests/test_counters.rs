@@ -0,0 +1,117 @@
+#![cfg(feature = "counters")]
+
+use geo::wkt;
+use geo_types::{Geometry, Line};
+use klippa::rect::INTERSECTION_CALLS;
+use klippa::*;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+
+// tests read the shared counter, so they must not run concurrently
+static COUNTER_LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn test_intersection_calls_dense_line() {
+    let _lock = COUNTER_LOCK.lock().unwrap();
+    let rect = Rect::new(0.0, 0.0, 4.0, 4.0);
+
+    // densified line crossing the rect horizontally
+    let n = 2400;
+    let coords: Vec<(f64, f64)> = (0..=n)
+        .map(|i| (-10.0 + 24.0 * i as f64 / n as f64, 2.0))
+        .collect();
+    let segments: Vec<Line<f64>> = coords.windows(2).map(|w| Line::new(w[0], w[1])).collect();
+
+    let before = INTERSECTION_CALLS.load(Ordering::Relaxed);
+    let groups = rect.clip_segments(&segments);
+    let calls = INTERSECTION_CALLS.load(Ordering::Relaxed) - before;
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].first().unwrap().start, (0.0, 2.0).into());
+    assert_eq!(groups[0].last().unwrap().end, (4.0, 2.0).into());
+
+    // only the segments crossing rect sides are tested against the edges
+    let crossing = segments
+        .iter()
+        .filter(|s| (s.start.x < 0.0) != (s.end.x < 0.0) || (s.start.x > 4.0) != (s.end.x > 4.0))
+        .count();
+    assert!(calls <= crossing * 4, "calls={calls}");
+}
+
+#[test]
+fn test_intersection_calls_boundary_vertices() {
+    let _lock = COUNTER_LOCK.lock().unwrap();
+    let rect = Rect::new(0.0, 0.0, 4.0, 4.0);
+
+    // line going back and forth across the rect, with every other vertex on the left or the
+    // right edge and the rest outside
+    let coords: Vec<(f64, f64)> = (0..400)
+        .flat_map(|i| {
+            let y = i as f64 * 0.01;
+            [
+                (-1.0, y),
+                (0.0, y + 0.0025),
+                (5.0, y + 0.005),
+                (4.0, y + 0.0075),
+            ]
+        })
+        .collect();
+    let segments: Vec<Line<f64>> = coords.windows(2).map(|w| Line::new(w[0], w[1])).collect();
+
+    // baseline clipping each segment on its own
+    let before = INTERSECTION_CALLS.load(Ordering::Relaxed);
+    let single: Vec<Line<f64>> = segments
+        .iter()
+        .filter_map(|s| rect.clip_segment(s))
+        .collect();
+    let baseline = INTERSECTION_CALLS.load(Ordering::Relaxed) - before;
+
+    let before = INTERSECTION_CALLS.load(Ordering::Relaxed);
+    let mut groups = rect.clip_segments(&segments);
+    let calls = INTERSECTION_CALLS.load(Ordering::Relaxed) - before;
+
+    // the group at the input start is moved last, as for rings
+    groups.rotate_right(1);
+    assert_eq!(groups.concat(), single);
+
+    // segments starting on an edge take the start as the intersection on it
+    let on_edge = segments
+        .iter()
+        .filter(|s| s.start.x == 0.0 || s.start.x == 4.0)
+        .count();
+    assert_eq!(
+        calls,
+        baseline - on_edge,
+        "calls={calls} baseline={baseline}"
+    );
+}
+
+#[test]
+fn test_intersection_calls_disjoint_bbox() {
+    let _lock = COUNTER_LOCK.lock().unwrap();
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let far = Geometry::Polygon(wkt!(POLYGON((100. 100.,110. 100.,110. 110.,100. 100.))));
+
+    let before = INTERSECTION_CALLS.load(Ordering::Relaxed);
+    assert_eq!(rect.clip(&far), None);
+    assert_eq!(INTERSECTION_CALLS.load(Ordering::Relaxed) - before, 0);
+}
+
+#[test]
+fn test_intersection_calls_split() {
+    let _lock = COUNTER_LOCK.lock().unwrap();
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let g = Geometry::Polygon(wkt!(POLYGON((-1.3 0.7,5.1 -0.9,4.7 5.3,-0.6 4.1,-1.3 0.7))));
+
+    let before = INTERSECTION_CALLS.load(Ordering::Relaxed);
+    assert!(rect.clip(&g).is_some());
+    let clip = INTERSECTION_CALLS.load(Ordering::Relaxed) - before;
+
+    // both halves are sewn from the intersections of one clip pass
+    let before = INTERSECTION_CALLS.load(Ordering::Relaxed);
+    let (inside, outside) = rect.split(&g);
+    assert!(inside.is_some() && outside.is_some());
+    assert_eq!(INTERSECTION_CALLS.load(Ordering::Relaxed) - before, clip);
+}
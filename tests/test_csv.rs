@@ -1,4 +1,6 @@
-use geo::BoundingRect;
+#![cfg(feature = "std")]
+
+use geo::{BoundingRect, CoordsIter};
 use geo_types::{Geometry, Polygon};
 use klippa::*;
 use std::fs::File;
@@ -21,8 +23,7 @@ pub fn get_wkt(id: &str) -> (ClipRect<f64>, Geometry) {
             let bbox: Geometry = Geometry::try_from_wkt_str(iter.next().unwrap()).unwrap();
             let geom: Geometry = Geometry::try_from_wkt_str(iter.next().unwrap()).unwrap();
 
-            let rect = bbox.bounding_rect().unwrap();
-            let clipper = ClipRect::new(rect.min().x, rect.min().y, rect.max().x, rect.max().y);
+            let clipper: ClipRect<_> = bbox.bounding_rect().unwrap().into();
 
             return (clipper, geom);
         }
@@ -41,3 +42,28 @@ fn test_osm_1() {
     let (_, int) = g.into_inner();
     assert_eq!(int.len(), 1);
 }
+
+// Clipping the clipped geometry again against the same window must not change it
+fn assert_idempotent(clipper: &ClipRect<f64>, g: &Geometry) {
+    let once = clipper.clip(g).unwrap();
+    let twice = clipper.clip(&once).unwrap();
+
+    let coords = |g: &Geometry| g.coords_iter().collect::<Vec<_>>();
+    let (a, b) = (coords(&once), coords(&twice));
+    assert_eq!(a.len(), b.len());
+    for (a, b) in a.iter().zip(&b) {
+        assert!(
+            (a.x - b.x).abs() <= 1e-9 && (a.y - b.y).abs() <= 1e-9,
+            "{a:?} != {b:?}"
+        );
+    }
+}
+
+#[test]
+fn test_idempotence() {
+    let file = File::open("./assets/test/wkt.csv").unwrap();
+    for result in csv::Reader::from_reader(file).records() {
+        let (clipper, g) = get_wkt(&result.unwrap()[0]);
+        assert_idempotent(&clipper, &g);
+    }
+}
@@ -0,0 +1,79 @@
+use geo::{wkt, Area, CoordsIter, Geometry, MapCoords, Polygon};
+use geo_types::Coord;
+use klippa::*;
+
+// Shapes from the polygon tests, clipped against the rect (0, 0, 4, 4)
+fn shapes() -> Vec<Polygon<f64>> {
+    vec![
+        wkt!(POLYGON((-1.1425781250000002 0.010986328057683181,-0.7031250000000007 0.4394488164139716,0.46142578124999994 0.40649073057389273,0.7360839843749998 -0.4833927027897005,0.0109863281249997 -0.9557662177941353,-1.1425781250000002 0.010986328057683181))),
+        wkt!(POLYGON((0.2526855468749994 4.937724274302482,5.174560546875 0.0549316322096729,3.3508300781249996 -1.0436434559084802,-1.3073730468750009 4.039617826768435,0.2526855468749994 4.937724274302482))),
+        wkt!(POLYGON((2.7465820312500004 4.423090477960912,1.1755371093750002 4.543570279371764,1.109619140625 1.603794430058997,4.7900390625 1.5159363834516881,4.746093749999999 3.217302058187144,2.7026367187499996 3.19536379832941,2.7465820312500004 4.423090477960912))),
+        wkt!(POLYGON((1.3732910156250002 4.532618393971788,2.867431640625 4.5764249358536375,2.933349609374999 2.8223442468940902,4.812011718749999 2.8113711933311407,4.822998046874999 1.537901237431484,3.021240234375 1.5488835798473986,3.0322265624999996 -0.3515602939922644,1.417236328125 -0.37353251022881295,1.3952636718749996 1.4939713066293194,-0.7690429687499999 1.482988685660274,-0.7360839843749998 2.8333171968552904,1.3293457031250002 2.7126091154394203,1.109619140625 4.4449973697272895,1.3732910156250002 4.532618393971788))),
+        wkt!(POLYGON((1.9335937500000013 -1.120534032250049,1.0986328124999993 0.5932511181408557,-2.1093749999999987 0.46142079353062115,0.28564453125000056 2.482133403730572,-0.8569335937499997 5.156598738411162,1.7797851562500016 3.798483975036973,4.987792968750002 4.609278084409837,3.8452148437499996 2.2625953010152386,6.26220703125 0.9008417889908884,2.7026367187500018 0.6811362994451144,1.9335937500000013 -1.120534032250049))),
+        wkt!(POLYGON((3.7353515625000004 4.740675384778385,3.790283203125001 2.756504385543252,0.5712890625000011 2.7784514150468738,0.5603027343750014 4.718777551249872,2.13134765625 3.1624555302378496,3.7353515625000004 4.740675384778385))),
+        wkt!(POLYGON((-1.0 -1.0,5.0 -1.0,5.0 5.0,-1.0 5.0,-1.0 -1.0),(1.0 1.0,3.0 1.0,3.0 3.0,1.0 3.0,1.0 1.0))),
+        wkt!(POLYGON((-1.0 1.0,5.0 1.0,5.0 3.0,-1.0 3.0,-1.0 1.0),(1.0 -1.0,3.0 -1.0,3.0 2.0,1.0 2.0,1.0 -1.0))),
+    ]
+}
+
+// Clips the shapes in f64, and in f32 after scaling and moving them, so that the f32 values
+// carry a few units of rounding at the rect boundary
+fn compare(scale: f64, offset: f64) {
+    let to_f32 = |c: Coord<f64>| Coord {
+        x: (c.x * scale + offset) as f32,
+        y: (c.y * scale + offset) as f32,
+    };
+    let (lo, hi) = (offset as f32, (4.0 * scale + offset) as f32);
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let rect_f32 = ClipRect::<f32>::new(lo, lo, hi, hi);
+
+    for (i, poly) in shapes().into_iter().enumerate() {
+        let expected = rect.clip(&Geometry::Polygon(poly.clone())).unwrap();
+        let clip = rect_f32
+            .clip(&Geometry::Polygon(poly.map_coords(to_f32)))
+            .unwrap_or_else(|| panic!("shape {i} clipped away"));
+
+        assert_eq!(
+            clip.coords_count(),
+            expected.coords_count(),
+            "shape {i}: {clip:?}"
+        );
+
+        let area = clip.unsigned_area() as f64 / (scale * scale);
+        assert!(
+            (area - expected.unsigned_area()).abs() < 1e-3,
+            "shape {i}: area {area} != {}",
+            expected.unsigned_area()
+        );
+    }
+}
+
+#[test]
+fn test_f32_polygons() {
+    compare(1.0, 0.0);
+}
+
+#[test]
+fn test_f32_large_coordinates() {
+    compare(250.0, 10000.0);
+    compare(1000.0, -200000.0);
+    compare(10000.0, 1000000.0);
+    compare(3.0, 3000.0);
+}
+
+#[test]
+fn test_f32_perimeter_index() {
+    let rect = Rect::<f32>::new(10000.0, 10000.0, 11000.0, 11000.0);
+
+    // one unit of f32 rounding off the edge
+    let y = f32::from_bits(11000f32.to_bits() + 1);
+    assert_eq!(rect.perimeter_index(&Coord { x: 10500.0, y }), Some(2.5));
+    assert_eq!(
+        rect.perimeter_index(&Coord {
+            x: 10500.0,
+            y: 10999.0
+        }),
+        None
+    );
+}
@@ -0,0 +1,27 @@
+use geo::{wkt, Area, BoundingRect, Geometry};
+use klippa::*;
+
+#[test]
+fn test_geo_polar_cap() {
+    // ring around the north pole at 70 degrees, crossing the antimeridian
+    let g = Geometry::Polygon(wkt!(POLYGON((0. 70.,90. 70.,180. 70.,-90. 70.,0. 70.))));
+
+    // window reaching the pole
+    let clip = ClipRectGeo::new(-10.0, 60.0, 10.0, 90.0).clip(&g).unwrap();
+    let bbox = clip.bounding_rect().unwrap();
+    assert_eq!((bbox.min().x, bbox.min().y), (-10.0, 70.0));
+    assert_eq!((bbox.max().x, bbox.max().y), (10.0, 90.0));
+    assert_eq!(clip.unsigned_area(), 400.0);
+
+    // window inside the cap, on the far side of the antimeridian from the first vertex
+    let clip = ClipRectGeo::new(-120.0, 75.0, -100.0, 85.0)
+        .clip(&g)
+        .unwrap();
+    assert_eq!(clip.unsigned_area(), 200.0);
+
+    // window south of the cap
+    assert_eq!(ClipRectGeo::new(-10.0, 60.0, 10.0, 65.0).clip(&g), None);
+
+    // planar clip sees no area for the same ring
+    assert_eq!(ClipRect::new(-10.0, 60.0, 10.0, 90.0).clip(&g), None);
+}
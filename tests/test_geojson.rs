@@ -0,0 +1,53 @@
+#![cfg(feature = "geojson")]
+
+use klippa::*;
+
+#[test]
+fn test_clip_geojson_str() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let polygon = r#"{"type":"Polygon","coordinates":[[[-1.0,1.0],[3.0,1.0],[3.0,3.0],[-1.0,3.0],[-1.0,1.0]]]}"#;
+    assert_eq!(
+        rect.clip_geojson_str(polygon).unwrap().as_deref(),
+        Some(
+            r#"{"type":"Polygon","coordinates":[[[0.0,1.0],[3.0,1.0],[3.0,3.0],[0.0,3.0],[0.0,1.0]]]}"#
+        )
+    );
+
+    // members clipping away are dropped from collections
+    let collection = r#"{"type":"GeometryCollection","geometries":[{"type":"Point","coordinates":[1.0,1.0]},{"type":"Point","coordinates":[5.0,5.0]}]}"#;
+    assert_eq!(
+        rect.clip_geojson_str(collection).unwrap().as_deref(),
+        Some(
+            r#"{"type":"GeometryCollection","geometries":[{"type":"Point","coordinates":[1.0,1.0]}]}"#
+        )
+    );
+
+    let outside = r#"{"type":"LineString","coordinates":[[5.0,5.0],[6.0,6.0]]}"#;
+    assert_eq!(rect.clip_geojson_str(outside).unwrap(), None);
+    assert!(matches!(
+        rect.clip_geojson_str("{\"type\":"),
+        Err(ClipError::Parse(_))
+    ));
+}
+
+#[test]
+fn test_clip_geojson() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let feature: geojson::Feature = r#"{"type":"Feature","id":"a","properties":{"name":"x"},"geometry":{"type":"LineString","coordinates":[[-1.0,1.0],[5.0,1.0]]}}"#
+        .parse()
+        .unwrap();
+    let clipped = rect.clip_geojson(&feature).unwrap();
+    assert_eq!(clipped.id, feature.id);
+    assert_eq!(clipped.properties, feature.properties);
+    assert_eq!(
+        clipped.geometry.unwrap().to_string(),
+        r#"{"type":"LineString","coordinates":[[0.0,1.0],[4.0,1.0]]}"#
+    );
+
+    let outside: geojson::Feature = r#"{"type":"Feature","id":1,"properties":null,"geometry":{"type":"Point","coordinates":[5.0,5.0]}}"#
+        .parse()
+        .unwrap();
+    assert_eq!(rect.clip_geojson(&outside), None);
+}
@@ -38,17 +38,92 @@ fn test_intersection() {
     //
     let a = Line::new((0.0, 0.0), (4.0, 0.0));
     let b = Line::new((4.0, 4.0), (4.0, 0.0));
-    assert!(!a.intersection(&b).is_some());
-    assert!(!a.intersection(&b.reverse()).is_some());
+    assert!(a.intersection(&b).is_none());
+    assert!(a.intersection(&b.reverse()).is_none());
 
     // Non-intersecting tests
     let a = Line::new((0.0, 0.0), (0.0, 4.0));
     let b = Line::new((1.0, 1.0), (0.1, 1.0));
-    assert!(!a.intersection(&b).is_some());
-    assert!(!a.intersection(&b.reverse()).is_some());
+    assert!(a.intersection(&b).is_none());
+    assert!(a.intersection(&b.reverse()).is_none());
 
     let a = Line::new((0.0, 0.0), (0.0, 4.0));
     let b = Line::new((1.0, 1.0), (4.0, 4.0));
-    assert!(!a.intersection(&b).is_some());
-    assert!(!a.intersection(&b.reverse()).is_some());
+    assert!(a.intersection(&b).is_none());
+    assert!(a.intersection(&b.reverse()).is_none());
+}
+
+#[test]
+fn test_reverse_multi() {
+    use geo::{wkt, Winding};
+
+    let mls = wkt! { MULTILINESTRING((0.0 0.0,4.0 0.0,4.0 4.0,0.0 0.0),(1.0 1.0,2.0 1.0)) };
+    let reversed = mls.clone().reverse();
+    assert!(mls.0[0].is_ccw());
+    assert!(reversed.0[0].is_cw());
+    assert_eq!(reversed.0[1], wkt! { LINESTRING(2.0 1.0,1.0 1.0) });
+
+    let mp = wkt! { MULTIPOLYGON(
+        ((0.0 0.0,4.0 0.0,4.0 4.0,0.0 4.0,0.0 0.0),(1.0 1.0,1.0 3.0,3.0 3.0,3.0 1.0,1.0 1.0)),
+        ((5.0 0.0,6.0 0.0,6.0 1.0,5.0 0.0))
+    ) };
+    let reversed = mp.reverse();
+    assert!(reversed.0[0].exterior().is_cw());
+    assert!(reversed.0[0].interiors()[0].is_ccw());
+    assert!(reversed.0[1].exterior().is_cw());
+}
+
+#[test]
+fn test_intersection_unfused() {
+    // inputs where fused multiply-add would round differently: the result must match the
+    // separately rounded multiply and add on every platform, also when built with
+    // `-C target-feature=+fma` as in CI
+    let a = Line::new((0.0_f64, 0.0), (0.0, 4.0));
+    let b = Line::new((-0.07142857142857142_f64, 0.365), (3.7, 2.9));
+
+    let dx_c: f64 = std::hint::black_box(0.07142857142857142);
+    let slope: f64 = std::hint::black_box((2.9 - 0.365) / (3.7 + 0.07142857142857142));
+    let unfused = 0.365 + dx_c * slope;
+    assert_ne!(unfused.to_bits(), dx_c.mul_add(slope, 0.365).to_bits());
+    assert_eq!(unfused.to_bits(), 0.4130113636363636_f64.to_bits());
+
+    let p = a.intersection(&b).unwrap();
+    assert_eq!(
+        (p.x.to_bits(), p.y.to_bits()),
+        (0.0_f64.to_bits(), unfused.to_bits())
+    );
+
+    // same bits when clipping through the rect side
+    let rect = klippa::Rect::new(0.0, 0.0, 4.0, 4.0);
+    let clipped = rect.clip_segment(&b).unwrap();
+    assert_eq!(clipped.start.y.to_bits(), unfused.to_bits());
+}
+
+#[test]
+fn test_overlap() {
+    let a = Line::new((0.0, 0.0), (4.0, 0.0));
+
+    // collinear lines are not intersections, but overlap
+    let b = Line::new((-1.0, 0.0), (5.0, 0.0));
+    assert_eq!(a.intersection(&b), None);
+    assert_eq!(a.overlap(&b), Some(a));
+    assert_eq!(a.overlap(&b.reverse()), Some(a.reverse()));
+
+    // partial overlap, directed as the other line
+    let b = Line::new((3.0, 0.0), (1.0, 0.0));
+    assert_eq!(a.overlap(&b), Some(b));
+    let b = Line::new((2.0, 0.0), (6.0, 0.0));
+    assert_eq!(a.overlap(&b), Some(Line::new((2.0, 0.0), (4.0, 0.0))));
+
+    // touching at an end point, parallel and crossing lines
+    assert_eq!(a.overlap(&Line::new((4.0, 0.0), (6.0, 0.0))), None);
+    assert_eq!(a.overlap(&Line::new((0.0, 1.0), (4.0, 1.0))), None);
+    assert_eq!(a.overlap(&Line::new((2.0, -1.0), (2.0, 1.0))), None);
+
+    // vertical
+    let a = Line::new((0.0, 0.0), (0.0, 4.0));
+    assert_eq!(
+        a.overlap(&Line::new((0.0, -2.0), (0.0, 2.0))),
+        Some(Line::new((0.0, 0.0), (0.0, 2.0)))
+    );
 }
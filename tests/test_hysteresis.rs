@@ -0,0 +1,38 @@
+use geo_types::{Geometry, Line};
+use klippa::ClipRect;
+
+#[test]
+fn test_clip_with_hysteresis() {
+    let g = Geometry::Line(Line::new((2.0, 2.0), (6.0, 2.0)));
+    let mut memo = None;
+
+    let first = ClipRect::new(0.0, 0.0, 4.0, 4.0).clip_with_hysteresis(&g, 0.5, &mut memo);
+    assert_eq!(
+        first,
+        Some(Geometry::Line(Line::new((2.0, 2.0), (4.0, 2.0))))
+    );
+
+    // small move within margin keeps previous clip
+    let nudged = ClipRect::new(0.1, 0.0, 4.1, 4.0);
+    assert_eq!(nudged.clip_with_hysteresis(&g, 0.5, &mut memo), first);
+    assert_ne!(nudged.clip(&g), first);
+
+    // larger move clips again
+    let moved = ClipRect::new(2.0, 0.0, 6.0, 4.0).clip_with_hysteresis(&g, 0.5, &mut memo);
+    assert_eq!(
+        moved,
+        Some(Geometry::Line(Line::new((2.0, 2.0), (6.0, 2.0))))
+    );
+    assert_eq!(memo.as_ref().and_then(|m| m.clipped()), moved.as_ref());
+
+    // geometry leaving the expanded window clips again even on a small move
+    let far = Geometry::Line(Line::new((6.8, 2.0), (7.4, 2.0)));
+    let mut memo = None;
+    assert!(ClipRect::new(3.0, 0.0, 7.0, 4.0)
+        .clip_with_hysteresis(&far, 0.5, &mut memo)
+        .is_some());
+    assert_eq!(
+        ClipRect::new(2.7, 0.0, 6.7, 4.0).clip_with_hysteresis(&far, 0.5, &mut memo),
+        None
+    );
+}
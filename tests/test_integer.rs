@@ -0,0 +1,137 @@
+use geo_types::{polygon, Geometry, Line};
+use klippa::{ClipError, ClipRectI64};
+
+#[test]
+fn test_integer_segment() {
+    let rect = ClipRectI64::new(0, 0, 4096, 4096);
+
+    // crossing point y = 1000 + 3000 * 96 / 4096 = 1070.3 is rounded to nearest
+    assert_eq!(
+        rect.clip_segment(&Line::new((4000, 1000), (7096, 4000))),
+        Some(Line::new((4000, 1000), (4096, 1093)))
+    );
+    assert_eq!(
+        rect.clip_segment(&Line::new((-100, 50), (100, 51))),
+        Some(Line::new((0, 51), (100, 51)))
+    );
+
+    // touching the corner only
+    assert_eq!(rect.clip_segment(&Line::new((-1, 1), (1, -1))), None);
+    assert_eq!(rect.clip_segment(&Line::new((5000, 0), (5000, 10))), None);
+}
+
+#[test]
+fn test_integer_polygon() {
+    let rect = ClipRectI64::new(0, 0, 4096, 4096);
+
+    // triangle crossing right side
+    let poly: Geometry<i64> = polygon![
+        (x: 3000, y: 1000),
+        (x: 5000, y: 1000),
+        (x: 3000, y: 3001),
+        (x: 3000, y: 1000),
+    ]
+    .into();
+
+    let Some(Geometry::Polygon(clipped)) = rect.clip(&poly) else {
+        panic!("expected polygon");
+    };
+
+    // ends on the crossed side are exact, rest of the coordinate rounded
+    let mut coords = clipped.exterior().0.clone();
+    coords.pop();
+    coords.sort_by_key(|c| (c.x, c.y));
+    assert_eq!(
+        coords,
+        vec![
+            (3000, 1000).into(),
+            (3000, 3001).into(),
+            (4096, 1000).into(),
+            (4096, 1904).into(),
+        ]
+    );
+
+    // rect covered by polygon with a hole
+    let poly: Geometry<i64> = polygon!(
+        exterior: [
+            (x: -10, y: -10),
+            (x: 5000, y: -10),
+            (x: 5000, y: 5000),
+            (x: -10, y: 5000),
+        ],
+        interiors: [[
+            (x: 10, y: 10),
+            (x: 10, y: 20),
+            (x: 20, y: 20),
+            (x: 20, y: 10),
+        ]],
+    )
+    .into();
+
+    let Some(Geometry::Polygon(clipped)) = rect.clip(&poly) else {
+        panic!("expected polygon");
+    };
+    assert_eq!(clipped.exterior().0.len(), 5);
+    assert!(clipped
+        .exterior()
+        .0
+        .iter()
+        .all(|c| (c.x == 0 || c.x == 4096) && (c.y == 0 || c.y == 4096)));
+    assert_eq!(clipped.interiors().len(), 1);
+
+    // polygon outside
+    let poly: Geometry<i64> = polygon![
+        (x: 5000, y: 0),
+        (x: 6000, y: 0),
+        (x: 6000, y: 10),
+    ]
+    .into();
+    assert_eq!(rect.clip(&poly), None);
+}
+
+#[test]
+fn test_integer_range() {
+    let max = ClipRectI64::MAX_COORD;
+
+    // triangle crossing the left side, moved to the far ends of the range
+    let clipped_at = |o: i64| {
+        let rect = ClipRectI64::new(o, o, o + 4096, o + 4096);
+        let poly: Geometry<i64> = polygon![
+            (x: o + 1096, y: o + 1000),
+            (x: o - 904, y: o + 1000),
+            (x: o + 1096, y: o + 3001),
+            (x: o + 1096, y: o + 1000),
+        ]
+        .into();
+
+        let Some(Geometry::Polygon(clipped)) = rect.clip(&poly) else {
+            panic!("expected polygon at {o}: {:?}", rect.try_clip(&poly));
+        };
+        let mut coords: Vec<_> = clipped
+            .exterior()
+            .0
+            .iter()
+            .map(|c| (c.x - o, c.y - o))
+            .collect();
+        coords.pop();
+        coords.sort();
+        coords
+    };
+
+    let expected = vec![(0, 1000), (0, 1904), (1096, 1000), (1096, 3001)];
+    assert_eq!(clipped_at(0), expected);
+    assert_eq!(clipped_at(-max + 904), expected);
+    assert_eq!(clipped_at(max - 4096), expected);
+
+    // beyond the range
+    let rect = ClipRectI64::new(0, 0, 4096, 4096);
+    let line: Geometry<i64> = Line::new((-1, 10), (max + 1, 10)).into();
+    assert_eq!(rect.try_clip(&line), Err(ClipError::CoordOutOfRange));
+    assert_eq!(rect.clip(&line), None);
+    let line: Geometry<i64> = Line::new((i64::MIN, 10), (i64::MAX, 20)).into();
+    assert_eq!(rect.try_clip(&line), Err(ClipError::CoordOutOfRange));
+
+    let rect = ClipRectI64::new(0, 0, max + 1, 4096);
+    let line: Geometry<i64> = Line::new((-1, 10), (1, 10)).into();
+    assert_eq!(rect.try_clip(&line), Err(ClipError::CoordOutOfRange));
+}
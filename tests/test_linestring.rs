@@ -0,0 +1,146 @@
+use geo::{wkt, Geometry};
+use klippa::*;
+
+#[test]
+fn test_clip_in_place() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // single piece matches the allocating path
+    for mut g in [
+        wkt! { LINESTRING(-1.0 1.0,2.0 1.0,2.0 3.0,5.0 3.0) },
+        wkt! { LINESTRING(1.0 1.0,1.0 1.0,3.0 2.0) },
+        wkt! { LINESTRING(2.0 2.0,2.0 6.0) },
+    ] {
+        let expected = rect.clip(&Geometry::LineString(g.clone()));
+        let capacity = g.0.capacity();
+
+        assert!(rect.clip_in_place(&mut g));
+        assert_eq!(Some(Geometry::LineString(g.clone())), expected);
+        assert_eq!(g.0.capacity(), capacity);
+    }
+
+    // multiple pieces leave input untouched
+    let mut g = wkt! { LINESTRING(1.0 1.0,1.0 5.0,3.0 5.0,3.0 1.0) };
+    let input = g.clone();
+    assert!(!rect.clip_in_place(&mut g));
+    assert_eq!(g, input);
+
+    // nothing inside
+    let mut g = wkt! { LINESTRING(5.0 1.0,6.0 5.0) };
+    assert!(!rect.clip_in_place(&mut g));
+    assert!(g.0.is_empty());
+}
+
+#[test]
+fn test_clip_results() {
+    use std::str::FromStr;
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let records = [
+        "LINESTRING(-1 1,2 1)",
+        "LINESTRING(1 1,",
+        "POINT(5 5)",
+        "POINT 2 2",
+    ];
+
+    let results: Vec<_> = rect
+        .clip_results(records.iter().map(|s| {
+            wkt::Wkt::from_str(s)
+                .and_then(|wkt| Geometry::<f64>::try_from(wkt).map_err(|_| "conversion"))
+        }))
+        .collect();
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(
+        results[0],
+        Ok(Some(Geometry::LineString(
+            wkt! { LINESTRING(0.0 1.0,2.0 1.0) }
+        )))
+    );
+    assert!(results[1].is_err());
+    assert_eq!(results[2], Ok(None));
+    assert!(results[3].is_err());
+}
+
+#[test]
+fn test_clipped_length() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // zigzag entering the window three times
+    let g = wkt! { LINESTRING(-1.0 1.0,2.0 1.0,2.0 6.0,3.0 6.0,3.0 -1.0,6.0 -1.0,6.0 3.0,3.0 3.0) };
+    assert_eq!(rect.clipped_length(&g), 2.0 + 3.0 + 4.0 + 1.0);
+
+    let g = wkt! { LINESTRING(-1.0 -1.0,5.0 5.0) };
+    assert!((rect.clipped_length(&g) - 32.0f64.sqrt()).abs() < 1e-12);
+    assert_eq!(
+        rect.clipped_length(&wkt! { LINESTRING(5.0 5.0,6.0 6.0) }),
+        0.0
+    );
+}
+
+#[test]
+fn test_clip_linestring_ordered() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // hatching across the rect at y = 1, 3 and 2
+    let g = wkt! { LINESTRING(-1.0 1.0,5.0 1.0,5.0 3.0,-1.0 3.0,-1.0 2.0,5.0 2.0) };
+
+    let gaps = |pieces: &[geo::LineString]| -> f64 {
+        pieces
+            .windows(2)
+            .map(|w| {
+                let (a, b) = (w[0].0.last().unwrap(), w[1].0[0]);
+                (a.x - b.x).hypot(a.y - b.y)
+            })
+            .sum()
+    };
+
+    let Some(Geometry::MultiLineString(clipped)) = rect.clip(&Geometry::LineString(g.clone()))
+    else {
+        panic!("expected multiple pieces");
+    };
+    let ordered = rect.clip_linestring_ordered(&g);
+    assert_eq!(ordered.len(), 3);
+    assert!(gaps(&ordered) < gaps(&clipped.0));
+
+    // from y = 3 over to y = 2, then y = 1 backwards
+    assert_eq!(gaps(&ordered), 2.0);
+    assert_eq!(ordered[1], wkt! { LINESTRING(0.0 2.0,4.0 2.0) });
+    assert_eq!(ordered[2], wkt! { LINESTRING(4.0 1.0,0.0 1.0) });
+}
+
+#[test]
+fn test_typed_clip() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let ls = wkt!(LINESTRING(-1. 1.,5. 1.,5. 2.,-1. 2.));
+    let mls = rect.clip_linestring(&ls);
+    assert_eq!(mls.0.len(), 2);
+    assert!(mls.0.contains(&wkt!(LINESTRING(0. 1.,4. 1.))));
+    assert_eq!(
+        rect.clip(&Geometry::LineString(ls)),
+        Some(Geometry::MultiLineString(mls))
+    );
+
+    let poly = wkt!(POLYGON((-1. -1.,2. -1.,2. 2.,-1. 2.,-1. -1.)));
+    let mp = rect.clip_polygon(&poly);
+    assert_eq!(mp.0.len(), 1);
+    assert_eq!(
+        rect.clip(&Geometry::Polygon(poly)),
+        Some(Geometry::Polygon(mp.0[0].clone()))
+    );
+
+    assert!(rect
+        .clip_polygon(&wkt!(POLYGON((5. 5.,6. 5.,6. 6.,5. 5.))))
+        .0
+        .is_empty());
+}
+
+#[test]
+fn test_near_vertices_kept() {
+    // vertices closer than 1e-5 are detail, not duplicates
+    let rect = ClipRect::new(0.0, 0.0, 10.0, 10.0);
+    let g =
+        Geometry::LineString(wkt! { LINESTRING(1.0 1.0,1.000001 1.0,1.000002 1.000001,5.0 5.0) });
+    assert_eq!(rect.clip(&g), Some(g));
+}
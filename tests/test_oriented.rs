@@ -0,0 +1,57 @@
+use geo::{wkt, Area, CoordsIter, Geometry};
+use klippa::*;
+use std::f64::consts::FRAC_PI_4;
+
+#[test]
+fn test_oriented_corners() {
+    let rect = OrientedRect::new((0.0, 0.0).into(), 1.0, 1.0, FRAC_PI_4);
+    let s = 2.0_f64.sqrt();
+
+    for (c, expected) in rect
+        .corners()
+        .iter()
+        .zip([(0.0, -s), (s, 0.0), (0.0, s), (-s, 0.0)])
+    {
+        assert!((c.x - expected.0).abs() < 1e-12 && (c.y - expected.1).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_oriented_clip_polygon() {
+    // diamond window covering the square between its corners
+    let rect = OrientedRect::new((0.0, 0.0).into(), 1.0, 1.0, FRAC_PI_4);
+    let g = Geometry::Polygon(wkt!(POLYGON((-1. -1.,1. -1.,1. 1.,-1. 1.,-1. -1.))));
+
+    let clipped = rect.clip(&g).unwrap();
+    assert!((clipped.unsigned_area() - (4.0 - 2.0 * (2.0 - 2.0_f64.sqrt()).powi(2))).abs() < 1e-9);
+
+    // large polygon covering the window clips to the rotated window itself
+    let g = Geometry::Polygon(wkt!(POLYGON((-5. -5.,5. -5.,5. 5.,-5. 5.,-5. -5.))));
+    let clipped = rect.clip(&g).unwrap();
+    assert!((clipped.unsigned_area() - 4.0).abs() < 1e-9);
+    for c in clipped.coords_iter() {
+        assert!(rect
+            .corners()
+            .iter()
+            .any(|k| (k.x - c.x).abs() < 1e-12 && (k.y - c.y).abs() < 1e-12));
+    }
+}
+
+#[test]
+fn test_oriented_clip_linestring() {
+    let rect = OrientedRect::new((2.0, 2.0).into(), 2.0, 1.0, FRAC_PI_4);
+
+    // line along the long axis is cut at the window ends
+    let g = Geometry::LineString(wkt!(LINESTRING(0. 0.,4. 4.)));
+    let Some(Geometry::LineString(ls)) = rect.clip(&g) else {
+        panic!("expected linestring");
+    };
+    let d = 2.0_f64.sqrt();
+    assert!((ls.0[0].x - (2.0 - d)).abs() < 1e-12 && (ls.0[0].y - (2.0 - d)).abs() < 1e-12);
+    assert!((ls.0[1].x - (2.0 + d)).abs() < 1e-12 && (ls.0[1].y - (2.0 + d)).abs() < 1e-12);
+
+    assert_eq!(
+        rect.clip(&Geometry::LineString(wkt!(LINESTRING(4. 0.,5. 0.)))),
+        None
+    );
+}
@@ -0,0 +1,46 @@
+use geo_types::Point;
+use klippa::*;
+
+#[test]
+fn test_point_index_clustered() {
+    let mut seed: u64 = 0x2545f4914f6cdd1d;
+    let mut rand = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 10_000) as f64 / 10_000.0
+    };
+
+    // clusters of points around a few centers
+    let centers = [(10.0, 10.0), (50.0, 80.0), (90.0, 20.0)];
+    let points: Vec<Point<f64>> = (0..3000)
+        .map(|i| {
+            let (cx, cy) = centers[i % centers.len()];
+            Point::new(cx + rand() * 10.0 - 5.0, cy + rand() * 10.0 - 5.0)
+        })
+        .collect();
+
+    let index = PointIndex::new(&points);
+    assert_eq!(index.len(), points.len());
+
+    for rect in [
+        ClipRect::new(8.0, 8.0, 12.0, 11.0),
+        ClipRect::new(45.0, 75.0, 100.0, 100.0),
+        ClipRect::new(20.0, 20.0, 40.0, 40.0),
+        ClipRect::new(-100.0, -100.0, 200.0, 200.0),
+    ] {
+        let brute: Vec<usize> = points
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| rect.rect().clip_point(p).is_some())
+            .map(|(i, _)| i)
+            .collect();
+
+        assert_eq!(index.clip(&rect), brute);
+    }
+
+    // small window next to a cluster only returns that cluster's points
+    let clipped = index.clip(&ClipRect::new(8.0, 8.0, 12.0, 11.0));
+    assert!(!clipped.is_empty());
+    assert!(clipped.iter().all(|i| i % centers.len() == 0));
+}
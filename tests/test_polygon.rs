@@ -1,6 +1,9 @@
-use geo::{orient::Direction, wkt, Area, Geometry, Orient};
+use geo::{
+    orient::Direction, wkt, Area, BoundingRect, CoordsIter, Geometry, GeometryCollection,
+    MultiPolygon, Orient, Polygon,
+};
 use klippa::*;
-use wkt::ToWkt;
+use wkt::{ToWkt, TryFromWkt};
 
 #[test]
 fn test_poly_corner() {
@@ -128,13 +131,28 @@ fn test_poly_alternating() {
     let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
     let g = wkt!(POLYGON((0.6042480468750002 4.412136788910175,0.7031249999999996 -0.3845185979490111,1.7028808593749993 -0.34057416628374426,1.4062500000000002 4.3683204208762305,2.142333984375 4.401182938278325,2.373046875 -0.3515602939922502,3.779296875 -0.31860187370565995,3.581542968749999 4.390228926463408,4.262695312499999 4.3245014930191985,4.39453125 -0.6591651462894532,2.1313476562499996 -0.59325111814087,1.9226074218749998 4.160158150193411,1.6918945312499998 4.160158150193411,1.8786621093749998 -0.6371938961998609,0.1757812499999994 -0.5493079911125278,0.28564453124999956 4.423090477960898,0.6042480468750002 4.412136788910175))).orient(Direction::Default);
 
-    let clip = rect.clip(&Geometry::Polygon(g)).unwrap();
+    let clip = rect.clip(&Geometry::Polygon(g.clone())).unwrap();
     println!("{}", clip.to_wkt());
 
     assert_eq!(
             clip.to_wkt().to_string(),
             "MULTIPOLYGON(((0.6951986379166918 0,0.6127437228557678 4,0.27629650565158514 4,0.18791800426372474 0,0.6951986379166918 0)),((1.853855266008407 0,1.6981297104779016 4,1.4294518791135804 4,1.6814268204799387 0,1.853855266008407 0)),((2.355981048446358 0,2.1618086479190275 4,1.9296405738552318 4,2.105295748986531 0,2.355981048446358 0)),((3.597931175889893 4,3.765916745677536 0,4 0,4 4,3.597931175889893 4)))"
         );
+
+    // two largest of the four arms are kept
+    let (kept, dropped) = rect.clip_capped_parts(&g, 2);
+    assert_eq!((kept.0.len(), dropped), (2, 2));
+
+    let min_kept = kept
+        .iter()
+        .map(|p| p.unsigned_area())
+        .fold(f64::MAX, f64::min);
+    let Some(Geometry::MultiPolygon(all)) = rect.clip(&Geometry::Polygon(g)) else {
+        panic!("expected multipolygon");
+    };
+    let mut areas: Vec<f64> = all.iter().map(|p| p.unsigned_area()).collect();
+    areas.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    assert_eq!(min_kept, areas[1]);
 }
 
 #[test]
@@ -156,13 +174,16 @@ fn test_poly_partial_cover() {
     let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
     let g = wkt!(POLYGON((-0.28564453125000056 4.466903610394141,4.493408203125 4.466903610394141,4.251708984375 0.2856433479945224,3.5705566406249996 -0.17578097424708972,-0.2636718750000001 -0.241698501901638,-0.28564453125000056 4.466903610394141))).orient(Direction::Default);
 
-    let clip = rect.clip(&Geometry::Polygon(g)).unwrap();
+    let clip = rect.clip(&Geometry::Polygon(g.clone())).unwrap();
     println!("{}", clip.to_wkt());
 
     assert_eq!(
         clip.to_wkt().to_string(),
         "POLYGON((3.830043661190787 0,4 0.115131349345382,4 4,0 4,0 0,3.830043661190787 0))"
     );
+
+    // boundary runs from the right edge around to the bottom
+    assert_eq!(rect.clip_boundary_traversal(&g), vec![vec![1, 2, 3, 0]]);
 }
 
 #[test]
@@ -274,7 +295,7 @@ fn test_poly_osm_1() {
 
     assert_eq!(
         clip.to_wkt().to_string(),
-        "POLYGON((1565.430339280367 4880478.354395046,1289.1187557081 4880098.138426283,1011.2516821912805 4878904.673193749,-3930.6571800881015 4878904.594347052,-3935.008426915177 4877279.778806011,-6403.976136442385 4877281.232887783,-9235.215379771613 4876098.869209311,-10162.65454498546 4876354.014939786,-12244.519074389009 4877867.024280159,-15058.761527072367 4878903.797094984,-16294.095618028978 4882137.566443686,-17105.58776983645 4882333.358751661,-20002.064829662817 4882137.962502141,-20635.61553978507 4882403.477049628,-21240.50647146651 4883754.617321803,-23714.235791431638 4883754.923742886,-25588.34223474753 4885147.654317883,-26188.666685525593 4885365.0791584095,-26193.350654483478 4883753.278687337,-26438.726505290182 4883659.104415881,-27423.49684970543 4883754.703220243,-28424.207300468515 4883015.280940808,-29308.453234305103 4882731.532533518,-30176.134105180477 4882954.908911108,-31149.596739659326 4883752.21452551,-30854.327575771193 4874956.077899112,-31150.097866057884 4872432.053513398,-33611.95085836651 4870816.275565053,-34128.89200399272 4869838.7898921305,-34844.86415084541 4865963.578452767,-32847.16684993201 4864454.418419109,-31035.961471082283 4864674.437483483,-29271.680708651747 4865536.580944964,-27422.50942402108 4865961.933318165,-26473.01042334732 4865590.094673743,-24912.769013954527 4864390.978655975,-23714.617533918878 4864344.4836069895,-22544.04250143567 4864987.383681261,-20093.026264610413 4867094.427966255,-18773.863108929374 4867574.409545303,-18771.674764598472 4865963.236398067,-16299.786472972157 4865962.485427853,-16299.914305498625 4864347.459968935,-15453.378869551036 4864117.427463171,-12592.707254779683 4865962.250100052,-6408.544992098486 4866749.705079893,1565.4303392803668 4865464.306724506,1565.430339280367 4880478.354395046),(-31138.51571132672 4869204.093103909,-30279.976606608427 4872412.335600809,-28456.73438831681 4873539.419514657,-26118.561711801234 4873305.271239855,-23724.075352855645 4872424.2048876155,-24859.767133120058 4870690.063240724,-26521.452164625618 4869636.926949294,-28645.593475825088 4869168.70893825,-31138.51571132672 4869204.093103909))"
+        "POLYGON((1565.430339280367 4880478.354395046,1289.1187557081 4880098.138426283,1011.2516821912805 4878904.673193749,-3930.6571800881015 4878904.594347052,-3935.008426915177 4877279.778806011,-6403.976136442385 4877281.232887783,-9235.215379771613 4876098.869209311,-10162.65454498546 4876354.014939786,-12244.519074389009 4877867.024280159,-15058.761527072367 4878903.797094984,-16294.095618028978 4882137.566443686,-17105.58776983645 4882333.358751661,-20002.064829662817 4882137.962502141,-20635.61553978507 4882403.477049628,-21240.50647146651 4883754.617321803,-23714.235791431638 4883754.923742886,-25588.34223474753 4885147.654317883,-26188.666685525593 4885365.0791584095,-26193.350654483478 4883753.278687337,-26438.726505290182 4883659.104415881,-27423.49684970543 4883754.703220243,-28424.207300468515 4883015.280940808,-29308.453234305103 4882731.532533518,-30176.134105180477 4882954.908911108,-31149.596739659326 4883752.21452551,-30854.327575771193 4874956.077899112,-31150.097866057884 4872432.053513398,-33611.95085836651 4870816.275565053,-34128.89200399272 4869838.7898921305,-34844.86415084541 4865963.578452767,-32847.16684993201 4864454.418419109,-31035.961471082283 4864674.437483483,-29271.680708651747 4865536.580944964,-27422.50942402108 4865961.933318165,-26473.01042334732 4865590.094673743,-24912.769013954527 4864390.978655975,-23714.617533918878 4864344.4836069895,-22544.04250143567 4864987.383681261,-20093.026264610413 4867094.427966255,-18773.863108929374 4867574.409545303,-18771.674764598472 4865963.236398067,-16299.786472972157 4865962.485427853,-16299.914305498625 4864347.459968935,-15453.378869551036 4864117.427463171,-12592.707254779683 4865962.250100052,-6408.544992098486 4866749.705079893,1565.430339280367 4865464.306724506,1565.430339280367 4880478.354395046),(-31138.51571132672 4869204.093103909,-30279.976606608427 4872412.335600809,-28456.73438831681 4873539.419514657,-26118.561711801234 4873305.271239855,-23724.075352855645 4872424.2048876155,-24859.767133120058 4870690.063240724,-26521.452164625618 4869636.926949294,-28645.593475825088 4869168.70893825,-31138.51571132672 4869204.093103909))"
     );
 }
 
@@ -330,3 +351,1524 @@ fn test_poly_of_2() {
 
     assert!(clip.to_wkt().to_string().starts_with("POLYGON"));
 }
+
+#[test]
+fn test_poly_contours() {
+    let rect = ClipRect::new(-1.0, 1.5, 5.0, 5.0);
+    let g = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.),(1. 1.,1. 2.9999999999999716,3. 2.9999999999999716,3. 1.,1. 1.))).orient(Direction::Default);
+
+    let contours = rect.clip_contours(&g);
+    assert_eq!(contours.len(), 2);
+
+    let (outer, winding) = &contours[0];
+    assert_eq!(*winding, Winding::Outer);
+    assert_eq!(outer.len(), 4);
+
+    let (hole, winding) = &contours[1];
+    assert_eq!(*winding, Winding::Hole);
+    assert_eq!(hole.len(), 4);
+}
+
+#[test]
+fn test_point_window() {
+    let rect = ClipRect::new(1.0, 1.0, 1.0, 1.0);
+
+    let g = wkt!(LINESTRING(0. 0.,2. 2.));
+    assert_eq!(rect.clip(&g.into()), Some(wkt!(POINT(1. 1.)).into()));
+
+    let g = wkt!(LINESTRING(0. 0.,2. 2.1));
+    assert_eq!(rect.clip(&g.into()), None);
+
+    let g = wkt!(POLYGON((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.)));
+    assert_eq!(rect.clip(&g.into()), Some(wkt!(POINT(1. 1.)).into()));
+
+    let g =
+        wkt!(POLYGON((0. 0.,2. 0.,2. 2.,0. 2.,0. 0.),(0.5 0.5,0.5 1.5,1.5 1.5,1.5 0.5,0.5 0.5)));
+    assert_eq!(rect.clip(&g.into()), None);
+}
+
+#[test]
+fn test_poly_winding() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let ccw = wkt!(POLYGON((-1. -1.,2. -1.,2. 2.,-1. 2.,-1. -1.)));
+    let clip = rect.clip(&Geometry::Polygon(ccw)).unwrap();
+    assert_eq!(clip.to_wkt().to_string(), "POLYGON((2 0,2 2,0 2,0 0,2 0))");
+
+    // without orienting, clockwise ring walks the corners in opposite direction
+    let rect = rect.with_winding(WindingOrder::Preserve);
+    let cw = wkt!(POLYGON((-1. -1.,-1. 2.,2. 2.,2. -1.,-1. -1.)));
+    let clip = rect.clip(&Geometry::Polygon(cw)).unwrap();
+    assert_eq!(clip.to_wkt().to_string(), "POLYGON((2 0,0 0,0 2,2 2,2 0))");
+
+    // clockwise ring covering the rect
+    let cw = wkt!(POLYGON((-1. -1.,-1. 5.,5. 5.,5. -1.,-1. -1.)));
+    let clip = rect.clip(&Geometry::Polygon(cw)).unwrap();
+    assert_eq!(clip.to_wkt().to_string(), "POLYGON((0 0,0 4,4 4,4 0,0 0))");
+}
+
+#[test]
+fn test_poly_full_cover_compact() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = wkt!(POLYGON((-1. -1., -1. 5., 5. 5., 5. -1., -1. -1.))).orient(Direction::Default);
+
+    let clip = rect.clip_compact(&Geometry::Polygon(g)).unwrap();
+    assert_eq!(
+        clip,
+        Geometry::Rect(
+            wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)))
+                .bounding_rect()
+                .unwrap()
+        )
+    );
+
+    // partial cover stays as polygon
+    let g = wkt!(POLYGON((-1. -1., -1. 5., 2. 5., 2. -1., -1. -1.))).orient(Direction::Default);
+    let clip = rect.clip_compact(&Geometry::Polygon(g)).unwrap();
+    assert_eq!(clip.to_wkt().to_string(), "POLYGON((2 0,2 4,0 4,0 0,2 0))");
+}
+
+#[test]
+fn test_multipoly_nested() {
+    // landmass with a lake, island inside the lake
+    let g = Geometry::MultiPolygon(wkt!(MULTIPOLYGON(
+        ((-5. -5.,5. -5.,5. 5.,-5. 5.,-5. -5.),(-3. -3.,-3. 3.,3. 3.,3. -3.,-3. -3.)),
+        ((-1. -1.,1. -1.,1. 1.,-1. 1.,-1. -1.))
+    )));
+
+    // windows covering every level and cutting the landmass keep the lake as a hole, and the
+    // island solid
+    let clip = ClipRect::new(-4.0, -4.0, 10.0, 10.0).clip(&g).unwrap();
+    assert_eq!(
+        clip.to_wkt().to_string(),
+        "MULTIPOLYGON(((5 -4,5 5,-4 5,-4 -4,5 -4),(-3 -3,-3 3,3 3,3 -3,-3 -3)),((-1 -1,1 -1,1 1,-1 1,-1 -1)))"
+    );
+    assert!((clip.unsigned_area() - 49.0_f64).abs() < 1e-9);
+
+    let clip = ClipRect::new(-4.0, -4.0, 4.0, 4.0).clip(&g).unwrap();
+    assert_eq!(
+        clip.to_wkt().to_string(),
+        "MULTIPOLYGON(((-4 -4,4 -4,4 4,-4 4,-4 -4),(-3 -3,-3 3,3 3,3 -3,-3 -3)),((-1 -1,1 -1,1 1,-1 1,-1 -1)))"
+    );
+    assert!((clip.unsigned_area() - 32.0_f64).abs() < 1e-9);
+}
+
+#[test]
+fn test_duplicate_vertices() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let g = wkt!(POLYGON((-1. 1.,2. -1.,2. -1.,5. 1.,5. 1.,2. 3.,2. 3.,-1. 1.)));
+    let deduped = wkt!(POLYGON((-1. 1.,2. -1.,5. 1.,2. 3.,-1. 1.)));
+    assert_eq!(
+        rect.clip(&Geometry::Polygon(g)),
+        rect.clip(&Geometry::Polygon(deduped))
+    );
+
+    // duplicates on the boundary and at the ring closure
+    let g = wkt!(POLYGON((0. 1.,0. 1.,2. -1.,4. 1.,2. 3.,0. 1.,0. 1.)));
+    let deduped = wkt!(POLYGON((0. 1.,2. -1.,4. 1.,2. 3.,0. 1.)));
+    assert_eq!(
+        rect.clip(&Geometry::Polygon(g)),
+        rect.clip(&Geometry::Polygon(deduped))
+    );
+
+    let g = wkt!(LINESTRING(-1. 2.,1. 2.,1. 2.,3. 2.,3. 2.,5. 2.));
+    let deduped = wkt!(LINESTRING(-1. 2.,1. 2.,3. 2.,5. 2.));
+    assert_eq!(
+        rect.clip(&Geometry::LineString(g)),
+        rect.clip(&Geometry::LineString(deduped))
+    );
+}
+
+#[test]
+fn test_near_vertices_kept() {
+    // vertices closer than 1e-5 are detail, not duplicates
+    let rect = ClipRect::new(0.0, 0.0, 10.0, 10.0);
+
+    let g =
+        Geometry::Polygon(wkt!(POLYGON((1. 1.,1.000003 1.,1.000003 1.000003,1. 1.000003,1. 1.))));
+    assert_eq!(rect.clip(&g), Some(g));
+
+    // cut by the window, slivers are only dropped within the configured tolerance
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((-1. 1.,1.000003 1.,1.000003 1.000003,-1. 1.000003,-1. 1.))),
+    );
+    let rect = ClipRect::new(0.0, 0.0, 10.0, 10.0).with_tolerance(1e-9);
+    assert_eq!(
+        rect.clip(&g),
+        Some(Geometry::Polygon(
+            wkt!(POLYGON((0. 1.,1.000003 1.,1.000003 1.000003,0. 1.000003,0. 1.)))
+        ))
+    );
+}
+
+#[test]
+fn test_poly_convex() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // hexagon crossing the top right corner
+    let g = wkt!(POLYGON((3. 1.,5. 1.,6. 3.,5. 5.,3. 5.,2. 3.,3. 1.)));
+
+    let convex = rect.clip_convex_polygon(&g).unwrap();
+    let general: Polygon = rect
+        .clip(&Geometry::Polygon(g.clone()))
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    assert!((convex.unsigned_area() - general.unsigned_area()).abs() < 1e-9);
+    assert!(convex.exterior().is_closed());
+    for c in general.exterior().coords() {
+        assert!(convex.exterior().coords().any(|d| d == c), "{c:?}");
+    }
+
+    // disjoint hexagon
+    let g = wkt!(POLYGON((13. 1.,15. 1.,16. 3.,15. 5.,13. 5.,12. 3.,13. 1.)));
+    assert!(rect.clip_convex_polygon(&g).is_none());
+
+    // holes are clipped, and rings wound by the winding order
+    let g = wkt!(POLYGON((3. 1.,5. 1.,6. 3.,5. 5.,3. 5.,2. 3.,3. 1.),(3. 2.,3. 3.,3.5 3.,3. 2.)));
+    let convex = ClipRect::new(0.0, 0.0, 4.0, 4.0)
+        .with_winding(WindingOrder::CwExterior)
+        .clip_convex_polygon(&g)
+        .unwrap();
+    let ccw = |ls: &geo::LineString| Polygon::new(ls.clone(), vec![]).signed_area() > 0.0;
+    assert!(!ccw(convex.exterior()));
+    assert_eq!(convex.interiors().len(), 1);
+    assert!(ccw(&convex.interiors()[0]));
+}
+
+// Pseudo-random star polygons clipped against various windows.
+// Every output polygon must have closed rings with at least four coordinates.
+#[test]
+fn test_poly_valid_rings() {
+    let mut seed: u64 = 0x2545f4914f6cdd1d;
+    let mut rand = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 10_000) as f64 / 10_000.0
+    };
+
+    for _ in 0..200 {
+        let (cx, cy) = (rand() * 6.0 - 1.0, rand() * 6.0 - 1.0);
+        let n = 3 + (rand() * 12.0) as usize;
+
+        let mut coords: Vec<(f64, f64)> = (0..n)
+            .map(|i| {
+                let a = i as f64 / n as f64 * std::f64::consts::TAU;
+                let r = 0.5 + rand() * 3.0;
+                (cx + r * a.cos(), cy + r * a.sin())
+            })
+            .collect();
+        coords.push(coords[0]);
+
+        let g = Polygon::new(coords.into(), vec![]);
+        let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+        let polys: Vec<Polygon> = match rect.clip(&Geometry::Polygon(g)) {
+            Some(Geometry::Polygon(p)) => vec![p],
+            Some(Geometry::MultiPolygon(mp)) => mp.0,
+            None => vec![],
+            Some(g) => panic!("unexpected {g:?}"),
+        };
+
+        for poly in polys {
+            for ring in [poly.exterior()].into_iter().chain(poly.interiors()) {
+                assert!(ring.0.len() >= 4, "{}", poly.to_wkt());
+                assert!(ring.is_closed(), "{}", poly.to_wkt());
+                assert!(closes_exactly(ring), "{}", poly.to_wkt());
+            }
+        }
+    }
+}
+
+// First and last coordinates are bit-for-bit identical
+fn closes_exactly(ring: &geo::LineString) -> bool {
+    let (first, last) = (ring.0[0], ring.0[ring.0.len() - 1]);
+    first.x.to_bits() == last.x.to_bits() && first.y.to_bits() == last.y.to_bits()
+}
+
+#[test]
+fn test_poly_exact_closure() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    for g in [
+        // inside, closing with negative zero
+        wkt!(POLYGON((0. 1.,2. 1.,2. 3.,-0. 1.))),
+        wkt!(POLYGON((-0. 1.,2. 3.,2. 1.,0. 1.))),
+        // crossing, with a hole inside
+        wkt!(POLYGON((-1. -1.,3. -1.,3. 3.,-1. 3.,-1. -1.),(1. 1.,1. 2.,2. 2.,2. 1.,1. 1.))),
+        // covering
+        wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.))),
+        // split in two
+        wkt!(POLYGON((1. 6.,1. 2.,1.5 2.,1.5 5.,2.5 5.,2.5 2.,3. 2.,3. 6.,1. 6.))),
+    ] {
+        let polys: Vec<Polygon> = match rect.clip(&Geometry::Polygon(g)) {
+            Some(Geometry::Polygon(p)) => vec![p],
+            Some(Geometry::MultiPolygon(mp)) => mp.0,
+            g => panic!("unexpected {g:?}"),
+        };
+
+        for poly in polys {
+            for ring in [poly.exterior()].into_iter().chain(poly.interiors()) {
+                assert!(closes_exactly(ring), "{}", poly.to_wkt());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_poly_ring_convention() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // listed exterior is the hole
+    let g = wkt!(POLYGON((1. 1.,1. 3.,3. 3.,3. 1.,1. 1.),(-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.)));
+
+    let clip = rect.clip_polygon_with_convention(&g, true);
+    assert_eq!(
+        clip.to_wkt().to_string(),
+        "MULTIPOLYGON(((0 0,4 0,4 4,0 4,0 0),(1 1,1 3,3 3,3 1,1 1)))"
+    );
+    assert!((clip.unsigned_area() - 12.0_f64).abs() < 1e-9);
+
+    // as listed, the covering ring ends up as a hole, both rings in their input winding
+    let clip = rect.clip_polygon_with_convention(&g, false);
+    assert_eq!(
+        clip.to_wkt().to_string(),
+        "MULTIPOLYGON(((1 1,1 3,3 3,3 1,1 1),(0 0,4 0,4 4,0 4,0 0)))"
+    );
+}
+
+#[test]
+fn test_poly_ring_convention_nan() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let mut g = wkt!(POLYGON((1. 1.,1. 3.,3. 3.,3. 1.,1. 1.),(-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.),(2. 2.,2. 2.5,2.5 2.5,2. 2.)));
+    g.interiors_mut(|holes| holes[1].0[1].x = f64::NAN);
+
+    // rejected by default
+    assert!(rect.clip_polygon_with_convention(&g, true).0.is_empty());
+
+    let rect = rect.with_nan_policy(NanPolicy::Zero);
+    let clip = rect.clip_polygon_with_convention(&g, true);
+    assert_eq!(clip.0.len(), 1);
+    assert_eq!(
+        clip.0[0].exterior().to_wkt().to_string(),
+        "LINESTRING(0 0,4 0,4 4,0 4,0 0)"
+    );
+
+    // NaN areas compare without panicking
+    assert!(ClipRect::new(0.0, 0.0, 4.0, 4.0).clip_largest(&g).is_some());
+}
+
+#[test]
+fn test_poly_hole_without_shell() {
+    // hole extends beyond its shell, into the clipping window
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((5. 0.,8. 0.,8. 3.,5. 3.,5. 0.),(1. 1.,1. 2.,6. 2.,6. 1.,1. 1.))),
+    );
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    assert_eq!(rect.clip(&g), None);
+    assert_eq!(rect.try_clip(&g), Ok(None));
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0).with_strict(true);
+    assert_eq!(rect.clip(&g), None);
+    assert_eq!(rect.try_clip(&g), Err(ClipError::HoleOutsideShell));
+
+    // well-formed polygon outside of the window is fine in strict mode
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((5. 0.,8. 0.,8. 3.,5. 3.,5. 0.),(6. 1.,6. 2.,7. 2.,7. 1.,6. 1.))),
+    );
+    assert_eq!(rect.try_clip(&g), Ok(None));
+}
+
+#[test]
+fn test_poly_boundary_segments() {
+    // arch above the rect with two legs reaching in through the top edge
+    let g = wkt!(POLYGON((1. 6.,1. 2.,1.5 2.,1.5 5.,2.5 5.,2.5 2.,3. 2.,3. 6.,1. 6.)));
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let parts = rect.clip_polygon_boundary(&g);
+    assert_eq!(parts.len(), 2);
+
+    for (poly, segments) in &parts {
+        assert_eq!(segments.len(), 1);
+
+        // counter-clockwise leg closes leftwards along the top edge
+        let s = segments[0];
+        assert_eq!((s.ring, s.edge, s.forward), (0, 2, true));
+
+        let l = poly.exterior().lines().nth(s.segment).unwrap();
+        assert_eq!((l.start.y, l.end.y), (4.0, 4.0));
+        assert!(l.start.x > l.end.x);
+    }
+
+    // without orienting, clockwise input travels the edge backwards
+    let rect = rect.with_winding(WindingOrder::Preserve);
+    let parts = rect.clip_polygon_boundary(&g.orient(Direction::Reversed));
+    assert!(parts
+        .iter()
+        .all(|(_, segments)| segments.len() == 1 && !segments[0].forward));
+}
+
+#[test]
+fn test_poly_edge_classes() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let on_boundary = |c: &geo::Coord| c.x == 0.0 || c.x == 4.0 || c.y == 0.0 || c.y == 4.0;
+
+    // polygon crossing the right edge, with a hole crossing the top
+    let g = wkt!(POLYGON((1. 1.,6. 1.,6. 6.,1. 6.,1. 1.),(2. 2.,2. 5.,3. 5.,3. 2.,2. 2.)));
+    let (original, seam) = rect.clip_edge_classes(&g);
+
+    assert_eq!(seam.0.len(), 2);
+    assert!(seam.iter().all(|ls| ls.0.iter().all(on_boundary)));
+
+    // original pieces touch the boundary at their ends only
+    assert_eq!(original.0.len(), 2);
+    assert!(original.iter().all(|ls| ls
+        .lines()
+        .all(|l| !on_boundary(&l.start) || !on_boundary(&l.end))));
+
+    // polygon fully inside has no seams
+    let g = wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 1.)));
+    let (original, seam) = rect.clip_edge_classes(&g);
+    assert_eq!(original.0, vec![g.exterior().clone()]);
+    assert!(seam.0.is_empty());
+}
+
+#[test]
+fn test_poly_exclusion() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0).with_exclusion(ClipRect::new(1.0, 1.0, 2.0, 3.0));
+
+    // covering both rects, inner rect becomes a hole
+    let g = Geometry::Polygon(wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.))));
+    let Some(Geometry::Polygon(clip)) = rect.clip(&g) else {
+        panic!("expected polygon");
+    };
+    assert_eq!(clip.interiors().len(), 1);
+    assert_eq!(
+        clip.interiors()[0].to_wkt().to_string(),
+        "LINESTRING(1 1,1 3,2 3,2 1,1 1)"
+    );
+    assert_eq!(clip.unsigned_area(), 14.0);
+
+    // biting into the polygon from the side
+    let g = Geometry::Polygon(wkt!(POLYGON((1.5 0.5,3. 0.5,3. 3.5,1.5 3.5,1.5 0.5))));
+    let Some(Geometry::Polygon(clip)) = rect.clip(&g) else {
+        panic!("expected polygon");
+    };
+    assert!(clip.interiors().is_empty());
+    assert_eq!(clip.unsigned_area(), 4.5 - 1.0);
+
+    // cut in two
+    let g = Geometry::Polygon(wkt!(POLYGON((1.5 0.5,1.8 0.5,1.8 3.5,1.5 3.5,1.5 0.5))));
+    let Some(Geometry::MultiPolygon(clip)) = rect.clip(&g) else {
+        panic!("expected multipolygon");
+    };
+    assert_eq!(clip.0.len(), 2);
+    assert!((clip.unsigned_area() - 0.3_f64).abs() < 1e-9);
+
+    // fully excluded
+    let g = Geometry::Polygon(wkt!(POLYGON((1.2 1.2,1.8 1.2,1.8 1.8,1.2 1.2))));
+    assert_eq!(rect.clip(&g), None);
+
+    // lines are cut at the inner rect
+    let g = Geometry::LineString(wkt!(LINESTRING(0. 2.,4. 2.)));
+    assert_eq!(
+        rect.clip(&g).unwrap().to_wkt().to_string(),
+        "MULTILINESTRING((0 2,1 2),(2 2,4 2))"
+    );
+}
+
+#[test]
+fn test_poly_join_touching() {
+    // notch reaching down to the bottom edge at (2 0), leaving two parts touching there
+    let g = Geometry::Polygon(wkt!(POLYGON((0.5 -1.,3.5 -1.,3.5 2.,2. 0.,0.5 2.,0.5 -1.))));
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    assert_eq!(
+        rect.clip(&g).unwrap().to_wkt().to_string(),
+        "MULTIPOLYGON(((2 0,0.5 2,0.5 0,2 0)),((2 0,3.5 0,3.5 2,2 0)))"
+    );
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0).with_join_touching(true);
+    assert_eq!(
+        rect.clip(&g).unwrap().to_wkt().to_string(),
+        "POLYGON((3.5 0,3.5 2,2 0,0.5 2,0.5 0,3.5 0))"
+    );
+}
+
+#[test]
+fn test_poly_arcs() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // polygon crossing the right edge
+    let g = wkt!(POLYGON((1. 1.,6. 1.,6. 3.,1. 3.,1. 1.)));
+    let (arcs, rings) = rect.clip_to_arcs(&g);
+
+    assert_eq!(arcs.len(), 2);
+    assert_eq!(rings, vec![vec![0, 1]]);
+
+    let boundary: Vec<_> = arcs
+        .iter()
+        .filter(|arc| rect.is_boundary_arc(arc))
+        .collect();
+    assert_eq!(boundary.len(), 1);
+    assert_eq!(
+        boundary[0],
+        &vec![geo::coord! {x: 4.0, y: 1.0}, geo::coord! {x: 4.0, y: 3.0}]
+    );
+
+    // polygon fully inside is a single non-boundary arc
+    let g = wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 1.)));
+    let (arcs, rings) = rect.clip_to_arcs(&g);
+    assert_eq!(arcs, vec![g.exterior().0.clone()]);
+    assert_eq!(rings, vec![vec![0]]);
+    assert!(!rect.is_boundary_arc(&arcs[0]));
+}
+
+#[test]
+fn test_poly_degenerate() {
+    // collinear exterior crossing the window
+    let g = Geometry::Polygon(wkt!(POLYGON((-1. 2.,2. 2.,6. 2.,-1. 2.))));
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    assert_eq!(rect.clip(&g), None);
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0).with_degenerate_as_lines(true);
+    assert_eq!(
+        rect.clip(&g).unwrap().to_wkt().to_string(),
+        "LINESTRING(0 2,2 2,4 2,0 2)"
+    );
+
+    // degenerate parts of multipolygons are skipped
+    let g = Geometry::MultiPolygon(
+        wkt!(MULTIPOLYGON(((-1. 2.,6. 2.,-1. 2.)),((1. 1.,2. 1.,2. 3.,1. 1.)))),
+    );
+    assert_eq!(
+        rect.clip(&g).unwrap().to_wkt().to_string(),
+        "MULTIPOLYGON(((1 1,2 1,2 3,1 1)))"
+    );
+}
+
+#[test]
+fn test_poly_for_each_ring() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    for g in [
+        wkt!(POLYGON((-1. -1.,3. -1.,3. 3.,-1. 3.,-1. -1.),(1. 1.,1. 2.,2. 2.,2. 1.,1. 1.))),
+        wkt!(POLYGON((1. 6.,1. 2.,1.5 2.,1.5 5.,2.5 5.,2.5 2.,3. 2.,3. 6.,1. 6.))),
+        wkt!(POLYGON((5. 5.,6. 5.,6. 6.,5. 5.))),
+    ] {
+        let mut rings = vec![];
+        rect.clip_polygon_for_each(&g, |ls, kind| rings.push((ls.clone(), kind)));
+
+        let expected: Vec<_> = match rect.clip(&Geometry::Polygon(g)) {
+            Some(Geometry::Polygon(p)) => vec![p],
+            Some(Geometry::MultiPolygon(mp)) => mp.0,
+            _ => vec![],
+        }
+        .into_iter()
+        .flat_map(|p| {
+            let (ext, holes) = p.into_inner();
+            [(ext, Winding::Outer)]
+                .into_iter()
+                .chain(holes.into_iter().map(|ls| (ls, Winding::Hole)))
+        })
+        .collect();
+
+        assert_eq!(rings.len(), expected.len());
+        assert!(expected.iter().all(|ring| rings.contains(ring)));
+    }
+}
+
+#[test]
+fn test_poly_disjoint_loops() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // loops attached to the boundary at single points, connected outside the rect
+    let g =
+        wkt!(POLYGON((1. 0.,1.5 1.,0.5 1.,1. 0.,2. -1.,3. 0.,3.5 1.,2.5 1.,3. 0.,2. -2.,1. 0.)));
+    assert_eq!(
+        rect.clip(&Geometry::Polygon(g))
+            .unwrap()
+            .to_wkt()
+            .to_string(),
+        "MULTIPOLYGON(((1 0,1.5 1,0.5 1,1 0)),((3 0,3.5 1,2.5 1,3 0)))"
+    );
+
+    // self crossing pieces ending at corners are closed separately without repeating corners
+    let g = wkt!(POLYGON((-1. -1.,5. 5.,5. -1.,-1. 5.,-1. -1.)));
+    assert_eq!(
+        rect.clip(&Geometry::Polygon(g))
+            .unwrap()
+            .to_wkt()
+            .to_string(),
+        "MULTIPOLYGON(((0 0,4 4,0 4,0 0)),((4 0,0 4,0 0,4 0)))"
+    );
+}
+
+#[test]
+fn test_poly_precision_model() {
+    let left = ClipRect::new(0.0, 0.0, 1.0, 1.0).with_precision_model(1000.0);
+    let right = ClipRect::new(1.0, 0.0, 2.0, 1.0).with_precision_model(1000.0);
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((0.3 0.1234567,1.7 0.3333333,1.4 0.9876543,0.2 0.7777777,0.3 0.1234567))),
+    );
+
+    // coordinates of the clipped output on the shared edge x = 1
+    let shared = |rect: &ClipRect<f64>| {
+        let mut coords = vec![];
+        if let Some(Geometry::Polygon(p)) = rect.clip(&g) {
+            for c in p.exterior().coords() {
+                assert_eq!((c.x * 1000.0).round() / 1000.0, c.x);
+                assert_eq!((c.y * 1000.0).round() / 1000.0, c.y);
+                if c.x == 1.0 && !coords.contains(c) {
+                    coords.push(*c);
+                }
+            }
+        }
+        coords.sort_by(|a, b| a.y.partial_cmp(&b.y).unwrap());
+        coords
+    };
+
+    let (a, b) = (shared(&left), shared(&right));
+    assert_eq!(a.len(), 2);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_poly_many_arms() {
+    use geo::BooleanOps;
+
+    let rect = ClipRect::new(-3.0, -3.0, 3.0, 3.0);
+
+    // star with 50 curved arms twisting around the center, each leaving and entering the window
+    let arms = 50;
+    let spacing = 2.0 * std::f64::consts::PI / arms as f64;
+    let at = |r: f64, a: f64| (r * (a + 0.03 * r).cos(), r * (a + 0.03 * r).sin());
+
+    let mut coords: Vec<(f64, f64)> = (0..arms)
+        .flat_map(|i| {
+            let a = i as f64 * spacing;
+            let up = (2..=10).map(move |r| at(r as f64, a - spacing / 4.0));
+            let down = (2..=10).rev().map(move |r| at(r as f64, a + spacing / 4.0));
+            [at(1.0, a - spacing / 2.0)]
+                .into_iter()
+                .chain(up)
+                .chain(down)
+        })
+        .collect();
+    coords.push(coords[0]);
+    let g = Polygon::new(coords.into(), vec![]);
+
+    let clip = match rect.clip(&Geometry::Polygon(g.clone())) {
+        Some(Geometry::Polygon(p)) => p,
+        g => panic!("expected single polygon, got {g:?}"),
+    };
+
+    let window = geo::Rect::new((-3.0, -3.0), (3.0, 3.0)).to_polygon();
+    let expected = g.intersection(&window);
+
+    assert_eq!(clip.interiors().len(), 0);
+    assert!(closes_exactly(clip.exterior()));
+    assert!((clip.unsigned_area() - expected.unsigned_area()).abs() < 1e-6);
+}
+
+#[test]
+fn test_poly_labels() {
+    use geo::Contains;
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // C shape with centroid outside, a holed square, and a shape clipped into two parts
+    for g in [
+        wkt!(POLYGON((1. 1.,3. 1.,3. 1.5,1.5 1.5,1.5 2.5,3. 2.5,3. 3.,1. 3.,1. 1.))),
+        wkt!(POLYGON((-1. -1.,3. -1.,3. 3.,-1. 3.,-1. -1.),(0.5 0.5,2.5 0.5,2.5 2.5,0.5 2.5,0.5 0.5))),
+        wkt!(POLYGON((1. 6.,1. 2.,1.5 2.,1.5 5.,2.5 5.,2.5 2.,3. 2.,3. 6.,1. 6.))),
+    ] {
+        let labeled = rect.clip_with_labels(&g);
+        assert!(!labeled.is_empty());
+
+        for (poly, p) in labeled {
+            assert!(poly.contains(&p), "{p:?} not inside {poly:?}");
+            assert!(rect.rect().coord_inside(&p.0));
+        }
+    }
+}
+
+#[test]
+fn test_poly_corner_threading() {
+    use geo::BooleanOps;
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let window = geo::Rect::new((0.0, 0.0), (4.0, 4.0)).to_polygon();
+
+    // edges passing exactly through rect corners
+    for g in [
+        wkt!(POLYGON((-1. -1.,2. -1.,2. 2.,-1. -1.))),
+        wkt!(POLYGON((-1. -1.,5. 5.,-1. 5.,-1. -1.))),
+        wkt!(POLYGON((-1. 5.,5. -1.,-1. -1.,-1. 5.))),
+        wkt!(POLYGON((2. 2.,6. 2.,5. 5.,2. 2.))),
+        wkt!(POLYGON((-2. 2.,2. -2.,6. 2.,2. 6.,-2. 2.))),
+        wkt!(POLYGON((-1. 1.,1. -1.,2. 0.,0. 2.,-1. 1.))),
+    ] {
+        let expected = g.intersection(&window).unsigned_area();
+
+        match rect.clip(&Geometry::Polygon(g)) {
+            Some(Geometry::Polygon(p)) => {
+                assert!(closes_exactly(p.exterior()));
+                assert_eq!(p.unsigned_area(), expected);
+            }
+            g => panic!("expected single polygon, got {g:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_poly_corner_entry() {
+    use geo::{BooleanOps, MapCoords};
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let window = geo::Rect::new((0.0, 0.0), (4.0, 4.0)).to_polygon();
+
+    // Ring entering and leaving the rect at the same corner, leaving the triangle inside out
+    // of the polygon. The piece leaves the corner before it arrives back in corner order, so
+    // sewing goes all the way around the rect. Turned a quarter at a time around the rect
+    // center, so that every corner gets entered.
+    let mut g = wkt!(POLYGON((-2. -2.,6. -2.,6. 6.,-2. 6.,-2. -1.,0. 0.,1. 3.,3. 1.,0. 0.,-1. -2.,-2. -2.)));
+
+    for turn in 0..4 {
+        g = g.map_coords(|c| geo::coord! { x: 4.0 - c.y, y: c.x });
+        let expected = g.intersection(&window).unsigned_area();
+
+        match rect.clip(&Geometry::Polygon(g.clone())) {
+            Some(Geometry::Polygon(p)) => {
+                assert!(closes_exactly(p.exterior()));
+                assert!((p.unsigned_area() - expected).abs() < 1e-9, "{turn}: {p:?}");
+                assert!((expected - 12.0_f64).abs() < 1e-9);
+            }
+            g => panic!("expected single polygon, got {g:?}"),
+        }
+    }
+
+    // ring inside the rect touching a corner stays as it is
+    let g = wkt!(POLYGON((0. 0.,3. 1.,1. 3.,0. 0.)));
+    assert_eq!(
+        rect.clip(&Geometry::Polygon(g.clone())),
+        Some(Geometry::Polygon(g))
+    );
+}
+
+#[test]
+fn test_poly_area_context() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // 6 x 2 bar with a hole, both cut by the window edge
+    let g = wkt!(POLYGON((1. 1.,7. 1.,7. 3.,1. 3.,1. 1.),(2. 1.5,2. 2.5,4.5 2.5,4.5 1.5,2. 1.5)));
+    let (original, parts) = rect.clip_with_area_context(&g);
+    assert_eq!(original, 12.0 - 2.5);
+    assert_eq!(parts.len(), 1);
+
+    let clipped = rect.clip(&Geometry::Polygon(g)).unwrap().unsigned_area();
+    let sum: f64 = parts.iter().map(|(_, area)| area).sum();
+    assert_eq!(sum, clipped);
+    assert_eq!(sum / original, 4.0 / 9.5);
+
+    for (poly, area) in parts {
+        assert_eq!(poly.unsigned_area(), area);
+    }
+}
+
+#[test]
+fn test_poly_largest() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = wkt!(POLYGON((0.6042480468750002 4.412136788910175,0.7031249999999996 -0.3845185979490111,1.7028808593749993 -0.34057416628374426,1.4062500000000002 4.3683204208762305,2.142333984375 4.401182938278325,2.373046875 -0.3515602939922502,3.779296875 -0.31860187370565995,3.581542968749999 4.390228926463408,4.262695312499999 4.3245014930191985,4.39453125 -0.6591651462894532,2.1313476562499996 -0.59325111814087,1.9226074218749998 4.160158150193411,1.6918945312499998 4.160158150193411,1.8786621093749998 -0.6371938961998609,0.1757812499999994 -0.5493079911125278,0.28564453124999956 4.423090477960898,0.6042480468750002 4.412136788910175))).orient(Direction::Default);
+
+    let parts = match rect.clip(&Geometry::Polygon(g.clone())) {
+        Some(Geometry::MultiPolygon(mp)) => mp.0,
+        g => panic!("expected multipolygon, got {g:?}"),
+    };
+    assert_eq!(parts.len(), 4);
+
+    let largest = rect.clip_largest(&g).unwrap();
+    assert!(parts.contains(&largest));
+    assert!(parts
+        .iter()
+        .all(|p| p.unsigned_area() <= largest.unsigned_area()));
+}
+
+#[test]
+fn test_poly_orient() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // clockwise exterior with counter-clockwise hole
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((-1. -1.,-1. 3.,3. 3.,3. -1.,-1. -1.),(1. 1.,2. 1.,2. 2.,1. 2.,1. 1.))),
+    );
+
+    // exteriors are counter-clockwise and holes clockwise when oriented by OGC rules
+    assert_eq!(
+        ClipRect::new(0.0, 0.0, 4.0, 4.0)
+            .with_winding(WindingOrder::CcwExterior)
+            .clip(&g)
+            .unwrap()
+            .to_wkt()
+            .to_string(),
+        "POLYGON((3 0,3 3,0 3,0 0,3 0),(1 1,1 2,2 2,2 1,1 1))"
+    );
+
+    // the input winding is kept by default
+    assert_eq!(
+        rect.clip(&g).unwrap().to_wkt().to_string(),
+        "POLYGON((3 0,0 0,0 3,3 3,3 0),(1 1,2 1,2 2,1 2,1 1))"
+    );
+}
+
+#[test]
+fn test_poly_trace() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = wkt!(POLYGON((3.7353515625000004 4.740675384778385,3.790283203125001 2.756504385543252,0.5712890625000011 2.7784514150468738,0.5603027343750014 4.718777551249872,2.13134765625 3.1624555302378496,3.7353515625000004 4.740675384778385))).orient(Direction::Default);
+
+    // two pieces are joined into one, which is then closed
+    let trace = rect.clip_polygon_trace(&g);
+    let actions: Vec<SewAction> = trace.iter().map(|step| step.action).collect();
+    assert_eq!(actions, vec![SewAction::Join, SewAction::Close]);
+    assert_eq!(trace[0].queue.len(), 2);
+    assert_eq!(trace[1].queue.len(), 1);
+
+    // perimeter indices are along the top edge, in sewing order
+    let indices: Vec<f64> = trace[0].queue.iter().map(|(p, _)| *p).collect();
+    assert!(indices.iter().all(|p| (2.0..3.0).contains(p)));
+    assert!(indices[0] < indices[1]);
+}
+
+#[test]
+fn test_poly_check_holes() {
+    // second hole pokes out of the exterior on the right
+    let g = Geometry::Polygon(wkt!(POLYGON(
+        (-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.),
+        (1. 1.,1. 2.,2. 2.,2. 1.,1. 1.),
+        (3. 3.,3. 4.,6. 4.,6. 3.,3. 3.)
+    )));
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // by default the out-of-shell hole is clipped along with the others
+    let clip = rect.clip(&g).unwrap();
+    assert_eq!(clip.unsigned_area(), 16.0 - 1.0 - 1.0);
+
+    // with checking on it is dropped
+    let rect = rect.with_check_holes(true);
+    let clip = rect.clip(&g).unwrap();
+    assert_eq!(
+        clip.to_wkt().to_string(),
+        "POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,1 2,2 2,2 1,1 1))"
+    );
+
+    // and reported in strict mode
+    let rect = rect.with_strict(true);
+    assert_eq!(rect.try_clip(&g), Err(ClipError::HoleOutsideShell));
+}
+
+#[test]
+fn test_poly_idempotence() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // clipping the output again leaves it untouched, including vertices on the boundary
+    for g in [
+        wkt!(POLYGON((-1. -1.,2. -1.,2. 2.,-1. -1.))),
+        wkt!(POLYGON((-2. 2.,2. -2.,6. 2.,2. 6.,-2. 2.))),
+        wkt!(POLYGON((-1. 1.,1. -1.,2. 0.,0. 2.,-1. 1.))),
+        wkt!(POLYGON((1. 6.,1. 2.,1.5 2.,1.5 5.,2.5 5.,2.5 2.,3. 2.,3. 6.,1. 6.))),
+        wkt!(POLYGON((1. 1.,7. 1.,7. 3.,1. 3.,1. 1.),(2. 1.5,2. 2.5,4.5 2.5,4.5 1.5,2. 1.5))),
+        wkt!(POLYGON((0.2526855468749994 4.937724274302482,5.174560546875 0.0549316322096729,3.3508300781249996 -1.0436434559084802,-1.3073730468750009 4.039617826768435,0.2526855468749994 4.937724274302482))),
+    ] {
+        let once = rect.clip(&Geometry::Polygon(g)).unwrap();
+        assert_eq!(rect.clip(&once), Some(once));
+    }
+}
+
+// Polygon and multipolygon fixtures of the tests in this file
+fn polygon_fixtures() -> Vec<Geometry> {
+    let source = include_str!("test_polygon.rs");
+
+    source
+        .split("wkt!(")
+        .skip(1)
+        .filter(|s| s.starts_with("POLYGON(") || s.starts_with("MULTIPOLYGON("))
+        .map(|s| {
+            let mut depth = 0;
+            let end = s
+                .char_indices()
+                .find_map(|(i, c)| {
+                    match c {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => return None,
+                    }
+                    (depth == 0).then_some(i + 1)
+                })
+                .unwrap();
+            Geometry::try_from_wkt_str(&s[..end]).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_poly_fixtures_idempotence() {
+    let fixtures = polygon_fixtures();
+    assert!(fixtures.len() > 50);
+
+    for rect in [
+        ClipRect::new(0.0, 0.0, 4.0, 4.0),
+        ClipRect::new(-1.0, -1.0, 2.0, 2.0),
+        ClipRect::new(0.5, 0.5, 4.0, 3.5),
+    ] {
+        for g in &fixtures {
+            if let Some(once) = rect.clip(g) {
+                assert_eq!(rect.clip(&once), Some(once.clone()), "{}", g.to_wkt());
+            }
+        }
+    }
+
+    // input vertex on the perimeter is not repeated next to the edge intersection at it
+    let rect = ClipRect::new(-1.0, -1.0, 2.0, 2.0);
+    let g = Geometry::Polygon(wkt!(POLYGON((0. 0.,2. 0.,4. 0.,4. 4.,0. 4.,0. 0.))));
+    assert_eq!(
+        rect.clip(&g).unwrap().to_wkt().to_string(),
+        "POLYGON((0 2,0 0,2 0,2 2,0 2))"
+    );
+}
+
+#[test]
+fn test_poly_nan_policy() {
+    let g = Geometry::Polygon(wkt!(POLYGON((-1. -1.,5. -1.,5. 2.,-1. 2.,-1. -1.))));
+    let mut with_nan = g.clone();
+    if let Geometry::Polygon(p) = &mut with_nan {
+        p.exterior_mut(|ls| ls.0[2].y = f64::NAN);
+    }
+
+    // rejected by default
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    assert_eq!(rect.try_clip(&with_nan), Err(ClipError::NanCoord));
+    assert_eq!(rect.clip(&with_nan), None);
+    assert!(rect.clip(&g).is_some());
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0).with_nan_policy(NanPolicy::Skip);
+    assert_eq!(rect.try_clip(&with_nan), Ok(None));
+
+    // NaN is coerced to zero, flattening the corner onto the bottom edge
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0).with_nan_policy(NanPolicy::Zero);
+    let coerced = Geometry::Polygon(wkt!(POLYGON((-1. -1.,5. -1.,5. 0.,-1. 2.,-1. -1.))));
+    assert_eq!(rect.try_clip(&with_nan), Ok(rect.clip(&coerced)));
+    assert!(rect.clip(&coerced).is_some());
+}
+
+#[test]
+fn test_poly_split_tagged() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    for g in [
+        // crossing the window, and covering it all around
+        wkt!(POLYGON((1. 1.,7. 1.,7. 3.,1. 3.,1. 1.),(2. 1.5,2. 2.5,4.5 2.5,4.5 1.5,2. 1.5))),
+        wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.))),
+    ] {
+        let (members, sides) = rect.clip_split_tagged(&Geometry::Polygon(g.clone()));
+        assert_eq!(sides, vec![Side::Inside, Side::Outside]);
+
+        let area: f64 = members.iter().map(|m| m.unsigned_area()).sum();
+        assert!((area - g.unsigned_area()).abs() < 1e-9);
+    }
+
+    // nothing outside
+    let g = wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 1.)));
+    let (members, sides) = rect.clip_split_tagged(&Geometry::Polygon(g));
+    assert_eq!(sides, vec![Side::Inside]);
+    assert_eq!(members.0.len(), 1);
+}
+
+#[test]
+fn test_geometry_collection() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = wkt!(GEOMETRYCOLLECTION(
+        POINT(1. 1.),
+        POINT(5. 5.),
+        LINESTRING(-1. 2.,2. 2.),
+        POLYGON((3. 3.,6. 3.,6. 6.,3. 6.,3. 3.))
+    ));
+
+    // members clipping away are skipped
+    assert_eq!(
+        rect.clip(&Geometry::GeometryCollection(g))
+            .unwrap()
+            .to_wkt()
+            .to_string(),
+        "GEOMETRYCOLLECTION(POINT(1 1),LINESTRING(0 2,2 2),POLYGON((3 4,3 3,4 3,4 4,3 4)))"
+    );
+
+    // nothing left, also in a nested collection
+    let g = wkt!(GEOMETRYCOLLECTION(POINT(5. 5.),LINESTRING(5. 5.,6. 6.)));
+    let nested = geo::GeometryCollection::new_from(vec![Geometry::GeometryCollection(g.clone())]);
+    assert_eq!(rect.clip(&Geometry::GeometryCollection(g)), None);
+    assert_eq!(rect.clip(&Geometry::GeometryCollection(nested)), None);
+
+    // multi members clipping away are skipped too
+    let g = wkt!(GEOMETRYCOLLECTION(
+        MULTIPOINT(-1. 2.,5. 2.),
+        MULTILINESTRING((-1. -1.,5. -1.5),(-1. 5.,5. 5.)),
+        POINT(1. 1.)
+    ));
+    assert_eq!(
+        rect.clip(&Geometry::GeometryCollection(g))
+            .unwrap()
+            .to_wkt()
+            .to_string(),
+        "GEOMETRYCOLLECTION(POINT(1 1))"
+    );
+    let g = wkt!(MULTIPOINT(-1. 2.,5. 2.));
+    assert_eq!(rect.clip(&Geometry::MultiPoint(g)), None);
+    let g = wkt!(MULTILINESTRING((-1. -1.,5. -1.5),(-1. 5.,5. 5.)));
+    assert_eq!(rect.clip(&Geometry::MultiLineString(g)), None);
+}
+
+#[test]
+fn test_triangle() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let g = Geometry::Triangle(geo::Triangle::new(
+        (1.0, 1.0).into(),
+        (6.0, 1.0).into(),
+        (1.0, 3.0).into(),
+    ));
+    let clip = rect.clip(&g).unwrap();
+    assert!((clip.unsigned_area() - 4.2_f64).abs() < 1e-12);
+    assert!(matches!(clip, Geometry::Polygon(_)));
+
+    // collinear vertices clip to nothing
+    let g = Geometry::Triangle(geo::Triangle::new(
+        (1.0, 1.0).into(),
+        (2.0, 2.0).into(),
+        (3.0, 3.0).into(),
+    ));
+    assert_eq!(rect.clip(&g), None);
+}
+
+#[test]
+fn test_rect() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // overlap of the two rects
+    let g = Geometry::Rect(geo::Rect::new((2.0, 3.0), (6.0, 5.0)));
+    assert_eq!(
+        rect.clip(&g).unwrap().to_wkt().to_string(),
+        "POLYGON((2 4,4 4,4 3,2 3,2 4))"
+    );
+
+    // contained rect comes back as polygon, wound as `geo::Rect::to_polygon`, or
+    // counter-clockwise by OGC rules
+    let inside = geo::Rect::new((1.0, 1.0), (2.0, 3.0));
+    assert_eq!(
+        rect.clip(&Geometry::Rect(inside)),
+        Some(Geometry::Polygon(inside.to_polygon()))
+    );
+    assert_eq!(
+        ClipRect::new(0.0, 0.0, 4.0, 4.0)
+            .with_winding(WindingOrder::CcwExterior)
+            .clip(&Geometry::Rect(inside)),
+        Some(Geometry::Polygon(
+            inside.to_polygon().orient(Direction::Default)
+        ))
+    );
+
+    let g = Geometry::Rect(geo::Rect::new((5.0, 5.0), (6.0, 6.0)));
+    assert_eq!(rect.clip(&g), None);
+}
+
+#[test]
+fn test_poly_clip_outside() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // bar sticking out on both sides splits into two parts
+    let g = Geometry::Polygon(wkt!(POLYGON((-2. 1.,6. 1.,6. 2.,-2. 2.,-2. 1.))));
+    let Some(Geometry::MultiPolygon(outside)) = rect.clip_outside(&g) else {
+        panic!("expected two parts");
+    };
+    assert_eq!(outside.0.len(), 2);
+    assert_eq!(outside.unsigned_area(), 4.0);
+
+    // surrounding polygon gets the window as a hole
+    let g = Geometry::Polygon(wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.))));
+    let Some(Geometry::Polygon(outside)) = rect.clip_outside(&g) else {
+        panic!("expected polygon");
+    };
+    assert_eq!(outside.interiors().len(), 1);
+    assert_eq!(outside.unsigned_area(), 36.0 - 16.0);
+
+    // nothing outside
+    let g = Geometry::Polygon(wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 1.))));
+    assert_eq!(rect.clip_outside(&g), None);
+
+    let g = Geometry::LineString(wkt!(LINESTRING(2. 2.,6. 2.)));
+    assert_eq!(
+        rect.clip_outside(&g),
+        Some(Geometry::LineString(wkt!(LINESTRING(4. 2.,6. 2.))))
+    );
+}
+
+#[test]
+fn test_poly_split() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g: Geometry = Geometry::Polygon(
+        wkt!(POLYGON((0.2526855468749994 4.937724274302482,5.174560546875 0.0549316322096729,3.3508300781249996 -1.0436434559084802,-1.3073730468750009 4.039617826768435,0.2526855468749994 4.937724274302482))),
+    );
+
+    let (Some(inside), Some(outside)) = rect.split(&g) else {
+        panic!("expected both halves");
+    };
+    assert!((inside.unsigned_area() + outside.unsigned_area() - g.unsigned_area()).abs() < 1e-9);
+
+    // vertices on the window boundary are shared exactly
+    let on_boundary = |g: &Geometry| {
+        let mut cs: Vec<(f64, f64)> = g
+            .coords_iter()
+            .filter(|c| c.x == 0.0 || c.x == 4.0 || c.y == 0.0 || c.y == 4.0)
+            .filter(|c| !(c.x == 0.0 || c.x == 4.0) || !(c.y == 0.0 || c.y == 4.0))
+            .map(|c| (c.x, c.y))
+            .collect();
+        cs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        cs.dedup();
+        cs
+    };
+    assert_eq!(on_boundary(&inside), on_boundary(&outside));
+    assert_eq!(on_boundary(&inside).len(), 4);
+
+    assert_eq!(
+        rect.split(&Geometry::Polygon(wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 1.)))))
+            .1,
+        None
+    );
+}
+
+#[test]
+fn test_poly_split_settings() {
+    let g: Geometry = Geometry::Polygon(wkt!(POLYGON((1. 1.,6. 0.3,5.5 3.1,1. 3.3,1. 1.))));
+
+    // boundary vertices off the rect corners, as bits
+    let on_boundary = |g: &Geometry| {
+        let mut cs: Vec<(u64, u64)> = g
+            .coords_iter()
+            .filter(|c| (c.x == 4.0) != (c.y == 0.0 || c.y == 4.0))
+            .map(|c| (c.x.to_bits(), c.y.to_bits()))
+            .collect();
+        cs.sort();
+        cs.dedup();
+        cs
+    };
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0)
+        .with_precision_model(10.0)
+        .with_winding(WindingOrder::CwExterior);
+    let (Some(inside), Some(outside)) = rect.split(&g) else {
+        panic!("expected both halves");
+    };
+    assert_eq!(on_boundary(&inside).len(), 2);
+    assert_eq!(on_boundary(&inside), on_boundary(&outside));
+    assert!(outside
+        .coords_iter()
+        .all(|c| c.x * 10.0 == (c.x * 10.0).round()));
+
+    let Geometry::Polygon(outside) = outside else {
+        panic!("expected polygon");
+    };
+    assert!(Polygon::new(outside.exterior().clone(), vec![]).signed_area() < 0.0);
+}
+
+#[test]
+fn test_poly_split_shared_intersections() {
+    // coords of both halves on the window boundary, as bits
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let on_boundary = |g: &Geometry| {
+        let mut cs: Vec<(u64, u64)> = g
+            .coords_iter()
+            .filter(|c| c.x == 0.0 || c.x == 4.0 || c.y == 0.0 || c.y == 4.0)
+            .map(|c| (c.x.to_bits(), c.y.to_bits()))
+            .collect();
+        cs.sort();
+        cs.dedup();
+        cs
+    };
+
+    // crossing polygon with a hole cut by the window, and a line through it
+    let inputs: Vec<Geometry> = vec![
+        Geometry::Polygon(
+            wkt!(POLYGON((-1.3 0.7,5.1 -0.9,4.7 5.3,-0.6 4.1,-1.3 0.7),(3.1 1.3,4.9 2.2,3.3 2.9,3.1 1.3))),
+        ),
+        Geometry::LineString(wkt!(LINESTRING(-0.7 0.3,1.3 -1.1,4.9 3.7,2.1 4.6,-0.3 2.9))),
+    ];
+    for g in inputs {
+        let (Some(inside), Some(outside)) = rect.split(&g) else {
+            panic!("expected both halves");
+        };
+        let shared = on_boundary(&inside);
+        assert_eq!(shared, on_boundary(&outside));
+        assert!(shared.len() >= 4);
+    }
+}
+
+#[test]
+fn test_strict_input_errors() {
+    let g = Geometry::Polygon(wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 1.))));
+
+    // zero height window, while point windows are fine
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 0.0).with_strict(true);
+    assert_eq!(rect.try_clip(&g), Err(ClipError::DegenerateRect));
+    assert_eq!(rect.clip(&g), None);
+    let rect = ClipRect::new(1.0, 1.0, 1.0, 1.0).with_strict(true);
+    assert!(rect.try_clip(&g).is_ok());
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0).with_strict(true);
+    let line = Geometry::LineString(wkt!(LINESTRING(2. 2.,2. 2.)));
+    let mut infinite = line.clone();
+    if let Geometry::LineString(ls) = &mut infinite {
+        ls.0[1].x = f64::INFINITY;
+    }
+    assert_eq!(rect.try_clip(&infinite), Err(ClipError::InfiniteCoord));
+
+    let short = Geometry::Polygon(Polygon::new(wkt!(LINESTRING(1. 1.,2. 2.,1. 1.)), vec![]));
+    assert_eq!(rect.try_clip(&short), Err(ClipError::TooFewPoints));
+}
+
+#[test]
+fn test_try_clip_input_errors() {
+    let g = Geometry::Polygon(wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 1.))));
+
+    // reported without strict mode, and a degenerate window clips nothing
+    let rect = ClipRect::new(0.0, 0.0, 0.0, 4.0);
+    assert_eq!(rect.try_clip(&g), Err(ClipError::DegenerateRect));
+    let rect = ClipRect::new(2.0, 0.0, 2.0, 4.0);
+    let line = Geometry::LineString(wkt!(LINESTRING(0. 1.,4. 1.)));
+    assert_eq!(rect.try_clip(&line), Err(ClipError::DegenerateRect));
+    assert_eq!(rect.clip(&line), None);
+    let rect = ClipRect::new(1.0, 1.0, 1.0, 1.0);
+    assert!(rect.try_clip(&g).is_ok());
+
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let mut infinite = wkt!(LINESTRING(-1. 2.,2. 2.));
+    infinite.0[0].x = f64::NEG_INFINITY;
+    let infinite = Geometry::LineString(infinite);
+    assert_eq!(rect.try_clip(&infinite), Err(ClipError::InfiniteCoord));
+
+    let short = Geometry::MultiPolygon(MultiPolygon::new(vec![
+        wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 1.))),
+        Polygon::new(wkt!(LINESTRING(1. 1.,2. 2.,1. 1.)), vec![]),
+    ]));
+    // malformed input is still clipped for what it gives outside strict mode
+    assert_eq!(rect.try_clip(&short), Err(ClipError::TooFewPoints));
+    assert!(rect.clip(&short).is_some());
+
+    let gc = Geometry::GeometryCollection(GeometryCollection::new_from(vec![short]));
+    assert_eq!(rect.try_clip(&gc), Err(ClipError::TooFewPoints));
+}
+
+#[test]
+fn test_clip_all() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let geoms = vec![
+        Geometry::Polygon(wkt!(POLYGON((1. 1.,2. 1.,2. 2.,1. 1.)))),
+        Geometry::Polygon(wkt!(POLYGON((5. 5.,6. 5.,6. 6.,5. 5.)))),
+        Geometry::LineString(wkt!(LINESTRING(-1. 2.,2. 2.))),
+    ];
+
+    let clipped = rect.clip_all(&geoms);
+    assert_eq!(clipped.len(), geoms.len());
+    assert_eq!(clipped[0], rect.clip(&geoms[0]));
+    assert_eq!(clipped[1], None);
+    assert_eq!(clipped[2], rect.clip(&geoms[2]));
+}
+
+#[test]
+fn test_multipolygon_order() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // parts come out in input order, also when clipped in parallel
+    let parts: Vec<Polygon> = (0..64)
+        .map(|i| {
+            let (x, y) = ((i % 8) as f64 * 0.6 - 0.5, (i / 8) as f64 * 0.6 - 0.5);
+            geo::Rect::new((x, y), (x + 0.4, y + 0.4)).to_polygon()
+        })
+        .collect();
+    let g = Geometry::MultiPolygon(parts.clone().into());
+
+    let expected: Vec<Polygon> = parts
+        .iter()
+        .filter_map(|p| match rect.clip(&Geometry::Polygon(p.clone())) {
+            Some(Geometry::Polygon(p)) => Some(p),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(rect.clip(&g), Some(Geometry::MultiPolygon(expected.into())));
+
+    let geoms: Vec<Geometry> = parts.into_iter().map(Geometry::Polygon).collect();
+    let sequential: Vec<_> = geoms.iter().map(|g| rect.clip(g)).collect();
+    assert_eq!(rect.clip_all(&geoms), sequential);
+}
+
+#[test]
+fn test_contained_passes_unchanged() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((0.1000000000000001 0.3,3.0000000000000004 0.30000000000000004,1.7 3.3333333333333335,0.1000000000000001 0.3),(1. 1.,1.7 2.,2. 1.,1. 1.))),
+    );
+    assert_eq!(rect.clip(&g), Some(g.clone()));
+
+    let g = Geometry::MultiLineString(wkt!(MULTILINESTRING((0.1 0.2,3.9 3.8),(1. 1.,2. 2.,1. 3.))));
+    assert_eq!(rect.clip(&g), Some(g.clone()));
+
+    // clockwise exterior passes as is by default, and is still oriented by a winding order
+    let g = wkt!(POLYGON((1. 1.,1. 3.,3. 3.,3. 1.,1. 1.)));
+    assert_eq!(
+        rect.clip(&Geometry::Polygon(g.clone())),
+        Some(Geometry::Polygon(g.clone()))
+    );
+    let oriented = ClipRect::new(0.0, 0.0, 4.0, 4.0).with_winding(WindingOrder::CcwExterior);
+    assert_eq!(
+        oriented.clip(&Geometry::Polygon(g.clone())),
+        Some(Geometry::Polygon(g.orient(Direction::Default)))
+    );
+
+    // ring closed by -0 takes the full path, closing it exactly
+    let rect = ClipRect::new(-1.0, -1.0, 4.0, 4.0);
+    let mut g: Polygon<f64> = wkt!(POLYGON((0. 1.,2. 1.,2. 3.,0. 1.)));
+    g.exterior_mut(|ls| ls.0[3].x = -0.0);
+    let Some(Geometry::Polygon(clip)) = rect.clip(&Geometry::Polygon(g)) else {
+        panic!("expected polygon");
+    };
+    let ring = &clip.exterior().0;
+    assert_eq!(ring[0].x.to_bits(), ring[ring.len() - 1].x.to_bits());
+}
+
+#[test]
+fn test_clip_with_area() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // half of the square is inside, with the hole fully inside
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((-2. 0.,2. 0.,2. 4.,-2. 4.,-2. 0.),(0.5 1.,1.5 1.,1.5 2.,0.5 2.,0.5 1.))),
+    );
+    let (clipped, area) = rect.clip_with_area(&g);
+    assert!(clipped.is_some());
+    assert_eq!(area, 7.0);
+
+    let g = Geometry::LineString(wkt!(LINESTRING(-1. 1.,5. 1.)));
+    let (clipped, area) = rect.clip_with_area(&g);
+    assert!(clipped.is_some());
+    assert_eq!(area, 0.0);
+
+    let g = Geometry::Polygon(wkt!(POLYGON((5. 5.,6. 5.,6. 6.,5. 5.))));
+    assert_eq!(rect.clip_with_area(&g), (None, 0.0));
+}
+
+#[test]
+fn test_clip_sutherland_hodgman() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // simple polygon agrees with the default clip
+    let g = wkt!(POLYGON((-1. -1.,3. -1.,3. 3.,-1. 3.,-1. -1.),(1. 1.,2. 1.,2. 2.,1. 1.)));
+    let sh = rect.clip_sutherland_hodgman(&g).unwrap();
+    let clipped = rect.clip(&Geometry::Polygon(g.clone())).unwrap();
+    assert_eq!(sh.unsigned_area(), clipped.unsigned_area());
+    assert_eq!(sh.interiors().len(), 1);
+    assert!(sh.signed_area() > 0.0);
+
+    // U shape with its base outside the rect stays one ring, joined along the rect edge
+    let g = wkt!(POLYGON((1. -2.,3. -2.,3. 2.,2.5 2.,2.5 -1.,1.5 -1.,1.5 2.,1. 2.,1. -2.)));
+    let sh = rect.clip_sutherland_hodgman(&g).unwrap();
+    let Some(Geometry::MultiPolygon(parts)) = rect.clip(&Geometry::Polygon(g.clone())) else {
+        panic!("expected multipolygon");
+    };
+    assert_eq!(parts.0.len(), 2);
+    assert_eq!(sh.unsigned_area(), parts.unsigned_area());
+
+    assert_eq!(
+        rect.clip_sutherland_hodgman(&wkt!(POLYGON((5. 5.,6. 5.,6. 6.,5. 5.)))),
+        None
+    );
+}
+
+#[test]
+fn test_clip_detailed() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // overlapping the bottom left corner
+    let g = Geometry::Polygon(wkt!(POLYGON((-1. -1.,2. -1.,2. 2.,-1. 2.,-1. -1.))));
+    let (clipped, stats) = rect.clip_detailed(&g);
+    assert_eq!(clipped, rect.clip(&g));
+    assert_eq!(
+        stats,
+        ClipStats {
+            parts: 1,
+            corner_nodes_inserted: 1,
+            intersections: 2,
+            rings_dropped: 0,
+        }
+    );
+
+    // covering the rect, with a hole outside of it
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((-2. -2.,6. -2.,6. 6.,-2. 6.,-2. -2.),(5. 5.,5. 5.5,5.5 5.5,5. 5.))),
+    );
+    let (_, stats) = rect.clip_detailed(&g);
+    assert_eq!(
+        stats,
+        ClipStats {
+            parts: 1,
+            corner_nodes_inserted: 4,
+            intersections: 0,
+            rings_dropped: 1,
+        }
+    );
+
+    // U shape leaving two parts
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((1. -2.,3. -2.,3. 2.,2.5 2.,2.5 -1.,1.5 -1.,1.5 2.,1. 2.,1. -2.))),
+    );
+    let (_, stats) = rect.clip_detailed(&g);
+    assert_eq!(stats.parts, 2);
+    assert_eq!(stats.intersections, 4);
+    assert_eq!(stats.corner_nodes_inserted, 0);
+
+    let g = Geometry::LineString(wkt!(LINESTRING(-1. 1.,5. 1.,5. 2.,3. 2.)));
+    let (_, stats) = rect.clip_detailed(&g);
+    assert_eq!((stats.parts, stats.intersections), (2, 3));
+
+    // input vertex on the perimeter, at the edge intersection
+    let rect_2 = ClipRect::new(-1.0, -1.0, 2.0, 2.0);
+    let g = Geometry::Polygon(wkt!(POLYGON((0. 0.,2. 0.,4. 0.,4. 4.,0. 4.,0. 0.))));
+    assert_eq!(
+        rect_2.clip_detailed(&g).1,
+        ClipStats {
+            parts: 1,
+            corner_nodes_inserted: 1,
+            intersections: 2,
+            rings_dropped: 0,
+        }
+    );
+
+    let g = Geometry::Polygon(wkt!(POLYGON((5. 5.,6. 5.,6. 6.,5. 5.))));
+    assert_eq!(
+        rect.clip_detailed(&g),
+        (
+            None,
+            ClipStats {
+                rings_dropped: 1,
+                ..Default::default()
+            }
+        )
+    );
+}
+
+#[test]
+fn test_with_tolerance() {
+    // window in degrees: the default tolerance takes the edge near the left side as on it
+    let g = wkt!(POLYGON((0.000001 -1.,2. -1.,2. 0.5,0.000001 0.5,0.000001 -1.)));
+    let coarse = ClipRect::new(0.0, 0.0, 1.0, 1.0);
+    let fine = ClipRect::new(0.0, 0.0, 1.0, 1.0).with_tolerance(1e-9);
+    assert_eq!(coarse.clip_boundary_traversal(&g), vec![vec![3, 0, 1]]);
+    assert_eq!(fine.clip_boundary_traversal(&g), vec![vec![0, 1]]);
+
+    // points just outside the rect are taken in with a larger tolerance
+    let rect = ClipRect::new(0.0, 0.0, 1000.0, 1000.0).with_tolerance(0.01);
+    let p = Geometry::Point(wkt!(POINT(1000.005 500.)));
+    assert_eq!(rect.clip(&p), Some(p.clone()));
+    assert_eq!(ClipRect::new(0.0, 0.0, 1000.0, 1000.0).clip(&p), None);
+}
+
+#[test]
+fn test_clip_polygons_iter() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let mp = wkt!(MULTIPOLYGON(
+        ((-1. -1.,2. -1.,2. 2.,-1. 2.,-1. -1.)),
+        ((5. 5.,6. 5.,6. 6.,5. 5.)),
+        ((1. -2.,3. -2.,3. 2.,2.5 2.,2.5 -1.,1.5 -1.,1.5 2.,1. 2.,1. -2.))
+    ));
+
+    let mut it = rect.clip_polygons_iter(&mp);
+    assert_eq!(it.next().map(|mp| mp.0.len()), Some(1));
+    assert_eq!(it.next().map(|mp| mp.0.len()), Some(0));
+    assert_eq!(it.next().map(|mp| mp.0.len()), Some(2));
+    assert_eq!(it.next(), None);
+
+    let parts: Vec<Polygon> = rect.clip_polygons_iter(&mp).flatten().collect();
+    assert_eq!(
+        Some(Geometry::MultiPolygon(parts.into())),
+        rect.clip(&Geometry::MultiPolygon(mp.clone()))
+    );
+}
+
+#[test]
+fn test_sliver_dropped() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // grazing the bottom edge, leaving a ring of width 0.000001 inside
+    let sliver = wkt!(POLYGON((0.5 -1.,3.5 -1.,3.5 0.000001,0.5 0.000001,0.5 -1.)));
+    assert!(rect.clip(&Geometry::Polygon(sliver.clone())).is_none());
+    assert!(rect.clip_polygon(&sliver).0.is_empty());
+
+    let thin = wkt!(POLYGON((0.5 -1.,3.5 -1.,3.5 0.001,0.5 0.001,0.5 -1.)));
+    assert_eq!(rect.clip_polygon(&thin).0.len(), 1);
+
+    // sliver part is dropped, the other part kept
+    let g = wkt!(POLYGON((0.5 -1.,3.5 -1.,3.5 0.000001,3. 0.000001,3. -0.5,2.5 -0.5,2.5 2.,0.5 2.,0.5 -1.)));
+    let clip = rect.clip_polygon(&g);
+    assert_eq!(clip.0.len(), 1);
+    assert!((clip.unsigned_area() - 4.0_f64).abs() < 1e-9);
+}
+
+#[test]
+fn test_poly_angle_exact_vertices() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = wkt!(POLYGON((2.7465820312500004 4.423090477960912,1.1755371093750002 4.543570279371764,1.109619140625 1.603794430058997,4.7900390625 1.5159363834516881,4.746093749999999 3.217302058187144,2.7026367187499996 3.19536379832941,2.7465820312500004 4.423090477960912)));
+
+    let clip = rect.clip(&Geometry::Polygon(g.clone())).unwrap();
+    let bits = |c: &geo::Coord| (c.x.to_bits(), c.y.to_bits());
+    let output: Vec<_> = clip.coords_iter().map(|c| bits(&c)).collect();
+
+    let inside: Vec<_> = g
+        .exterior()
+        .coords()
+        .filter(|c| c.x > 0.0 && c.x < 4.0 && c.y > 0.0 && c.y < 4.0)
+        .collect();
+    assert_eq!(inside.len(), 2);
+    for c in inside {
+        assert!(output.contains(&bits(c)), "{c:?} moved");
+    }
+}
+
+#[test]
+fn test_winding_order() {
+    let rect = || ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((-1. -1.,-1. 3.,3. 3.,3. -1.,-1. -1.),(1. 1.,2. 1.,2. 2.,1. 2.,1. 1.))),
+    );
+    let winding = |clip: Geometry<f64>| {
+        let Geometry::Polygon(poly) = clip else {
+            panic!("not a polygon: {clip:?}");
+        };
+        let ccw = |ls: &geo::LineString| Polygon::new(ls.clone(), vec![]).signed_area() > 0.0;
+        (ccw(poly.exterior()), ccw(&poly.interiors()[0]))
+    };
+
+    // input rings wind clockwise and counter-clockwise, and are kept so by default
+    assert_eq!(winding(rect().clip(&g).unwrap()), (false, true));
+    let preserve = rect().with_winding(WindingOrder::Preserve);
+    assert_eq!(preserve.clip(&g), rect().clip(&g));
+
+    // exteriors counter-clockwise and holes clockwise by OGC rules, or the other way around
+    let ccw = rect().with_winding(WindingOrder::CcwExterior);
+    assert_eq!(winding(ccw.clip(&g).unwrap()), (true, false));
+
+    let cw = rect().with_winding(WindingOrder::CwExterior);
+    assert_eq!(winding(cw.clip(&g).unwrap()), (false, true));
+}
@@ -0,0 +1,181 @@
+use geo::{wkt, Geometry};
+use klippa::*;
+
+#[test]
+fn test_intersects_touch() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // touching corner at a point
+    let g = Geometry::Polygon(wkt!(POLYGON((4. 4.,6. 4.,6. 6.,4. 6.,4. 4.))));
+    assert!(rect.intersects_with(&g, true));
+    assert!(!rect.intersects_with(&g, false));
+
+    // touching along the right edge
+    let g = Geometry::Polygon(wkt!(POLYGON((4. 1.,6. 1.,6. 3.,4. 3.,4. 1.))));
+    assert!(rect.intersects_with(&g, true));
+    assert!(!rect.intersects_with(&g, false));
+
+    // overlapping
+    let g = Geometry::Polygon(wkt!(POLYGON((3. 1.,6. 1.,6. 3.,3. 3.,3. 1.))));
+    assert!(rect.intersects_with(&g, true));
+    assert!(rect.intersects_with(&g, false));
+
+    // covering the whole rect
+    let g = Geometry::Polygon(wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.))));
+    assert!(rect.intersects_with(&g, false));
+
+    // rect inside a hole, touching its boundary
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((-2. -2.,6. -2.,6. 6.,-2. 6.,-2. -2.),(0. 0.,0. 4.,4. 4.,4. 0.,0. 0.))),
+    );
+    assert!(rect.intersects_with(&g, true));
+    assert!(!rect.intersects_with(&g, false));
+
+    // disjoint
+    let g = Geometry::Polygon(wkt!(POLYGON((5. 1.,6. 1.,6. 3.,5. 1.))));
+    assert!(!rect.intersects_with(&g, true));
+
+    // lines along the boundary and through a corner
+    let g = Geometry::LineString(wkt!(LINESTRING(0. 1.,0. 3.)));
+    assert!(rect.intersects_with(&g, true));
+    assert!(!rect.intersects_with(&g, false));
+
+    let g = Geometry::LineString(wkt!(LINESTRING(-1. 1.,1. -1.)));
+    assert!(rect.intersects_with(&g, true));
+    assert!(!rect.intersects_with(&g, false));
+
+    // points
+    let g = Geometry::Point(wkt!(POINT(4. 2.)));
+    assert!(rect.intersects_with(&g, true));
+    assert!(!rect.intersects_with(&g, false));
+}
+
+#[test]
+fn test_classify() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let inside = Geometry::Polygon(wkt!(POLYGON((1. 1.,3. 1.,3. 3.,1. 1.))));
+    assert_eq!(rect.classify(&inside), Relation::Inside);
+    assert_eq!(rect.classify_refined(&inside), Relation::Inside);
+
+    let crossing = Geometry::Polygon(wkt!(POLYGON((3. 1.,6. 1.,6. 3.,3. 1.))));
+    assert_eq!(rect.classify(&crossing), Relation::Crossing);
+    assert_eq!(rect.classify_refined(&crossing), Relation::Crossing);
+
+    let outside = Geometry::LineString(wkt!(LINESTRING(5. 5.,6. 7.)));
+    assert_eq!(rect.classify(&outside), Relation::Outside);
+    assert_eq!(rect.classify_refined(&outside), Relation::Outside);
+
+    // bounding box crosses the window, but the diagonal line passes by its corner
+    let by_corner = Geometry::LineString(wkt!(LINESTRING(3. 6.,6. 3.)));
+    assert_eq!(rect.classify(&by_corner), Relation::Crossing);
+    assert_eq!(rect.classify_refined(&by_corner), Relation::Outside);
+}
+
+#[test]
+fn test_clip_relate() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let g = wkt!(POLYGON((5. 5.,6. 5.,6. 6.,5. 5.)));
+    assert_eq!(rect.clip_relate(&g), ClipRelation::Disjoint);
+
+    // sharing the right edge
+    let g = wkt!(POLYGON((4. 0.,6. 0.,6. 4.,4. 4.,4. 0.)));
+    assert_eq!(rect.clip_relate(&g), ClipRelation::Touches);
+
+    let g = wkt!(POLYGON((3. 1.,6. 1.,6. 3.,3. 3.,3. 1.)));
+    assert_eq!(
+        rect.clip_relate(&g),
+        ClipRelation::Overlaps(wkt!(MULTIPOLYGON(((4. 3.,3. 3.,3. 1.,4. 1.,4. 3.)))))
+    );
+
+    let g = wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.)));
+    assert_eq!(rect.clip_relate(&g), ClipRelation::Contains);
+
+    let g = wkt!(POLYGON((1. 1.,3. 1.,3. 3.,1. 1.)));
+    assert_eq!(rect.clip_relate(&g), ClipRelation::Within);
+}
+
+#[test]
+fn test_intersects() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let inside = Geometry::Polygon(wkt!(POLYGON((1. 1.,3. 1.,3. 3.,1. 1.))));
+    assert!(rect.intersects(&inside));
+
+    // bounding box overlaps, but the triangle misses the rect
+    let corner = Geometry::Polygon(wkt!(POLYGON((5. 3.5,5. 5.,3.5 5.,5. 3.5))));
+    assert!(!rect.intersects(&corner));
+
+    let crossing = Geometry::LineString(wkt!(LINESTRING(-1. 2.,5. 2.)));
+    assert!(rect.intersects(&crossing));
+
+    // covering the rect with no vertices inside
+    let cover = Geometry::Polygon(wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.))));
+    assert!(rect.intersects(&cover));
+
+    let touching = Geometry::Point(wkt!(POINT(4. 2.)));
+    assert!(rect.intersects(&touching));
+
+    let outside = Geometry::Point(wkt!(POINT(5. 2.)));
+    assert!(!rect.intersects(&outside));
+
+    for g in [inside, corner, crossing, cover, outside] {
+        assert_eq!(rect.intersects(&g), rect.clip(&g).is_some());
+    }
+}
+
+#[test]
+fn test_contains() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let inside = Geometry::Polygon(wkt!(POLYGON((1. 1.,3. 1.,3. 3.,1. 1.))));
+    assert!(rect.contains(&inside));
+    assert_eq!(rect.clip(&inside), Some(inside));
+
+    // boundary counts as inside
+    let on_edge = Geometry::LineString(wkt!(LINESTRING(0. 0.,4. 0.,4. 4.)));
+    assert!(rect.contains(&on_edge));
+
+    // intersecting, but not contained
+    let crossing = Geometry::LineString(wkt!(LINESTRING(1. 2.,5. 2.)));
+    assert!(rect.intersects(&crossing));
+    assert!(!rect.contains(&crossing));
+
+    let cover = Geometry::Polygon(wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.))));
+    assert!(!rect.contains(&cover));
+
+    let outside = Geometry::Point(wkt!(POINT(5. 2.)));
+    assert!(!rect.contains(&outside));
+}
+
+#[test]
+fn test_rect_inside_polygon() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let cover = wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.)));
+    assert!(rect.rect_inside_polygon(&cover));
+    assert!(rect
+        .rect()
+        .is_contained(&cover.exterior().lines().collect::<Vec<_>>()));
+
+    // sharing the rect boundary still leaves the rect inside
+    let touching = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.)));
+    assert!(rect.rect_inside_polygon(&touching));
+
+    // rect inside the hole
+    let hole =
+        wkt!(POLYGON((-2. -2.,6. -2.,6. 6.,-2. 6.,-2. -2.),(-1. -1.,-1. 5.,5. 5.,5. -1.,-1. -1.)));
+    assert!(!rect.rect_inside_polygon(&hole));
+
+    // hole reaching into the rect
+    let inner_hole =
+        wkt!(POLYGON((-1. -1.,5. -1.,5. 5.,-1. 5.,-1. -1.),(1. 1.,1. 3.,3. 3.,3. 1.,1. 1.)));
+    assert!(!rect.rect_inside_polygon(&inner_hole));
+
+    let crossing = wkt!(POLYGON((2. -1.,5. -1.,5. 5.,2. 5.,2. -1.)));
+    assert!(!rect.rect_inside_polygon(&crossing));
+
+    let outside = wkt!(POLYGON((5. 5.,6. 5.,6. 6.,5. 5.)));
+    assert!(!rect.rect_inside_polygon(&outside));
+}
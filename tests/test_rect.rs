@@ -61,9 +61,9 @@ fn test_clip_multi() {
     let rect = Rect::new(0.0, 0.0, 4.0, 4.0);
 
     assert_eq!(
-        rect.clip_segments(&vec![
+        rect.clip_segments(&[
             Line::new((-1.0, 2.0), (1.0, 2.0)),
-            Line::new((1.0, 2.0), (5.0, 2.0)),
+            Line::new((1.0, 2.0), (5.0, 2.0))
         ]),
         vec![vec![
             Line::new((0.0, 2.0), (1.0, 2.0)),
@@ -72,10 +72,10 @@ fn test_clip_multi() {
     );
 
     assert_eq!(
-        rect.clip_segments(&vec![
+        rect.clip_segments(&[
             Line::new((-1.0, 2.0), (1.0, 2.0)),
             Line::new((1.0, 2.0), (5.0, 2.0)),
-            Line::new((5.0, 2.0), (7.0, 7.0)),
+            Line::new((5.0, 2.0), (7.0, 7.0))
         ]),
         vec![vec![
             Line::new((0.0, 2.0), (1.0, 2.0)),
@@ -84,9 +84,9 @@ fn test_clip_multi() {
     );
 
     assert_eq!(
-        rect.clip_segments(&vec![
+        rect.clip_segments(&[
             Line::new((1.0, 2.0), (5.0, 2.0)),
-            Line::new((5.0, 2.0), (3.0, 4.0)),
+            Line::new((5.0, 2.0), (3.0, 4.0))
         ]),
         vec![
             vec![Line::new((4.0, 3.0), (3.0, 4.0)),],
@@ -95,7 +95,7 @@ fn test_clip_multi() {
     );
 
     assert_eq!(
-        rect.clip_segments(&vec![
+        rect.clip_segments(&[
             Line::new((2.0, 4.0), (4.0, 2.0)),
             Line::new((4.0, 2.0), (2.0, 0.0))
         ])
@@ -104,7 +104,7 @@ fn test_clip_multi() {
     );
 
     assert_eq!(
-        rect.clip_segments(&vec![
+        rect.clip_segments(&[
             Line::new((2.0, 4.0), (6.0, 2.0)),
             Line::new((6.0, 2.0), (2.0, 0.0))
         ])
@@ -114,7 +114,7 @@ fn test_clip_multi() {
 
     // non-clipping segments
     assert!(rect
-        .clip_segments(&vec![
+        .clip_segments(&[
             Line::new((5.0, 2.0), (5.0, 4.0)),
             Line::new((5.0, 4.0), (7.0, 0.0))
         ])
@@ -143,10 +143,10 @@ fn test_self_crossing_segments() {
     let rect = Rect::new(0.0, 0.0, 4.0, 4.0);
 
     assert_eq!(
-        rect.clip_segments(&vec![
+        rect.clip_segments(&[
             Line::new((-1.0, -1.0), (5.0, 5.0)),
             Line::new((5.0, 5.0), (5.0, -1.0)),
-            Line::new((5.0, -1.0), (-1.0, 5.0)),
+            Line::new((5.0, -1.0), (-1.0, 5.0))
         ]),
         vec![
             vec![Line::new((4.0, 0.0), (0.0, 4.0))],
@@ -155,20 +155,177 @@ fn test_self_crossing_segments() {
     );
 }
 
+#[test]
+fn test_clip_segment_keeps_inside_end() {
+    let rect = Rect::new(0.1, 0.3, 4.7, 3.9);
+
+    // exiting through the corner, meeting both corner edges at points apart by rounding
+    let seg = Line::new(
+        (4.004976467578003, 0.4634957885320904),
+        (5.072759350483108, 5.743087346028831),
+    );
+    assert_eq!(rect.clip_segment(&seg).unwrap().start, seg.start);
+
+    let seg = Line::new(seg.end, seg.start);
+    assert_eq!(rect.clip_segment(&seg).unwrap().end, seg.end);
+}
+
+#[test]
+fn test_clip_segments_into() {
+    let rect = Rect::new(0.0, 0.0, 4.0, 4.0);
+    let crossing = [
+        Line::new((-1.0, -1.0), (5.0, 5.0)),
+        Line::new((5.0, 5.0), (5.0, -1.0)),
+        Line::new((5.0, -1.0), (-1.0, 5.0)),
+    ];
+    let inside = Rect::new(1.0, 1.0, 3.0, 3.0).lines;
+
+    // buffer is cleared and refilled on each call
+    let mut groups = vec![];
+    rect.clip_segments_into(&crossing, &mut groups);
+    assert_eq!(groups, rect.clip_segments(&crossing));
+    rect.clip_segments_into(&inside, &mut groups);
+    assert_eq!(groups, vec![inside.to_vec()]);
+    rect.clip_segments_into(&[], &mut groups);
+    assert!(groups.is_empty());
+}
+
 #[test]
 fn test_perimeter_index() {
     let rect = Rect::new(0.0, 0.0, 4.0, 4.0);
 
     // test perimeter index for coordinates
-    assert_eq!(rect.perimeter_index(&coord! {x: 0.0, y: 0.0}), 0.0,);
-    assert_eq!(rect.perimeter_index(&coord! {x: 3.0, y: 0.0}), 0.75);
-    assert_eq!(rect.perimeter_index(&coord! {x: 4.0, y: 0.0}), 1.0,);
-    assert_eq!(rect.perimeter_index(&coord! {x: 2.0, y: 0.0}), 0.5);
-    assert_eq!(rect.perimeter_index(&coord! {x: 0.0, y: 4.0}), 3.0,);
-    assert_eq!(rect.perimeter_index(&coord! {x: 0.0, y: 1.0}), 3.75,);
+    assert_eq!(rect.perimeter_index(&coord! {x: 0.0, y: 0.0}), Some(0.0));
+    assert_eq!(rect.perimeter_index(&coord! {x: 3.0, y: 0.0}), Some(0.75));
+    assert_eq!(rect.perimeter_index(&coord! {x: 4.0, y: 0.0}), Some(1.0));
+    assert_eq!(rect.perimeter_index(&coord! {x: 2.0, y: 0.0}), Some(0.5));
+    assert_eq!(rect.perimeter_index(&coord! {x: 0.0, y: 4.0}), Some(3.0));
+    assert_eq!(rect.perimeter_index(&coord! {x: 0.0, y: 1.0}), Some(3.75));
+
+    // points off the perimeter, inside the rect and on an edge line past the corner
+    assert_eq!(rect.perimeter_index(&coord! {x: 2.0, y: 2.0}), None);
+    assert_eq!(rect.perimeter_index(&coord! {x: 5.0, y: 0.0}), None);
 
     // test finding corner nodes between indexes
     assert_eq!(rect.corner_nodes_between(0.1, 1.1).len(), 1);
     assert_eq!(rect.corner_nodes_between(1.1, 0.1).len(), 3);
     assert_eq!(rect.corner_nodes_between(3.9, 0.1).len(), 1);
 }
+
+#[test]
+fn test_approx_eq() {
+    let a = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    assert!(a.approx_eq(&ClipRect::new(0.0, 1e-9, 4.0 - 1e-9, 4.0), 1e-6));
+    assert!(a.approx_eq(&ClipRect::new(0.0, 0.0, 4.0, 4.0), 0.0));
+    assert!(!a.approx_eq(&ClipRect::new(0.0, 0.0, 4.1, 4.0), 1e-6));
+    assert!(a.approx_eq(&ClipRect::new(4.0, 4.0, 0.0, 0.0), 0.0));
+
+    // same bounds with settings changing the output
+    let b = || ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    assert!(!a.approx_eq(&b().with_tolerance(1e-3), 1e-6));
+    assert!(!a.approx_eq(&b().with_precision_model(10.0), 1e-6));
+    assert!(!a.approx_eq(&b().with_winding(WindingOrder::CcwExterior), 1e-6));
+    assert!(!a.approx_eq(&b().with_strict(true), 1e-6));
+    assert!(!a.approx_eq(&b().with_nan_policy(NanPolicy::Skip), 1e-6));
+    assert!(!a.approx_eq(&b().with_join_touching(true), 1e-6));
+    assert!(!a.approx_eq(&b().with_degenerate_as_lines(true), 1e-6));
+    assert!(!a.approx_eq(&b().with_check_holes(true), 1e-6));
+    assert!(b()
+        .with_winding(WindingOrder::CcwExterior)
+        .approx_eq(&b().with_winding(WindingOrder::CcwExterior), 0.0));
+}
+
+#[test]
+fn test_edges() {
+    let rect = Rect::new(0.0, 0.0, 4.0, 2.0);
+
+    assert_eq!(rect.edge(0), &Line::new((0.0, 0.0), (4.0, 0.0)));
+    assert_eq!(rect.edge(1), &Line::new((4.0, 0.0), (4.0, 2.0)));
+    assert_eq!(rect.edge(2), &Line::new((4.0, 2.0), (0.0, 2.0)));
+    assert_eq!(rect.edge(3), &Line::new((0.0, 2.0), (0.0, 0.0)));
+
+    assert_eq!(rect.edge_normal(0), coord! {x: 0.0, y: 1.0});
+    assert_eq!(rect.edge_normal(1), coord! {x: -1.0, y: 0.0});
+    assert_eq!(rect.edge_normal(2), coord! {x: 0.0, y: -1.0});
+    assert_eq!(rect.edge_normal(3), coord! {x: 1.0, y: 0.0});
+
+    // normal points from edge midpoint towards the rect center
+    let center = coord! {x: 2.0, y: 1.0};
+    for i in 0..4 {
+        let e = rect.edge(i);
+        let mid = (e.start + e.end) / 2.0;
+        let n = rect.edge_normal(i);
+        let to_center = center - mid;
+        assert!(n.x * to_center.x + n.y * to_center.y > 0.0);
+    }
+}
+
+#[test]
+fn test_clip_segments_tagged() {
+    let rect = Rect::new(0.0, 0.0, 4.0, 4.0);
+
+    // line starting inside, leaving and ending inside again
+    assert_eq!(
+        rect.clip_segments_tagged(&[
+            Line::new((1.0, 2.0), (5.0, 2.0)),
+            Line::new((5.0, 2.0), (3.0, 4.0))
+        ]),
+        vec![
+            (vec![Line::new((4.0, 3.0), (3.0, 4.0))], true, false),
+            (vec![Line::new((1.0, 2.0), (4.0, 2.0))], false, true),
+        ]
+    );
+
+    // line end point on the boundary is not a crossing
+    assert_eq!(
+        rect.clip_segments_tagged(&[Line::new((2.0, 2.0), (4.0, 2.0))]),
+        vec![(vec![Line::new((2.0, 2.0), (4.0, 2.0))], false, false)]
+    );
+
+    // ring pieces always cross, unless the whole ring is inside
+    let ring = [
+        Line::new((2.0, 2.0), (6.0, 2.0)),
+        Line::new((6.0, 2.0), (2.0, 3.0)),
+        Line::new((2.0, 3.0), (2.0, 2.0)),
+    ];
+    assert!(rect
+        .clip_segments_tagged(&ring)
+        .iter()
+        .all(|(_, start, end)| *start && *end));
+
+    let ring = [
+        Line::new((1.0, 1.0), (3.0, 1.0)),
+        Line::new((3.0, 1.0), (1.0, 3.0)),
+        Line::new((1.0, 3.0), (1.0, 1.0)),
+    ];
+    assert_eq!(
+        rect.clip_segments_tagged(&ring),
+        vec![(ring.to_vec(), false, false)]
+    );
+}
+
+#[test]
+fn test_from_geo_rect() {
+    let clipper: ClipRect<f64> = geo_types::Rect::new((4.0, 0.0), (0.0, 2.0)).into();
+    assert!(clipper.approx_eq(&ClipRect::new(0.0, 0.0, 4.0, 2.0), 0.0));
+}
+
+#[test]
+fn test_swapped_bounds() {
+    let rect = Rect::new(4.0, 4.0, 0.0, 0.0);
+    assert_eq!((rect.x0, rect.y0, rect.x1, rect.y1), (0.0, 0.0, 4.0, 4.0));
+    assert_eq!(rect.edge(0), &Line::new((0.0, 0.0), (4.0, 0.0)));
+
+    let g = geo::Geometry::Polygon(geo::wkt!(POLYGON((-1. 1.,5. 1.,5. 3.,-1. 3.,-1. 1.))));
+    for (x0, y0, x1, y1) in [
+        (4.0, 4.0, 0.0, 0.0),
+        (0.0, 4.0, 4.0, 0.0),
+        (4.0, 0.0, 0.0, 4.0),
+    ] {
+        assert_eq!(
+            ClipRect::new(x0, y0, x1, y1).clip(&g),
+            ClipRect::new(0.0, 0.0, 4.0, 4.0).clip(&g)
+        );
+    }
+}
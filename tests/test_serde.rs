@@ -0,0 +1,19 @@
+#![cfg(feature = "serde")]
+use klippa::*;
+
+#[test]
+fn test_serde_roundtrip() {
+    let rect = ClipRect::new(0.0, 1.0, 4.0, 5.0);
+    let json = serde_json::to_string(&rect).unwrap();
+    assert_eq!(json, r#"{"x0":0.0,"y0":1.0,"x1":4.0,"y1":5.0}"#);
+
+    let back: ClipRect<f64> = serde_json::from_str(&json).unwrap();
+    assert!(back.approx_eq(&rect, 0.0));
+
+    // swapped bounds are normalized
+    let swapped: ClipRect<f64> =
+        serde_json::from_str(r#"{"x0":4.0,"y0":5.0,"x1":0.0,"y1":1.0}"#).unwrap();
+    assert!(swapped.approx_eq(&rect, 0.0));
+
+    assert!(serde_json::from_str::<ClipRect<f64>>(r#"{"x0":4.0}"#).is_err());
+}
@@ -0,0 +1,78 @@
+use geo::{orient::Direction, wkt, Geometry, MultiPolygon, Orient, Polygon};
+use klippa::*;
+
+#[test]
+fn test_stream_complex() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = wkt!(POLYGON((-0.28564453125000056 3.743671274749744,3.7573242187499987 3.6888551431470518,3.7573242187499987 2.7235830833483874,-0.20874023437500058 2.8113711933311407,-0.20874023437500058 3.0417830279332634,3.4716796875000004 2.975955935944782,3.4716796875000004 3.2282710112526445,-0.28564453125000056 3.2282710112526445,-0.30761718749999994 2.097919733594921,3.6804199218749987 1.9991059831233287,3.669433593749999 1.680667133750731,0.5053710937499998 1.6587038068676208,0.49438476562500006 1.2633253574893217,4.482421875 1.3621763466641852,4.39453125 0.8349313860427259,0.1867675781250001 0.9557662177941495,0.16479492187499967 1.7794990011582144,1.8127441406249998 1.8344033244935218,-0.2966308593750003 1.9002862838753884,-0.1867675781250001 0.5712795966325501,3.3508300781249996 0.41747677467076016,3.482666015625 -0.5383221578577064,2.801513671875 -0.6042368463810561,2.867431640625 0.08789059053081871,-0.5712890625000001 0.23071226715249793,-0.6042480468750002 3.721745231068965,-0.28564453125000056 3.743671274749744))).orient(Direction::Default);
+
+    let mut stream = StreamingRingClipper::new(&rect);
+    for c in g.exterior().coords() {
+        stream.push_coord(*c);
+    }
+    let streamed: MultiPolygon = stream
+        .finish()
+        .into_iter()
+        .map(|ls| Polygon::new(ls, vec![]))
+        .collect();
+
+    assert_eq!(
+        Geometry::MultiPolygon(streamed),
+        rect.clip(&Geometry::Polygon(g)).unwrap()
+    );
+}
+
+#[test]
+fn test_stream_full_cover() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = wkt!(POLYGON((-1. -1., -1. 5., 5. 5., 5. -1., -1. -1.))).orient(Direction::Default);
+
+    let mut stream = StreamingRingClipper::new(&rect);
+    for c in g.exterior().coords() {
+        stream.push_coord(*c);
+    }
+    let rings = stream.finish();
+
+    assert_eq!(
+        Geometry::Polygon(Polygon::new(rings[0].clone(), vec![])),
+        rect.clip(&Geometry::Polygon(g)).unwrap()
+    );
+}
+
+#[test]
+fn test_stream_clockwise() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = wkt!(POLYGON((1. 1.,1. 6.,3. 6.,3. 1.,1. 1.)));
+
+    let mut stream = StreamingRingClipper::new(&rect);
+    for c in g.exterior().coords() {
+        stream.push_coord(*c);
+    }
+    let rings = stream.finish();
+
+    assert_eq!(rings.len(), 1);
+    assert_eq!(
+        Geometry::Polygon(Polygon::new(rings[0].clone(), vec![])),
+        rect.clip(&Geometry::Polygon(g)).unwrap()
+    );
+}
+
+#[test]
+fn test_stream_near_vertices() {
+    // vertices closer than 1e-5 are kept
+    let rect = ClipRect::new(0.0, 0.0, 10.0, 10.0).with_tolerance(1e-9);
+    let g = wkt!(POLYGON((-1. 1.,1.000003 1.,1.000003 1.000003,-1. 1.000003,-1. 1.)));
+
+    let mut stream = StreamingRingClipper::new(&rect);
+    for c in g.exterior().coords() {
+        stream.push_coord(*c);
+    }
+    let rings = stream.finish();
+
+    assert_eq!(rings.len(), 1);
+    assert_eq!(
+        Geometry::Polygon(Polygon::new(rings[0].clone(), vec![])),
+        rect.clip(&Geometry::Polygon(g)).unwrap()
+    );
+    assert_eq!(rings[0].0.len(), 5);
+}
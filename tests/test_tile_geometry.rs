@@ -0,0 +1,137 @@
+use geo::{wkt, Geometry, LineString, MultiPolygon, Polygon};
+use klippa::*;
+
+// Reconstructs polygons from tile geometry in clip rect coordinates
+fn to_polygons(tile: &TileGeometry, rect: &Rect<f64>, extent: f64) -> MultiPolygon {
+    let mut polys: Vec<Polygon> = vec![];
+
+    for (range, kind) in tile.rings.iter().zip(&tile.windings) {
+        let mut ls: LineString = tile.coords[range.clone()]
+            .iter()
+            .map(|[x, y]| {
+                (
+                    rect.x0 + *x as f64 / extent * (rect.x1 - rect.x0),
+                    rect.y1 - *y as f64 / extent * (rect.y1 - rect.y0),
+                )
+            })
+            .collect();
+        ls.close();
+
+        match kind {
+            Winding::Outer => polys.push(Polygon::new(ls, vec![])),
+            Winding::Hole => polys.last_mut().unwrap().interiors_push(ls),
+        }
+    }
+
+    polys.into()
+}
+
+#[test]
+fn test_tile_geometry_polygon() {
+    let rect = ClipRect::new(-1.0, 1.5, 5.0, 5.0);
+    let g = Geometry::Polygon(
+        wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.),(1. 1.,1. 3.,3. 3.,3. 1.,1. 1.))),
+    );
+
+    let tile = rect.clip_to_tile_geometry(&g, 4096).unwrap().unwrap();
+    assert_eq!(tile.windings, vec![Winding::Outer, Winding::Hole]);
+    assert!(tile.lines.is_empty() && tile.points.is_empty());
+    assert_eq!(tile.rings, vec![0..4, 4..8]);
+
+    // y axis points down
+    assert_eq!(tile.coords[0], [3413.3333, 4096.0]);
+
+    let clip: Polygon = rect.clip(&g).unwrap().try_into().unwrap();
+    let polys = to_polygons(&tile, rect.rect(), 4096.0);
+    assert_eq!(polys.0.len(), 1);
+
+    for (a, b) in polys.0[0].exterior().coords().zip(clip.exterior().coords()) {
+        assert!((a.x - b.x).abs() < 1e-5 && (a.y - b.y).abs() < 1e-5);
+    }
+    for (a, b) in polys.0[0].interiors()[0]
+        .coords()
+        .zip(clip.interiors()[0].coords())
+    {
+        assert!((a.x - b.x).abs() < 1e-5 && (a.y - b.y).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_tile_geometry_lines() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = Geometry::LineString(wkt!(LINESTRING(-1. 1.,2. 1.,2. 5.)));
+
+    let tile = rect.clip_to_tile_geometry(&g, 4).unwrap().unwrap();
+    assert_eq!(tile.lines, vec![0..3]);
+    assert!(tile.rings.is_empty());
+    assert_eq!(tile.coords, vec![[0.0, 3.0], [2.0, 3.0], [2.0, 0.0]]);
+
+    let g = Geometry::LineString(wkt!(LINESTRING(5. 5.,6. 6.)));
+    assert_eq!(rect.clip_to_tile_geometry(&g, 4), Ok(None));
+}
+
+#[test]
+fn test_tile_geometry_collection() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = Geometry::GeometryCollection(
+        vec![
+            Geometry::Point(wkt!(POINT(1. 1.))),
+            Geometry::GeometryCollection(
+                vec![Geometry::LineString(wkt!(LINESTRING(-1. 1.,2. 1.)))].into(),
+            ),
+        ]
+        .into(),
+    );
+
+    let tile = rect.clip_to_tile_geometry(&g, 4).unwrap().unwrap();
+    assert_eq!(tile.points, vec![[1.0, 3.0]]);
+    assert_eq!(tile.lines, vec![0..2]);
+    assert_eq!(tile.coords, vec![[0.0, 3.0], [2.0, 3.0]]);
+}
+
+#[test]
+fn test_tile_geometry_degenerate() {
+    let g = Geometry::Point(wkt!(POINT(1. 1.)));
+
+    for rect in [
+        ClipRect::new(0.0, 0.0, 0.0, 4.0),
+        ClipRect::new(0.0, 1.0, 4.0, 1.0),
+        ClipRect::new(1.0, 1.0, 1.0, 1.0),
+    ] {
+        assert_eq!(
+            rect.clip_to_tile_geometry(&g, 4096),
+            Err(ClipError::DegenerateRect)
+        );
+    }
+}
+
+#[test]
+fn test_f32_buffer() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+    let g = wkt!(POLYGON((1. 6.,1. 2.,1.5 2.,1.5 5.,2.5 5.,2.5 2.,3. 2.,3. 6.,1. 6.)));
+
+    let (buffer, counts) = rect.clip_to_f32_buffer(&g, 4.0).unwrap();
+    assert_eq!(buffer.len(), 2 * counts.iter().sum::<usize>());
+
+    // rings rebuilt from the buffer match the clip output
+    let mut vertices = buffer.chunks(2).map(|v| (v[0] as f64, 4.0 - v[1] as f64));
+    let rings: Vec<LineString> = counts
+        .iter()
+        .map(|n| {
+            let mut ls: LineString = vertices.by_ref().take(*n).collect();
+            ls.close();
+            ls
+        })
+        .collect();
+
+    let expected: Vec<LineString> = match rect.clip(&Geometry::Polygon(g)) {
+        Some(Geometry::MultiPolygon(mp)) => mp.0.iter().map(|p| p.exterior().clone()).collect(),
+        g => panic!("expected multipolygon, got {g:?}"),
+    };
+    assert_eq!(rings, expected);
+
+    assert_eq!(
+        rect.clip_to_f32_buffer(&wkt!(POLYGON((5. 5.,6. 5.,6. 6.,5. 5.))), 4.0),
+        None
+    );
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "tiles")]
+
+use klippa::*;
+
+fn assert_bounds(rect: &Rect<f64>, bounds: (f64, f64, f64, f64)) {
+    let eps = 1e-6;
+    assert!((rect.x0 - bounds.0).abs() < eps, "x0={}", rect.x0);
+    assert!((rect.y0 - bounds.1).abs() < eps, "y0={}", rect.y0);
+    assert!((rect.x1 - bounds.2).abs() < eps, "x1={}", rect.x1);
+    assert!((rect.y1 - bounds.3).abs() < eps, "y1={}", rect.y1);
+}
+
+#[test]
+fn test_from_tile() {
+    let e = 20037508.342789244;
+
+    let clip = ClipRect::<f64>::from_tile(0, 0, 0);
+    assert_bounds(clip.rect(), (-e, -e, e, e));
+
+    let clip = ClipRect::<f64>::from_tile(1, 0, 0);
+    assert_bounds(clip.rect(), (-e, 0.0, 0.0, e));
+
+    let clip = ClipRect::<f64>::from_tile(1, 1, 1);
+    assert_bounds(clip.rect(), (0.0, -e, e, 0.0));
+
+    let clip = ClipRect::<f64>::from_tile(2, 3, 1);
+    assert_bounds(clip.rect(), (e / 2.0, 0.0, e, e / 2.0));
+}
+
+#[test]
+fn test_from_geohash() {
+    let clip = ClipRect::<f64>::from_geohash("ezs42").unwrap();
+    assert_bounds(
+        clip.rect(),
+        (-5.625, 42.5830078125, -5.5810546875, 42.626953125),
+    );
+
+    let clip = ClipRect::<f64>::from_geohash("u").unwrap();
+    assert_bounds(clip.rect(), (0.0, 45.0, 45.0, 90.0));
+
+    assert!(ClipRect::<f64>::from_geohash("ezs4a").is_none());
+}
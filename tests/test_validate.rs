@@ -0,0 +1,54 @@
+#![cfg(feature = "validate")]
+
+use geo::{orient::Direction, wkt, Geometry, Orient};
+use klippa::validate::ValidationError;
+use klippa::*;
+
+#[test]
+fn test_validate_polygons() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    let polys = [
+        wkt!(POLYGON((-1.1425781250000002 0.010986328057683181,-0.7031250000000007 0.4394488164139716,0.46142578124999994 0.40649073057389273,0.7360839843749998 -0.4833927027897005,0.0109863281249997 -0.9557662177941353,-1.1425781250000002 0.010986328057683181))),
+        wkt!(POLYGON((0.2526855468749994 4.937724274302482,5.174560546875 0.0549316322096729,3.3508300781249996 -1.0436434559084802,-1.3073730468750009 4.039617826768435,0.2526855468749994 4.937724274302482))),
+        wkt!(POLYGON((0.6042480468750002 4.412136788910175,0.7031249999999996 -0.3845185979490111,1.7028808593749993 -0.34057416628374426,1.4062500000000002 4.3683204208762305,2.142333984375 4.401182938278325,2.373046875 -0.3515602939922502,3.779296875 -0.3405741662837584,3.427734375 4.412136788910175,0.6042480468750002 4.412136788910175))),
+        wkt!(POLYGON((-1. -1., -1. 5., 5. 5., 5. -1., -1. -1.))),
+        wkt!(POLYGON((-0.7580566406250002 4.4559505716470795,4.438476562499999 4.423090477960898,4.416503906249999 -0.5822653680900771,0.7690429687499999 -0.4064907305738785,-0.7910156250000003 1.4500404973608028,-0.7580566406250002 4.4559505716470795),(3.9 3.9,3.3 3.43,3.2 4.2,3.9 3.9))),
+    ];
+
+    for g in polys {
+        let g = g.orient(Direction::Default);
+        assert!(rect.clip_checked(&Geometry::Polygon(g)).unwrap().is_some());
+    }
+}
+
+#[test]
+fn test_validate_holes() {
+    let rect = ClipRect::new(-1.0, 1.5, 5.0, 5.0);
+    let g = wkt!(POLYGON((0. 0.,4. 0.,4. 4.,0. 4.,0. 0.),(1. 1.,1. 2.9999999999999716,3. 2.9999999999999716,3. 1.,1. 1.))).orient(Direction::Default);
+
+    assert!(rect.clip_checked(&Geometry::Polygon(g)).is_ok());
+}
+
+#[test]
+fn test_validate_errors() {
+    let rect = ClipRect::new(-10.0, -10.0, 10.0, 10.0);
+
+    // bowtie
+    let g = wkt!(POLYGON((0. 0.,4. 4.,4. 0.,0. 4.,0. 0.)));
+    assert!(matches!(
+        rect.clip_checked(&Geometry::Polygon(g)),
+        Err(ValidationError::SelfIntersection { .. })
+    ));
+
+    // clockwise exterior, kept as is without orienting
+    let rect = rect.with_winding(WindingOrder::Preserve);
+    let g = wkt!(POLYGON((0. 0.,0. 4.,4. 4.,4. 0.,0. 0.)));
+    assert_eq!(
+        rect.clip_checked(&Geometry::Polygon(g)),
+        Err(ValidationError::WrongWinding {
+            polygon: 0,
+            ring: 0
+        })
+    );
+}
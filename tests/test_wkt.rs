@@ -0,0 +1,52 @@
+#![cfg(feature = "wkt")]
+
+use klippa::*;
+
+#[test]
+fn test_clip_wkt_str() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // contained polygon is passed through with its original digits
+    let wkt = "POLYGON((1.10 1.000,3.0 1.25,3.00 3.0,1.10 1.000))";
+    assert_eq!(rect.clip_wkt_str(wkt).unwrap().as_deref(), Some(wkt));
+
+    // clockwise polygon is oriented by a winding order, as with `clip_wkt`
+    let oriented = ClipRect::new(0.0, 0.0, 4.0, 4.0).with_winding(WindingOrder::CcwExterior);
+    let wkt = "POLYGON((1 1,1 2,2 2,2 1,1 1))";
+    let expected = Some("POLYGON((1 1,2 1,2 2,1 2,1 1))".to_string());
+    assert_eq!(oriented.clip_wkt_str(wkt).unwrap(), expected);
+    assert_eq!(oriented.clip_wkt(wkt).unwrap(), expected);
+
+    assert_eq!(
+        rect.clip_wkt_str("LINESTRING(-1 2,5 2)")
+            .unwrap()
+            .as_deref(),
+        Some("LINESTRING(0 2,4 2)")
+    );
+    assert_eq!(rect.clip_wkt_str("POINT(5 5)").unwrap(), None);
+    assert!(matches!(
+        rect.clip_wkt_str("POLYGON((1 1,"),
+        Err(ClipError::Parse(_))
+    ));
+}
+
+#[test]
+fn test_clip_wkt() {
+    let rect = ClipRect::new(0.0, 0.0, 4.0, 4.0);
+
+    // output is always rendered, also for contained input
+    assert_eq!(
+        rect.clip_wkt("POLYGON((1.10 1.000,3.0 1.25,3.00 3.0,1.10 1.000))")
+            .unwrap()
+            .as_deref(),
+        Some("POLYGON((1.1 1,3 1.25,3 3,1.1 1))")
+    );
+    assert_eq!(
+        rect.clip_wkt("POLYGON((-1 -1,5 -1,5 2,-1 2,-1 -1))")
+            .unwrap()
+            .as_deref(),
+        Some("POLYGON((4 2,0 2,0 0,4 0,4 2))")
+    );
+    assert_eq!(rect.clip_wkt("POINT(5 5)").unwrap(), None);
+    assert!(matches!(rect.clip_wkt("POINT(1"), Err(ClipError::Parse(_))));
+}